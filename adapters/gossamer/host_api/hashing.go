@@ -20,6 +20,8 @@
 package host_api
 
 import (
+	"bytes"
+	"encoding/hex"
 	"fmt"
 
 	"github.com/ChainSafe/gossamer/lib/runtime"
@@ -28,8 +30,20 @@ import (
 
 // Simple wrapper to test hash function that input and output byte arrays
 func test_hashing(r runtime.Instance, name, input string) error {
+	return test_hashing_data(r, name, []byte(input))
+}
+
+// Hash repeated byte, to cover empty inputs and inputs too large to pass as argument
+func test_hashing_repeated(r runtime.Instance, name, byteHex string, count uint32) error {
+	b, err := hex.DecodeString(byteHex)
+	if err != nil {
+		return fmt.Errorf("Invalid byte: %w", err)
+	}
+	return test_hashing_data(r, name, bytes.Repeat(b, int(count)))
+}
 
-	enc, err := scale.Encode([]byte(input))
+func test_hashing_data(r runtime.Instance, name string, data []byte) error {
+	enc, err := scale.Encode(data)
 	if err != nil {
 		return fmt.Errorf("Encoding failed: %w", err)
 	}
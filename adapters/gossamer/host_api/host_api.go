@@ -18,6 +18,7 @@
 package host_api
 
 import (
+	"encoding/hex"
 	"errors"
 	"flag"
 	"fmt"
@@ -51,8 +52,12 @@ func ToUint32(input string) uint32 {
 	return uint32(output)
 }
 
-// Return absolute runtime patch
+// Return absolute runtime patch, can be overridden by WASM_BLOB_PATH
 func GetRuntimePath() string {
+	if blob, ok := os.LookupEnv("WASM_BLOB_PATH"); ok {
+		return blob
+	}
+
 	dir, err := os.Getwd()
 	if err != nil {
 		panic("failed to get current working directory")
@@ -60,15 +65,51 @@ func GetRuntimePath() string {
 	return path.Join(dir, RELATIVE_WASM_ADAPTER_PATH)
 }
 
+// Repeatable flag collecting initial state entries
+type stateEntries []string
+
+func (e *stateEntries) String() string {
+	return strings.Join(*e, " ")
+}
+
+func (e *stateEntries) Set(value string) error {
+	*e = append(*e, value)
+	return nil
+}
+
+// Decode initial state entry of hex encoded, colon separated key and value
+func parseStateEntry(entry string) ([]byte, []byte, error) {
+	parts := strings.Split(entry, ":")
+	if len(parts) != 2 {
+		return nil, nil, fmt.Errorf("State entry '%s' is not of form KEY:VALUE", entry)
+	}
+	key, err := hex.DecodeString(strings.TrimPrefix(parts[0], "0x"))
+	if err != nil {
+		return nil, nil, fmt.Errorf("Invalid state entry key: %w", err)
+	}
+	value, err := hex.DecodeString(strings.TrimPrefix(parts[1], "0x"))
+	if err != nil {
+		return nil, nil, fmt.Errorf("Invalid state entry value: %w", err)
+	}
+	return key, value, nil
+}
+
 // Main hostapi test argument parser
 func ProcessHostApiCommand(args []string) {
 
 	// List of expected flags
 	functionTextPtr := flag.String("function", "", "Function to call (required).")
 	inputTextPtr := flag.String("input", "", "Input to pass on call.")
+	inputFilePtr := flag.String("input-file", "", "Read input to pass on call from file (for large inputs).")
 
 	environmentTextPtr := flag.String("environment", "wasmer", "WASM environment to use:  wasmer, wasmtime or life")
 	runtimeTextPtr := flag.String("runtime", GetRuntimePath(), "Override path to hostapi test runtime to use.")
+	rawPtr := flag.Bool("raw", false, "Call host function directly with raw arguments (not supported).")
+	flag.Bool("classify-trap", false, "Print class of traps of raw calls (not supported).")
+
+	var storageEntries, childStorageEntries stateEntries
+	flag.Var(&storageEntries, "storage", "Initial entry of top storage (hex encoded KEY:VALUE, repeatable).")
+	flag.Var(&childStorageEntries, "child-storage", "Initial entry of child storage (not supported).")
 
 	// Parse provided argument list
 	flag.CommandLine.Parse(args)
@@ -84,12 +125,35 @@ func ProcessHostApiCommand(args []string) {
 		os.Exit(1)
 	}
 
-	function := *functionTextPtr
+	// Calling host functions with raw arguments is not supported
+	if *rawPtr {
+		fmt.Println("Not implemented: raw", *functionTextPtr)
+		os.Exit(C.EOPNOTSUPP)
+	}
+
+	// Initial child storage is not supported
+	if len(childStorageEntries) > 0 {
+		fmt.Println("Not implemented: child-storage", *functionTextPtr)
+		os.Exit(C.EOPNOTSUPP)
+	}
+
+	// Large inputs are passed in a file instead of the argument list, one
+	// argument per line
 	inputs := strings.Split(*inputTextPtr, ",")
+	if *inputFilePtr != "" {
+		content, err := ioutil.ReadFile(*inputFilePtr)
+		if err != nil {
+			fmt.Println("Failed to read input file: ", err)
+			os.Exit(1)
+		}
+		inputs = strings.Split(strings.TrimSuffix(string(content), "\n"), "\n")
+	}
+
+	function := *functionTextPtr
 	environment := *environmentTextPtr
 	runtimePath := *runtimeTextPtr
 
-	err := executeHostApiTest(function, inputs, environment, runtimePath)
+	err := executeHostApiTest(function, inputs, storageEntries, environment, runtimePath)
 
 	if err != nil {
 		if _, ok := err.(MissingImplementation); ok {
@@ -102,7 +166,7 @@ func ProcessHostApiCommand(args []string) {
 }
 
 // Main hostapi test executor
-func executeHostApiTest(function string, inputs []string, environment, runtimePath string) error {
+func executeHostApiTest(function string, inputs, storageEntries []string, environment, runtimePath string) error {
 	// Initialize storage
 	store, err := storage.NewTrieState(nil)
 	if err != nil {
@@ -112,6 +176,15 @@ func executeHostApiTest(function string, inputs []string, environment, runtimePa
 	store.Set([]byte(":code"), []byte{})
 	store.Set([]byte(":heappages"), []byte{8, 0, 0, 0, 0, 0, 0, 0})
 
+	// Initialize state as declared by the test vector
+	for _, entry := range storageEntries {
+		key, value, err := parseStateEntry(entry)
+		if err != nil {
+			return err
+		}
+		store.Set(key, value)
+	}
+
 	// Initialize runtime environment..
 	var rtm runtime.Instance
 	switch environment {
@@ -211,6 +284,16 @@ func executeHostApiTest(function string, inputs []string, environment, runtimePa
 	     "ext_hashing_twox_256_version_1":
 		return test_hashing(rtm, function, inputs[0])
 
+	case "test_hashing_blake2_128_repeated",
+	     "test_hashing_blake2_256_repeated",
+	     "test_hashing_keccak_256_repeated",
+	     "test_hashing_sha2_256_repeated",
+	     "test_hashing_twox_64_repeated",
+	     "test_hashing_twox_128_repeated",
+	     "test_hashing_twox_256_repeated":
+		name := strings.Replace(strings.TrimSuffix(function, "_repeated"), "test_", "ext_", 1) + "_version_1"
+		return test_hashing_repeated(rtm, name, inputs[0], ToUint32(inputs[1]))
+
 	// test storage api
 	case "test_storage_init":
 		return test_storage_init(rtm)
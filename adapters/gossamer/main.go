@@ -27,7 +27,7 @@ import (
 )
 
 func usage() {
-	fmt.Println("usage: ", os.Args[0], " <scale-codec|state-trie|host-api> <subcommand-args>")
+	fmt.Println("usage: ", os.Args[0], " <scale-codec|state-trie|host-api|healthcheck> <subcommand-args>")
 }
 
 func main() {
@@ -45,6 +45,8 @@ func main() {
 		ProcessStateTrieCommand(os.Args[2:])
 	case "host-api":
 		host_api.ProcessHostApiCommand(os.Args[2:])
+	case "healthcheck":
+		fmt.Println("ok")
 	default:
 		usage()
 		os.Exit(1)
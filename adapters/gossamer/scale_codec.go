@@ -27,6 +27,9 @@ import (
 	"os"
 )
 
+// #include <errno.h>
+import "C"
+
 func ProcessScaleCodecCommand(scale_codec_args []string) {
 	//here we need to parse the args related to scale_codec
 
@@ -49,6 +52,12 @@ func ProcessScaleCodecCommand(scale_codec_args []string) {
 	switch scale_codec_args[0] {
 	case "encode":
 		encodeCommand.Parse(scale_codec_args[1:])
+	case "encode-compact", "decode-compact", "option-bool", "sparse-enum",
+		"perbill", "percent", "fixed-u128", "mortal-era", "decode-era",
+		"multi-address", "multi-signer", "multi-signature", "signed-payload",
+		"digest-item", "header", "justifications", "extrinsics-root":
+		fmt.Println("Not implemented: ", scale_codec_args[0])
+		os.Exit(C.EOPNOTSUPP)
 	default:
 		flag.PrintDefaults()
 		os.Exit(1)
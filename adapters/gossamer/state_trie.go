@@ -32,6 +32,9 @@ import (
 	"github.com/go-yaml/yaml"
 )
 
+// #include <errno.h>
+import "C"
+
 func ProcessStateTrieCommand(command_args []string) {
 
 	// Subcommands
@@ -55,6 +58,9 @@ func ProcessStateTrieCommand(command_args []string) {
 
 	case "insert-and-delete":
 		stateRootCommand.Parse(command_args[1:])
+	case "trie-nodes":
+		fmt.Println("Not implemented: ", command_args[0])
+		os.Exit(C.EOPNOTSUPP)
 	default:
 		flag.PrintDefaults()
 		os.Exit(1)
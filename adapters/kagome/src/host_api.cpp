@@ -19,6 +19,8 @@
 
 #include "assert.hpp"
 
+#include <fstream>
+
 #include <boost/optional.hpp>
 #include <boost/program_options.hpp>
 
@@ -38,11 +40,17 @@ HostApiCommandArgs extractHostApiArgs(int argc, char **argv) {
   po::options_description desc("HostApi codec related tests\nAllowed options:");
   boost::optional<std::string> function;
   boost::optional<std::string> inputStr;
+  boost::optional<std::string> inputFile;
 
   desc.add_options()
     ("help", "produce help message")
     ("function", po::value(&function), "specify a function")
-    ("input", po::value(&inputStr), "specify a input");
+    ("input", po::value(&inputStr), "specify a input")
+    ("input-file", po::value(&inputFile), "read input from file (for large inputs)")
+    ("storage", po::value<std::vector<std::string>>(), "initial top storage entry (KEY:VALUE)")
+    ("child-storage", po::value<std::vector<std::string>>(), "initial child storage entry (CHILD:KEY:VALUE)")
+    ("raw", "call function directly with raw arguments")
+    ("classify-trap", "print class of traps of raw calls");
 
   po::positional_options_description pd;
   pd.add("function", 1);
@@ -54,13 +62,30 @@ HostApiCommandArgs extractHostApiArgs(int argc, char **argv) {
   );
   po::notify(vm);
 
+  if (vm.count("raw")) {
+    throw NotImplemented(); // TODO not implemented
+  }
+
+  if (vm.count("storage") || vm.count("child-storage")) {
+    throw NotImplemented(); // TODO not implemented
+  }
+
   BOOST_ASSERT_MSG(function, "Function is not stated");
 
   HostApiCommandArgs args;
 
   args.function = *function;
 
-  if (inputStr) {
+  // Large inputs are passed in a file instead of the argument list
+  if (inputFile) {
+    // One argument per line
+    std::ifstream file(*inputFile);
+    BOOST_ASSERT_MSG(file, "Failed to read input file");
+    std::string inputElement;
+    while (std::getline(file, inputElement)) {
+      args.input.push_back(inputElement);
+    }
+  } else if (inputStr) {
     std::stringstream inputStream(*inputStr);
     std::string inputElement;
     while (std::getline(inputStream, inputElement, ',')) {
@@ -125,6 +150,62 @@ void processHostApiCommands(const HostApiCommandArgs &args) {
                          BOOST_ASSERT(args.size() == 4);
                          storage::processNextKey(args[0], args[1], args[2], args[3]);
                        });
+  router.addSubcommand("test_storage_clear_prefix_limit_root",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_transaction_depth",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_storage_transaction_commit_unbalanced",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_storage_transaction_rollback_unbalanced",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_root_changes_root",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_clear_prefix_limit_count",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_exists_read_partial",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_next_key_order",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_append_existing",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_overlay_reads",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_storage_long_key",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_storage_transaction_restore",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
 
   // test child storage (currentyl no upstream support)
   router.addSubcommand("ext_default_child_storage_set_version_1",
@@ -167,6 +248,14 @@ void processHostApiCommands(const HostApiCommandArgs &args) {
                        [](const std::vector<std::string> &args) {
                          throw NotImplemented(); // TODO not implemented
                        });
+  router.addSubcommand("test_child_storage_prefix_root",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // Child storage not implemented upstream
+                       });
+  router.addSubcommand("test_child_storage_kill_root",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // Child storage not implemented upstream
+                       });
 
   // test crypto api
   router.addSubcommand("ext_crypto_ed25519_public_keys_version_1",
@@ -210,6 +299,46 @@ void processHostApiCommands(const HostApiCommandArgs &args) {
                          BOOST_ASSERT(args.size() == 2);
                          crypto::processSr25519Verify(args[0], args[1]);
                        });
+  router.addSubcommand("test_crypto_ed25519_generate_randomness",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_ed25519_verify_encodings",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_sr25519_generate_randomness",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_ed25519_generate_seeds",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_sr25519_generate_seeds",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_ed25519_keystore_concurrency",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_sr25519_keystore_concurrency",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_secp256k1_ecdsa_recover",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_secp256k1_ecdsa_recover_compressed",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_crypto_batch_verify",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
 
   // test hashing api
   router.addSubcommand("ext_hashing_blake2_128_version_1",
@@ -248,6 +377,42 @@ void processHostApiCommands(const HostApiCommandArgs &args) {
                          hashing::processHashFunction("twox", 32, args[0]);
                        });
 
+  router.addSubcommand("test_hashing_blake2_128_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("blake2", 16, args[0], args[1]);
+                       });
+  router.addSubcommand("test_hashing_blake2_256_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("blake2", 32, args[0], args[1]);
+                       });
+  router.addSubcommand("test_hashing_keccak_256_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("keccak", 32, args[0], args[1]);
+                       });
+  router.addSubcommand("test_hashing_sha2_256_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("sha2", 32, args[0], args[1]);
+                       });
+  router.addSubcommand("test_hashing_twox_64_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("twox", 8, args[0], args[1]);
+                       });
+  router.addSubcommand("test_hashing_twox_128_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("twox", 16, args[0], args[1]);
+                       });
+  router.addSubcommand("test_hashing_twox_256_repeated",
+                       [](const std::vector<std::string> &args) {
+                         BOOST_ASSERT(args.size() == 2);
+                         hashing::processHashFunctionRepeated("twox", 32, args[0], args[1]);
+                       });
+
   // test allocator api
   router.addSubcommand("ext_allocator_malloc_version_1",
                        [](const std::vector<std::string> &args) {
@@ -259,6 +424,26 @@ void processHostApiCommands(const HostApiCommandArgs &args) {
                          BOOST_ASSERT(args.size() == 1);
                          allocator::processMallocFree(args[0]);
                        });
+  router.addSubcommand("test_allocator_zero_size",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_allocator_roundtrip",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_allocator_exhaust",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_allocator_double_free",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_allocator_fragmentation",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
 
   // test trie api
   router.addSubcommand("ext_trie_blake2_256_root_version_1",
@@ -272,6 +457,172 @@ void processHostApiCommands(const HostApiCommandArgs &args) {
                          trie::processOrderedRoot(args[0], args[1], args[2]);
                        });
 
+  router.addSubcommand("ext_trie_blake2_256_verify_proof_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_trie_keccak_256_verify_proof_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_trie_blake2_256_root_generated",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_trie_blake2_256_ordered_root_generated",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_trie_long_key",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  // test misc api
+  router.addSubcommand("test_misc_print_utf8_encodings",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_misc_print_utf8",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_misc_print_long",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_misc_print_hex",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_misc_print_num",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  router.addSubcommand("test_misc_runtime_version",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  // test module shape of the runtime
+  router.addSubcommand("test_wasm_probe",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  // test logging api
+  router.addSubcommand("test_logging_log_encodings",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_logging_log",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  // test offchain api
+  router.addSubcommand("ext_offchain_is_validator_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_offchain_random_seed_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_offchain_timestamp_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_offchain_sleep_until_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_offchain_local_storage_set_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_offchain_local_storage_compare_and_set_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_offchain_http",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_offchain_http_deadlines",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_offchain_capabilities",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  // test sandbox api
+  router.addSubcommand("ext_sandbox_instantiate_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_sandbox_invoke_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_sandbox_instance_teardown_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_sandbox_memory_new_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_sandbox_memory_get_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_sandbox_memory_set_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("ext_sandbox_memory_teardown_version_1",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_sandbox_memory_limit",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_sandbox_nested",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
+  // test block builder (tester runtime)
+  router.addSubcommand("test_block_weight_accounting",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_block_length_accounting",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_block_initialization",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_transactional_depth",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+  router.addSubcommand("test_tester_api_version",
+                       [](const std::vector<std::string> &args) {
+                         throw NotImplemented(); // TODO not implemented
+                       });
+
   std::string commands_list = "Valid function are: ";
   for (auto &&name : router.collectSubcommandNames()) {
     commands_list += name;
@@ -43,4 +43,13 @@ namespace hashing {
     std::cout << hash.toHex() << std::endl;
   }
 
+  void processHashFunctionRepeated(
+    const std::string_view name, uint32_t size,
+    const std::string &byte, const std::string &count
+  ) {
+    std::string input(std::stoul(count), char(std::stoul(byte, nullptr, 16)));
+
+    processHashFunction(name, size, input);
+  }
+
 }
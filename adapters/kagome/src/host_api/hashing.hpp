@@ -34,5 +34,15 @@ namespace hashing {
     const std::string_view name, uint32_t size, const std::string_view input
   );
 
+  // execute hash function test with input of a repeated byte, to cover
+  // empty inputs and inputs too large to pass as argument.
+  //
+  // @param byte to repeat, hex encoded
+  // @param count of repetitions
+  void processHashFunctionRepeated(
+    const std::string_view name, uint32_t size,
+    const std::string &byte, const std::string &count
+  );
+
 } // namespace hashing
 
@@ -31,6 +31,7 @@
 #include "state_trie.hpp"
 
 #include <cerrno>
+#include <iostream>
 
 /**
  * Implementation of Polkadot Host API, SCALE codec and Merkle-Patricia
@@ -55,6 +56,9 @@ int main(int argc, char **argv) {
   router.addSubcommand("host-api", [](int argc, char **argv) {
     processHostApiCommands(extractHostApiArgs(argc, argv));
   });
+  router.addSubcommand("healthcheck", [](int, char **) {
+    std::cout << "ok" << std::endl;
+  });
 
   std::string commands_list = "Valid subcommands are: ";
   for (auto &&name : router.collectSubcommandNames()) {
@@ -43,7 +43,7 @@ ScaleCommandArgs extractScaleArgs(int argc, char **argv) {
   desc.add_options()
       ("help", "produce help message")
       ("subcommand", po::value(&subcommand)->required(), "specify a subcommand")
-      ("input,i", po::value(&input)->required(), "the string to be encoded");
+      ("input,i", po::value(&input)->required(), "the input data to be encoded or decoded");
   // clang-format on
 
   po::positional_options_description pd;
@@ -81,6 +81,74 @@ void processScaleCodecCommand(ScaleCommandArgs args) {
     std::cout << "]\n";
   });
 
+  router.addSubcommand("encode-compact", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("decode-compact", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("option-bool", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("sparse-enum", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("perbill", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("percent", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("fixed-u128", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("mortal-era", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("decode-era", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("multi-address", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("multi-signer", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("multi-signature", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("signed-payload", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("digest-item", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("header", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("justifications", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
+  router.addSubcommand("extrinsics-root", [](std::string input) {
+    throw NotImplemented(); // TODO not implemented
+  });
+
   BOOST_VERIFY_MSG(
       router.executeSubcommand(args.subcommand,
                                args.input),
@@ -176,6 +176,10 @@ void processTrieCommand(const TrieCommandArgs &args) {
     }
     std::cout << "state root: " << hex_lower(trie->commit().value()) << "\n";
   });
+  router.addSubcommand("trie-nodes", [](std::vector<Buffer> keys,
+                                        std::vector<Buffer> values) {
+    throw NotImplemented(); // TODO not implemented
+  });
 
   auto [keys, values] =
       parseYamlStateFile(args.state_file_name, args.keys_in_hex);
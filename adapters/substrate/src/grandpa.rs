@@ -0,0 +1,141 @@
+// Copyright (c) 2019 Web3 Technologies Foundation
+
+// This file is part of Polkadot Host Test Suite
+
+// Polkadot Host Test Suite is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot Host Tests is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot Host Test Suite.  If not, see <https://www.gnu.org/licenses/>.
+
+///This file is an interface to drive the GRANDPA voter rounds of Parity's implementation.
+extern crate clap;
+
+use clap::ArgMatches;
+use finality_grandpa::round::{Round, RoundParams, State};
+use finality_grandpa::{Chain, Error, Precommit, Prevote, VoterSet};
+
+use std::collections::BTreeMap;
+
+/// Chain of named blocks, defined by their parent
+struct NamedChain {
+    parents: BTreeMap<String, String>,
+}
+
+impl NamedChain {
+    /// Parse chain from comma separated `parent:child` pairs
+    fn parse(input: &str) -> Self {
+        let parents = input
+            .split(',')
+            .map(|edge| {
+                let mut parts = edge.splitn(2, ':');
+                let parent = parts.next().unwrap().to_string();
+                let child = parts.next().expect("child block is missing").to_string();
+                (child, parent)
+            })
+            .collect();
+
+        NamedChain { parents }
+    }
+
+    /// Number of block, i.e. the count of its ancestors
+    fn number(&self, block: &str) -> u32 {
+        match self.parents.get(block) {
+            Some(parent) => self.number(parent) + 1,
+            None => 0,
+        }
+    }
+}
+
+impl Chain<String, u32> for NamedChain {
+    /// Blocks between base and block, excluding both
+    fn ancestry(&self, base: String, block: String) -> Result<Vec<String>, Error> {
+        let mut ancestry = Vec::new();
+        let mut current = block;
+        loop {
+            current = self.parents.get(&current).ok_or(Error::NotDescendent)?.clone();
+            if current == base {
+                return Ok(ancestry);
+            }
+            ancestry.push(current.clone());
+        }
+    }
+}
+
+fn block(target: &Option<(String, u32)>) -> &str {
+    target.as_ref().map_or("none", |(hash, _)| hash.as_str())
+}
+
+fn print_state(state: &State<String, u32>) -> String {
+    format!(
+        "ghost={}, estimate={}, finalized={}, completable={}",
+        block(&state.prevote_ghost),
+        block(&state.estimate),
+        block(&state.finalized),
+        state.completable as u8
+    )
+}
+
+/// Import scripted prevotes and precommits (comma separated `kind:voter:block`)
+/// into a single round on top of base, printing the prevote ghost, estimate,
+/// finalized block and completability of the round after every vote.
+fn round(matches: &ArgMatches) {
+    let chain = NamedChain::parse(matches.value_of("chain").unwrap());
+
+    let voters = VoterSet::new(matches.value_of("voters").unwrap().split(',').map(|voter| {
+        let mut parts = voter.splitn(2, ':');
+        let id = parts.next().unwrap().to_string();
+        let weight = parts.next().map_or(1, |w| w.parse().expect("Invalid voter weight"));
+        (id, weight)
+    }))
+    .expect("Voter set has to be non-empty");
+
+    let base = matches.value_of("base").unwrap().to_string();
+    let base_number = chain.number(&base);
+
+    let mut round: Round<String, String, u32, ()> = Round::new(RoundParams {
+        round_number: 1,
+        voters,
+        base: (base, base_number),
+    });
+
+    for vote in matches.value_of("votes").unwrap().split(',') {
+        let parts: Vec<&str> = vote.split(':').collect();
+        assert_eq!(parts.len(), 3, "vote has to be kind:voter:block");
+        let (kind, voter, target) = (parts[0], parts[1].to_string(), parts[2].to_string());
+        let number = chain.number(&target);
+
+        let res = match kind {
+            "prevote" => round
+                .import_prevote(&chain, Prevote::new(target.clone(), number), voter.clone(), ())
+                .map(|r| (r.valid_voter, r.duplicated, r.equivocation.is_some())),
+            "precommit" => round
+                .import_precommit(&chain, Precommit::new(target.clone(), number), voter.clone(), ())
+                .map(|r| (r.valid_voter, r.duplicated, r.equivocation.is_some())),
+            _ => panic!("Unknown vote kind: {}", kind),
+        };
+
+        let note = match res {
+            Err(_) => " (not descendent of base)",
+            Ok((false, _, _)) => " (unknown voter)",
+            Ok((_, true, _)) => " (duplicate)",
+            Ok((_, _, true)) => " (equivocation)",
+            Ok(_) => "",
+        };
+        println!("{} {} {}{}: {}", kind, voter, target, note, print_state(&round.state()));
+    }
+}
+
+pub fn process_grandpa_command(subcmd_matches: &ArgMatches) {
+    match subcmd_matches.value_of("grandpa-subcommand") {
+        Some("round") => round(subcmd_matches),
+        _ => panic!("grandpa subcommand is required"),
+    }
+}
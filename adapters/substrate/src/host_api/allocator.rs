@@ -1,5 +1,5 @@
 use crate::host_api::utils::{str, ParsedInput, Runtime};
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
 
 /// The Wasm function tests both the allocation and freeing of the buffer
 pub fn ext_allocator_malloc_version_1(mut rtm: Runtime, input: ParsedInput) {
@@ -18,3 +18,74 @@ pub fn ext_allocator_malloc_version_1(mut rtm: Runtime, input: ParsedInput) {
 pub fn ext_allocator_free_version_1(rtm: Runtime, input: ParsedInput) {
     ext_allocator_malloc_version_1(rtm, input)
 }
+
+/// Allocation of zero bytes has to succeed and be freeable
+pub fn test_allocator_zero_size(mut rtm: Runtime) {
+    let res = rtm.try_call("rtm_ext_allocator_malloc_version_1", &Vec::<u8>::new().encode());
+
+    println!("{}", if res.is_ok() { "ok" } else { "trap" });
+}
+
+/// Repeatedly allocate and free buffers, which must not overlap
+pub fn test_allocator_roundtrip(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let count = input.get_u32(0);
+    let size = input.get_u32(1);
+
+    let res = rtm.call_and_decode::<u32>(
+        "rtm_ext_allocator_roundtrip_version_1", &(count, size).encode()
+    );
+    assert_eq!(res, 1, "Allocated buffers overlap");
+
+    println!("ok");
+}
+
+/// Allocate until out of memory, which has to result in a trap
+pub fn test_allocator_exhaust(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let size = input.get_u32(0);
+
+    match rtm.try_call("rtm_ext_allocator_exhaust_version_1", &size.encode()) {
+        Ok(_) => println!("null"),
+        Err(_) => println!("trap"),
+    }
+}
+
+/// Free the same buffer twice, which has to result in a trap
+pub fn test_allocator_double_free(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let size = input.get_u32(0);
+
+    match rtm.try_call("rtm_ext_allocator_double_free_version_1", &size.encode()) {
+        Ok(_) => println!("ok"),
+        Err(_) => println!("trap"),
+    }
+}
+
+/// Allocate buffers of the provided sizes (`;` separated) in order, each freed only after
+/// the next one got allocated. The freeing-bump allocator only reuses freed buffers for
+/// the same size class (power of two of at least 8 bytes, plus an 8 byte header), so
+/// growing sizes fragment the heap until an allocation fails once their sum exceeds it.
+/// Every prefix of the sequence is run on a fresh instance, printing the peak extent of
+/// the allocated buffers or stopping at the first one that traps.
+pub fn test_allocator_fragmentation(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let sizes: Vec<u32> = std::str::from_utf8(input.get(0))
+        .expect("Invalid sizes")
+        .split(';')
+        .map(|s| s.parse().expect("Invalid size"))
+        .collect();
+
+    for n in 1..=sizes.len() {
+        match rtm.try_call("rtm_ext_allocator_fragment_version_1", &sizes[..n].encode()) {
+            Ok(res) => {
+                let peak = u32::decode(&mut res.as_slice()).expect("Failed to decode peak extent");
+                println!("{}. {}: peak={}", n, sizes[n - 1], peak);
+            }
+            Err(_) => {
+                println!("{}. {}: trap", n, sizes[n - 1]);
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,320 @@
+use crate::host_api::utils::{str, ParsedInput, Runtime};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{
+    hashing::{blake2_64, twox_128},
+    sr25519, Pair,
+};
+use sp_runtime::{
+    generic::{Era, SignedPayload},
+    transaction_validity::{InvalidTransaction, TransactionValidityError},
+    ApplyExtrinsicResult,
+};
+use sp_version::RuntimeVersion;
+use tester_runtime::{
+    AccountId, BabeConfig, BalancesConfig, BuildStorage, Call, GenesisConfig, GrandpaConfig,
+    Hash, Header, Index, Runtime as TesterRuntime, SignedExtra, SudoCall, SudoConfig, System,
+    SystemCall, SystemConfig, UncheckedExtrinsic, BABE_GENESIS_EPOCH_CONFIG, TRANSACTIONAL_KEY,
+    VERSION, WASM_BINARY,
+};
+
+/// Heap pages of the tester runtime, as used by hosts by default
+const HEAP_PAGES: u64 = 2048;
+
+/// Upper bound of extrinsics per block, to catch hosts not enforcing any weight limit
+const MAX_EXTRINSICS: usize = 10_000;
+
+/// Minimal genesis of the tester runtime, with the sudo key held by Alice
+fn genesis(sudo: AccountId) -> GenesisConfig {
+    GenesisConfig {
+        system: SystemConfig {
+            code: WASM_BINARY.unwrap().to_vec(),
+            changes_trie_config: Default::default(),
+        },
+        balances: BalancesConfig { balances: vec![] },
+        babe: BabeConfig {
+            authorities: vec![],
+            epoch_config: Some(BABE_GENESIS_EPOCH_CONFIG),
+        },
+        grandpa: GrandpaConfig { authorities: vec![] },
+        sudo: SudoConfig { key: sudo },
+    }
+}
+
+/// Runtime executing the tester runtime on top of its genesis
+fn with_genesis(rtm: Runtime, sudo: &sr25519::Pair) -> Runtime {
+    let storage = genesis(AccountId::from(sudo.public()))
+        .build_storage()
+        .expect("Failed to build genesis storage");
+
+    rtm.with_genesis(storage)
+        .with_blob(WASM_BINARY.unwrap().to_vec())
+        .with_heap_pages(HEAP_PAGES)
+}
+
+/// Block number, parent hash and all entries of the block hash ring buffer
+fn print_system(rtm: &mut Runtime, prefix: &str) {
+    rtm.execute_with(|| {
+        println!("{}number: {}", prefix, System::block_number());
+        println!("{}parent hash: {}", prefix, hex::encode(System::parent_hash()));
+
+        let mut hashes: Vec<(u32, Hash)> = frame_system::BlockHash::<TesterRuntime>::iter().collect();
+        hashes.sort();
+        for (number, hash) in hashes {
+            println!("{}block hash {}: {}", prefix, number, hex::encode(hash));
+        }
+    });
+}
+
+/// Initialize block with number and hex encoded parent hash on top of the
+/// genesis of the tester runtime, printing the storage of the system pallet
+/// before and after, or that the initialization trapped (e.g. on underflow
+/// of the number of the parent block).
+pub fn test_block_initialization(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let number: u32 = str(input.get(0)).parse().expect("Invalid block number");
+    let parent_hash = Hash::from_slice(&hex::decode(input.get(1)).expect("Invalid parent hash"));
+
+    let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let mut rtm = with_genesis(rtm, &alice);
+
+    print_system(&mut rtm, "genesis ");
+
+    let header = Header {
+        parent_hash,
+        number,
+        state_root: Default::default(),
+        extrinsics_root: Default::default(),
+        digest: Default::default(),
+    };
+
+    // Traps are reported by the executor, only their occurrence is of interest
+    if rtm.try_call_silent("Core_initialize_block", &header.encode()).is_err() {
+        println!("initialize: trap");
+        return;
+    }
+
+    print_system(&mut rtm, "");
+}
+
+/// Nest storage transactions of FRAME to the given depth in the tester runtime,
+/// printing how many of them were entered and the depth written in the
+/// innermost one (persisted as all are committed), or that nesting trapped.
+pub fn test_transactional_depth(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let depth = input.get_u32(0);
+
+    let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let mut rtm = with_genesis(rtm, &alice);
+
+    // Traps are reported by the executor, only their occurrence is of interest
+    let entered = match rtm.try_call_silent("TesterApi_nested_transactions", &depth.encode()) {
+        Ok(res) => u32::decode(&mut res.as_slice()).expect("Failed to decode entered transactions"),
+        Err(_) => {
+            println!("nested: trap");
+            return;
+        }
+    };
+
+    let written = rtm
+        .execute_with(|| sp_io::storage::get(TRANSACTIONAL_KEY))
+        .map(|v| u32::decode(&mut v.as_slice()).expect("Failed to decode written depth"));
+
+    println!("entered: {}", entered);
+    match written {
+        Some(depth) => println!("written: {}", depth),
+        None => println!("written: none"),
+    }
+}
+
+/// Negotiate the version of `TesterApi` declared in `Core_version` of the
+/// tester runtime against the version required by the caller, printing the
+/// declared version and the result of nesting a single transaction if the
+/// runtime provides at least the required version. Runtimes declaring an older
+/// version are not called, but reported as unsupported.
+pub fn test_tester_api_version(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let required: u32 = str(input.get(0)).parse().expect("Invalid api version");
+
+    let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let mut rtm = with_genesis(rtm, &alice);
+
+    let version = rtm.call_and_decode::<RuntimeVersion>("Core_version", &[]);
+    let declared = match version.api_version(&blake2_64(b"TesterApi")) {
+        Some(declared) => declared,
+        None => {
+            println!("declared: none");
+            return;
+        }
+    };
+
+    println!("declared: {}", declared);
+    if declared < required {
+        println!("call: unsupported");
+        return;
+    }
+
+    // Only version 2 and later return the number of transactions entered
+    let res = rtm.call("TesterApi_nested_transactions", &1u32.encode());
+    if declared < 2 {
+        println!("call: ok");
+    } else {
+        let entered = u32::decode(&mut res.as_slice()).expect("Failed to decode entered transactions");
+        println!("call: {}", entered);
+    }
+}
+
+/// Extrinsic signed by pair, valid immortally on chain of genesis hash
+fn signed_extrinsic(pair: &sr25519::Pair, nonce: Index, call: Call, genesis: Hash) -> UncheckedExtrinsic {
+    let extra: SignedExtra = (
+        frame_system::CheckSpecVersion::new(),
+        frame_system::CheckTxVersion::new(),
+        frame_system::CheckGenesis::new(),
+        frame_system::CheckEra::from(Era::Immortal),
+        frame_system::CheckNonce::from(nonce),
+        frame_system::CheckWeight::new(),
+    );
+    let additional = (VERSION.spec_version, VERSION.transaction_version, genesis, genesis, (), ());
+
+    let payload = SignedPayload::from_raw(call, extra, additional);
+    let signature = payload.using_encoded(|p| pair.sign(p));
+    let (call, extra, _) = payload.deconstruct();
+
+    UncheckedExtrinsic::new_signed(call, AccountId::from(pair.public()), signature.into(), extra)
+}
+
+/// Initialize first block on top of genesis, returning the genesis hash
+fn initialize_first_block(rtm: &mut Runtime) -> Hash {
+    let genesis_hash = rtm.execute_with(|| System::block_hash(0u32));
+    let header = Header {
+        parent_hash: genesis_hash,
+        number: 1,
+        state_root: Default::default(),
+        extrinsics_root: Default::default(),
+        digest: Default::default(),
+    };
+    rtm.call("Core_initialize_block", &header.encode());
+
+    genesis_hash
+}
+
+/// Apply extrinsic as a block builder would, discarding changes of rejected ones
+fn apply_extrinsic(rtm: &mut Runtime, extrinsic: &UncheckedExtrinsic) -> ApplyExtrinsicResult {
+    rtm.execute_with(sp_io::storage::start_transaction);
+    let res = rtm.call_and_decode::<ApplyExtrinsicResult>("BlockBuilder_apply_extrinsic", &extrinsic.encode());
+    if res.is_ok() {
+        rtm.execute_with(sp_io::storage::commit_transaction);
+    } else {
+        rtm.execute_with(sp_io::storage::rollback_transaction);
+    }
+    res
+}
+
+/// Consumed weight of current block (normal, operational and mandatory class)
+fn block_weight(rtm: &mut Runtime) -> String {
+    let key = [twox_128(b"System"), twox_128(b"BlockWeight")].concat();
+    let (normal, operational, mandatory) = rtm
+        .execute_with(|| sp_io::storage::get(&key))
+        .map(|v| Decode::decode(&mut v.as_slice()).expect("Failed to decode block weight"))
+        .unwrap_or((0u64, 0u64, 0u64));
+
+    format!("normal={}, operational={}, mandatory={}", normal, operational, mandatory)
+}
+
+/// Fill first block of the tester runtime with extrinsics of the provided weights (`;`
+/// separated, repeated in order) until the host reports exhausted resources, printing
+/// the block weight after each application.
+pub fn test_block_weight_accounting(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let weights: Vec<u64> = std::str::from_utf8(input.get(0))
+        .expect("Invalid weights")
+        .split(';')
+        .map(|w| w.parse().expect("Invalid weight"))
+        .collect();
+
+    let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let mut rtm = with_genesis(rtm, &alice);
+
+    let genesis_hash = initialize_first_block(&mut rtm);
+
+    println!("initialized: {}", block_weight(&mut rtm));
+
+    let mut nonce = 0;
+    for (i, weight) in weights.iter().cycle().enumerate().take(MAX_EXTRINSICS) {
+        let call = Call::Sudo(SudoCall::sudo_unchecked_weight(
+            Box::new(Call::System(SystemCall::remark(vec![]))),
+            *weight,
+        ));
+        let extrinsic = signed_extrinsic(&alice, nonce, call, genesis_hash);
+        let res = apply_extrinsic(&mut rtm, &extrinsic);
+
+        let status = match &res {
+            Ok(Ok(())) => "applied",
+            Ok(Err(_)) => "dispatch error",
+            Err(TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)) => "exhausts resources",
+            Err(e) => panic!("Extrinsic {} rejected: {:?}", i + 1, e),
+        };
+        println!("{}. {}: {}", i + 1, status, block_weight(&mut rtm));
+
+        if res.is_err() {
+            return;
+        }
+        nonce += 1;
+    }
+
+    panic!("Weight limit not reached after {} extrinsics", MAX_EXTRINSICS);
+}
+
+/// Applied extrinsics and their total encoded length in the current block
+fn block_length(rtm: &mut Runtime) -> String {
+    let key = [twox_128(b"System"), twox_128(b"AllExtrinsicsLen")].concat();
+    let (count, length) = rtm.execute_with(|| {
+        let length = sp_io::storage::get(&key)
+            .map(|v| u32::decode(&mut v.as_slice()).expect("Failed to decode block length"));
+        (System::extrinsic_index(), length)
+    });
+
+    format!("extrinsics={}, length={}", count.unwrap_or(0), length.unwrap_or(0))
+}
+
+/// Fill first block of the tester runtime with signed remarks of the provided sizes (`;`
+/// separated, repeated in order) until the host reports exhausted resources, printing
+/// the applied extrinsics and block length after each application. The length of each
+/// extrinsic is its encoding, i.e. 110 bytes plus the remark for remarks of at least
+/// 16 KiB among the first 64 extrinsics.
+pub fn test_block_length_accounting(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let sizes: Vec<usize> = std::str::from_utf8(input.get(0))
+        .expect("Invalid sizes")
+        .split(';')
+        .map(|s| s.parse().expect("Invalid size"))
+        .collect();
+
+    let alice = sr25519::Pair::from_string("//Alice", None).unwrap();
+    let mut rtm = with_genesis(rtm, &alice);
+
+    let genesis_hash = initialize_first_block(&mut rtm);
+
+    println!("initialized: {}", block_length(&mut rtm));
+
+    let mut nonce = 0;
+    for (i, size) in sizes.iter().cycle().enumerate().take(MAX_EXTRINSICS) {
+        let call = Call::System(SystemCall::remark(vec![0; *size]));
+        let extrinsic = signed_extrinsic(&alice, nonce, call, genesis_hash);
+        let res = apply_extrinsic(&mut rtm, &extrinsic);
+
+        let status = match &res {
+            Ok(Ok(())) => "applied",
+            Ok(Err(_)) => "dispatch error",
+            Err(TransactionValidityError::Invalid(InvalidTransaction::ExhaustsResources)) => "exhausts resources",
+            Err(e) => panic!("Extrinsic {} rejected: {:?}", i + 1, e),
+        };
+        println!("{}. {}: {}", i + 1, status, block_length(&mut rtm));
+
+        if res.is_err() {
+            return;
+        }
+        nonce += 1;
+    }
+
+    panic!("Length limit not reached after {} extrinsics", MAX_EXTRINSICS);
+}
@@ -1,5 +1,6 @@
 use crate::host_api::utils::{str, ParsedInput, Runtime};
 use parity_scale_codec::Encode;
+use sp_core::storage::well_known_keys::{CHILD_STORAGE_KEY_PREFIX, DEFAULT_CHILD_STORAGE_KEY_PREFIX};
 
 pub fn ext_default_child_storage_set_version_1(mut rtm: Runtime, input: ParsedInput) {
     // Parse inputs
@@ -419,3 +420,134 @@ pub fn ext_default_child_storage_next_key_version_1(mut rtm: Runtime, input: Par
     );
     assert!(res.is_none());
 }
+
+pub fn test_child_storage_prefix_root(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let child_key1 = input.get(0);
+    let child_key2 = input.get(1);
+    let key = input.get(2);
+    let value = input.get(3);
+
+    // Keys reserved for child storage roots in the main storage
+    let prefixed_key1 = [DEFAULT_CHILD_STORAGE_KEY_PREFIX, child_key1].concat();
+    let prefixed_key2 = [CHILD_STORAGE_KEY_PREFIX, child_key2].concat();
+
+    // Try to directly set reserved keys
+    let _ = rtm.call(
+        "rtm_ext_storage_set_version_1",
+        &(&prefixed_key1, value).encode(),
+    );
+    let _ = rtm.call(
+        "rtm_ext_storage_set_version_1",
+        &(&prefixed_key2, value).encode(),
+    );
+
+    // Direct writes to reserved keys have to be refused
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_get_version_1",
+        &prefixed_key1.encode(),
+    );
+    assert!(res.is_none());
+
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_get_version_1",
+        &prefixed_key2.encode(),
+    );
+    assert!(res.is_none());
+
+    // Set key/value in child storage
+    let _ = rtm.call(
+        "rtm_ext_default_child_storage_set_version_1",
+        &(child_key1, key, value).encode(),
+    );
+
+    // Get child root
+    let child_root = rtm.call_and_decode::<Vec<u8>>(
+        "rtm_ext_default_child_storage_root_version_1",
+        &child_key1.encode()
+    );
+
+    // Get main root
+    let root = rtm.call_and_decode::<Vec<u8>>("rtm_ext_storage_root_version_1", &[]);
+
+    // Main trie only contains the child root under the reserved key
+    let expected = sp_io::trie::blake2_256_root(vec![(prefixed_key1, child_root.clone())]);
+    assert_eq!(root, expected.as_bytes());
+
+    println!("{}", hex::encode(child_root));
+    println!("{}", hex::encode(root));
+}
+
+/// Fill two child storages and kill the first one, printing the child roots
+/// and the main root before and after: the killed child storage has to be
+/// empty and removed from the main root.
+pub fn test_child_storage_kill_root(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let child_key1 = input.get(0);
+    let child_key2 = input.get(1);
+    let key1 = input.get(2);
+    let value1 = input.get(3);
+    let key2 = input.get(4);
+    let value2 = input.get(5);
+
+    // Set key/values in both child storages
+    for (child, key, value) in &[(child_key1, key1, value1), (child_key1, key2, value2), (child_key2, key1, value2)] {
+        let _ = rtm.call(
+            "rtm_ext_default_child_storage_set_version_1",
+            &(child, key, value).encode(),
+        );
+    }
+
+    let res = rtm.call_and_decode::<u32>(
+        "rtm_ext_default_child_storage_exists_version_1",
+        &(child_key1, key1).encode(),
+    );
+    assert_eq!(res, 1);
+
+    // Roots of both child storages
+    let child_root1 = rtm.call_and_decode::<Vec<u8>>(
+        "rtm_ext_default_child_storage_root_version_1",
+        &child_key1.encode()
+    );
+    let child_root2 = rtm.call_and_decode::<Vec<u8>>(
+        "rtm_ext_default_child_storage_root_version_1",
+        &child_key2.encode()
+    );
+
+    // Main trie contains both child roots under their reserved keys
+    let root = rtm.call_and_decode::<Vec<u8>>("rtm_ext_storage_root_version_1", &[]);
+
+    println!("{}", hex::encode(&child_root1));
+    println!("{}", hex::encode(&child_root2));
+    println!("{}", hex::encode(&root));
+
+    // Kill first child storage
+    let _ = rtm.call(
+        "rtm_ext_default_child_storage_storage_kill_version_1",
+        &child_key1.encode(),
+    );
+
+    let res = rtm.call_and_decode::<u32>(
+        "rtm_ext_default_child_storage_exists_version_1",
+        &(child_key1, key1).encode(),
+    );
+    assert_eq!(res, 0);
+
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_default_child_storage_next_key_version_1",
+        &(child_key1, Vec::<u8>::new()).encode(),
+    );
+    assert!(res.is_none());
+
+    // Killed child storage has the root of an empty trie
+    let child_root1 = rtm.call_and_decode::<Vec<u8>>(
+        "rtm_ext_default_child_storage_root_version_1",
+        &child_key1.encode()
+    );
+
+    // Killed child storage is removed from the main trie
+    let root = rtm.call_and_decode::<Vec<u8>>("rtm_ext_storage_root_version_1", &[]);
+
+    println!("{}", hex::encode(&child_root1));
+    println!("{}", hex::encode(&root));
+}
@@ -1,8 +1,15 @@
 use crate::host_api::utils::{str, ParsedInput, Runtime};
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Decode, Encode};
+use sp_core::ecdsa;
 use sp_core::ed25519;
 use sp_core::sr25519;
 use sp_core::crypto::key_types::DUMMY;
+use sp_core::Pair;
+use sp_io::EcdsaVerifyError;
+use sp_keystore::testing::KeyStore;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+use std::thread;
 
 pub fn ext_crypto_ed25519_public_keys_version_1(rtm: Runtime, input: ParsedInput) {
     let mut rtm = rtm.with_keystore();
@@ -120,6 +127,29 @@ pub fn ext_crypto_ed25519_verify_version_1(rtm: Runtime, input: ParsedInput) {
     }
 }
 
+/// Verify externally provided (edge case) signature, without any assumptions on its validity
+pub fn test_crypto_ed25519_verify_encodings(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let mut pubkey = [0u8; 32];
+    pubkey.copy_from_slice(&hex::decode(input.get(0)).expect("Invalid public key"));
+    let mut sig = [0u8; 64];
+    sig.copy_from_slice(&hex::decode(input.get(1)).expect("Invalid signature"));
+    let msg = hex::decode(input.get(2)).expect("Invalid message");
+
+    // Verify signature
+    let verified = rtm.call_and_decode::<bool>(
+        "rtm_ext_crypto_ed25519_verify_version_1",
+        &(&sig, &msg, &pubkey).encode(),
+    );
+
+    // Print result
+    if verified {
+        println!("GOOD SIGNATURE");
+    } else {
+        println!("BAD SIGNATURE");
+    }
+}
+
 pub fn ext_crypto_sr25519_public_keys_version_1(rtm: Runtime, input: ParsedInput) {
     let mut rtm = rtm.with_keystore();
 
@@ -237,3 +267,302 @@ pub fn ext_crypto_sr25519_verify_version_1(rtm: Runtime, input: ParsedInput) {
         println!("BAD SIGNATURE");
     }
 }
+
+/// Signatures of count messages by the key of seed, as batch entries (signature, message, public key)
+fn batch_entries<P: Pair>(seed: &str, count: u32) -> Vec<(P::Signature, Vec<u8>, P::Public)> {
+    let pair = P::from_string(seed, None).expect("Invalid seed");
+    (0..count)
+        .map(|i| {
+            let msg = format!("message {}", i).into_bytes();
+            (pair.sign(&msg), msg, pair.public())
+        })
+        .collect()
+}
+
+/// Copy of batch entries with the message of the last entry altered
+fn altered<S: Clone, P: Clone>(sigs: &[(S, Vec<u8>, P)]) -> Vec<(S, Vec<u8>, P)> {
+    let mut sigs = sigs.to_vec();
+    if let Some((_, msg, _)) = sigs.last_mut() {
+        msg.push(0);
+    }
+    sigs
+}
+
+/// Verify batches of ed25519, sr25519 and ecdsa signatures (made natively), where a
+/// single invalid signature has to fail the whole batch.
+pub fn test_crypto_batch_verify(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_task_executor();
+
+    // Parse inputs
+    let seed = str(input.get(0));
+    let count = input.get_u32(1);
+
+    let ed25519_sigs = batch_entries::<ed25519::Pair>(seed, count);
+    let sr25519_sigs = batch_entries::<sr25519::Pair>(seed, count);
+    let ecdsa_sigs = batch_entries::<ecdsa::Pair>(seed, count);
+
+    // Same batches, with the message of the last signature altered
+    let ed25519_invalid = altered(&ed25519_sigs);
+    let sr25519_invalid = altered(&sr25519_sigs);
+    let ecdsa_invalid = altered(&ecdsa_sigs);
+
+    let batches = [
+        ("empty batch", vec![], vec![], vec![]),
+        ("ed25519 batch", ed25519_sigs.clone(), vec![], vec![]),
+        ("sr25519 batch", vec![], sr25519_sigs.clone(), vec![]),
+        ("ecdsa batch", vec![], vec![], ecdsa_sigs.clone()),
+        ("mixed batch", ed25519_sigs.clone(), sr25519_sigs.clone(), ecdsa_sigs.clone()),
+        ("mixed batch, invalid ed25519 signature", ed25519_invalid, sr25519_sigs.clone(), ecdsa_sigs.clone()),
+        ("mixed batch, invalid sr25519 signature", ed25519_sigs.clone(), sr25519_invalid, ecdsa_sigs),
+        ("mixed batch, invalid ecdsa signature", ed25519_sigs, sr25519_sigs, ecdsa_invalid),
+    ];
+
+    for (name, ed25519_batch, sr25519_batch, ecdsa_batch) in batches.iter() {
+        let verified = rtm.call_and_decode::<u32>(
+            "rtm_ext_crypto_batch_verify_version_1",
+            &(ed25519_batch, sr25519_batch, ecdsa_batch).encode(),
+        );
+
+        // Print result
+        if verified == 1 {
+            println!("{}: GOOD SIGNATURES", name);
+        } else {
+            println!("{}: BAD SIGNATURES", name);
+        }
+    }
+}
+
+/// Recover public key from signature natively with the secp256k1 crate, using
+/// the same interpretation of the recovery id as the host function.
+fn secp256k1_recover_oracle(sig: &[u8; 65], msg: &[u8; 32]) -> Result<secp256k1::PublicKey, EcdsaVerifyError> {
+    let rs = secp256k1::Signature::parse_slice(&sig[0..64]).map_err(|_| EcdsaVerifyError::BadRS)?;
+    let v = secp256k1::RecoveryId::parse(if sig[64] > 26 { sig[64] - 27 } else { sig[64] })
+        .map_err(|_| EcdsaVerifyError::BadV)?;
+    secp256k1::recover(&secp256k1::Message::parse(msg), &rs, &v).map_err(|_| EcdsaVerifyError::BadSignature)
+}
+
+/// Recover public key of externally provided (edge case) signature and check the
+/// result of the host against the secp256k1 crate as an oracle.
+fn test_secp256k1_ecdsa_recover<K>(mut rtm: Runtime, func: &str, serialize: fn(&secp256k1::PublicKey) -> K, input: ParsedInput)
+where
+    K: Decode + AsRef<[u8]> + PartialEq,
+{
+    // Parse inputs
+    let mut sig = [0u8; 65];
+    sig.copy_from_slice(&hex::decode(input.get(0)).expect("Invalid signature"));
+    let mut msg = [0u8; 32];
+    msg.copy_from_slice(&hex::decode(input.get(1)).expect("Invalid message"));
+
+    // Recover public key
+    let res = rtm.call_and_decode::<Result<K, EcdsaVerifyError>>(
+        func,
+        &(sig.to_vec(), msg.to_vec()).encode(),
+    );
+
+    // Compare with oracle, errors only by their variant
+    let expected = secp256k1_recover_oracle(&sig, &msg);
+    match (&res, &expected) {
+        (Ok(pubkey), Ok(oracle)) if *pubkey == serialize(oracle) => {}
+        (Err(e), Err(oracle)) if e.encode() == oracle.encode() => {}
+        _ => panic!("Result of host differs from oracle"),
+    }
+
+    // Print result
+    match res {
+        Ok(pubkey) => println!("Public key: {}", hex::encode(pubkey)),
+        Err(EcdsaVerifyError::BadRS) => println!("Error: bad RS"),
+        Err(EcdsaVerifyError::BadV) => println!("Error: bad V"),
+        Err(EcdsaVerifyError::BadSignature) => println!("Error: bad signature"),
+    }
+}
+
+pub fn test_crypto_secp256k1_ecdsa_recover(rtm: Runtime, input: ParsedInput) {
+    test_secp256k1_ecdsa_recover::<[u8; 64]>(
+        rtm,
+        "rtm_ext_crypto_secp256k1_ecdsa_recover_version_1",
+        |pubkey| {
+            let mut res = [0u8; 64];
+            res.copy_from_slice(&pubkey.serialize()[1..65]);
+            res
+        },
+        input,
+    )
+}
+
+pub fn test_crypto_secp256k1_ecdsa_recover_compressed(rtm: Runtime, input: ParsedInput) {
+    test_secp256k1_ecdsa_recover::<[u8; 33]>(
+        rtm,
+        "rtm_ext_crypto_secp256k1_ecdsa_recover_compressed_version_1",
+        |pubkey| pubkey.serialize_compressed(),
+        input,
+    )
+}
+
+/// Generate many keys without seed and check for obviously broken randomness
+fn test_generate_randomness(rtm: Runtime, scheme: &str, input: ParsedInput) {
+    let mut rtm = rtm.with_keystore();
+
+    // Parse inputs
+    let count = input.get_u32(0);
+
+    // Generate keys, collecting distinct keys and distribution of key bytes
+    let mut keys = BTreeSet::new();
+    let mut histogram = [0u32; 256];
+    for _ in 0..count {
+        let pubkey = rtm.call_and_decode::<[u8; 32]>(
+            &format!("rtm_ext_crypto_{}_generate_version_1", scheme),
+            &(DUMMY.0, None::<Vec<u8>>).encode(),
+        );
+
+        for byte in pubkey.iter() {
+            histogram[*byte as usize] += 1;
+        }
+
+        if !keys.insert(pubkey) {
+            panic!("Duplicate key generated: {}", hex::encode(pubkey));
+        }
+    }
+
+    // Chi-squared statistic of the key bytes (255 degrees of freedom), the
+    // threshold is far beyond anything reached by uniformly random bytes.
+    let expected = (count * 32) as f64 / 256.0;
+    let chi2: f64 = histogram
+        .iter()
+        .map(|&n| (n as f64 - expected).powi(2) / expected)
+        .sum();
+    if chi2 > 400.0 {
+        panic!("Low entropy in generated keys (chi-squared: {:.1})", chi2);
+    }
+
+    // Print result
+    println!("{} unique keys", keys.len());
+}
+
+pub fn test_crypto_ed25519_generate_randomness(rtm: Runtime, input: ParsedInput) {
+    test_generate_randomness(rtm, "ed25519", input)
+}
+
+pub fn test_crypto_sr25519_generate_randomness(rtm: Runtime, input: ParsedInput) {
+    test_generate_randomness(rtm, "sr25519", input)
+}
+
+/// Generate keys from the provided seeds (`;` separated, each a BIP39 phrase, hex encoded
+/// seed or derivation path), which have to match their derivation by substrate and all be
+/// listed by the keystore afterwards (each only once).
+fn test_generate_seeds<P: Pair>(rtm: Runtime, scheme: &str, input: ParsedInput) {
+    let mut rtm = rtm.with_keystore();
+
+    // Parse inputs
+    let seeds: Vec<&str> = std::str::from_utf8(input.get(0))
+        .expect("Invalid seeds")
+        .split(';')
+        .collect();
+
+    // Generate keys
+    let mut generated = BTreeSet::new();
+    for (i, seed) in seeds.iter().enumerate() {
+        let pubkey = rtm.call_and_decode::<[u8; 32]>(
+            &format!("rtm_ext_crypto_{}_generate_version_1", scheme),
+            &(DUMMY.0, Some(seed.as_bytes())).encode(),
+        );
+
+        let expected = P::from_string(seed, None).expect("Invalid seed").public();
+        assert_eq!(&pubkey[..], expected.as_ref(), "Key of seed {} does not match its derivation", i + 1);
+
+        generated.insert(pubkey);
+        println!("{}. Public key: {}", i + 1, hex::encode(pubkey));
+    }
+
+    // Retrieve all known keys
+    let listed = rtm.call_and_decode::<Vec<[u8; 32]>>(
+        &format!("rtm_ext_crypto_{}_public_keys_version_1", scheme),
+        &DUMMY.0.encode(),
+    );
+    assert_eq!(listed.len(), generated.len(), "Keystore does not list every key once");
+    assert_eq!(listed.into_iter().collect::<BTreeSet<_>>(), generated, "Keystore does not list generated keys");
+
+    println!("Listed keys: {}", generated.len());
+}
+
+pub fn test_crypto_ed25519_generate_seeds(rtm: Runtime, input: ParsedInput) {
+    test_generate_seeds::<ed25519::Pair>(rtm, "ed25519", input)
+}
+
+pub fn test_crypto_sr25519_generate_seeds(rtm: Runtime, input: ParsedInput) {
+    test_generate_seeds::<sr25519::Pair>(rtm, "sr25519", input)
+}
+
+/// Generate keys and sign with them concurrently from runtimes sharing one keystore,
+/// then check that no key got lost and all signatures are valid.
+fn test_keystore_concurrency<P, S>(rtm: Runtime, scheme: &'static str, input: ParsedInput)
+where
+    P: Encode + Decode + Send + 'static,
+    S: Encode + Decode + Send + 'static,
+{
+    // Parse inputs
+    let threads = input.get_u32(0);
+    let count = input.get_u32(1);
+
+    let keystore = Arc::new(KeyStore::new());
+
+    // Every thread generates keys and signs a message with each of them
+    let handles: Vec<_> = (0..threads)
+        .map(|t| {
+            let new_runtime = rtm.factory();
+            let keystore = keystore.clone();
+            thread::spawn(move || {
+                let mut rtm = new_runtime().with_shared_keystore(keystore);
+                let mut results = Vec::new();
+                for i in 0..count {
+                    let msg = format!("thread {} message {}", t, i).into_bytes();
+                    let pubkey = rtm.call_and_decode::<P>(
+                        &format!("rtm_ext_crypto_{}_generate_version_1", scheme),
+                        &(DUMMY.0, None::<Vec<u8>>).encode(),
+                    );
+                    let sig = rtm.call_and_decode::<Option<S>>(
+                        &format!("rtm_ext_crypto_{}_sign_version_1", scheme),
+                        &(DUMMY.0, &pubkey, &msg).encode(),
+                    ).expect("Generated key missing in keystore");
+                    let verified = rtm.call_and_decode::<bool>(
+                        &format!("rtm_ext_crypto_{}_verify_version_1", scheme),
+                        &(&sig, &msg, &pubkey).encode(),
+                    );
+                    results.push((pubkey.encode(), verified));
+                }
+                results
+            })
+        })
+        .collect();
+
+    let results: Vec<(Vec<u8>, bool)> = handles
+        .into_iter()
+        .flat_map(|h| h.join().expect("Thread failed"))
+        .collect();
+
+    // All generated keys have to be known to the shared keystore
+    let generated: BTreeSet<Vec<u8>> = results.iter().map(|(k, _)| k.clone()).collect();
+    let mut rtm = rtm.with_shared_keystore(keystore);
+    let known: BTreeSet<Vec<u8>> = rtm
+        .call_and_decode::<Vec<P>>(
+            &format!("rtm_ext_crypto_{}_public_keys_version_1", scheme),
+            &DUMMY.0.encode(),
+        )
+        .iter()
+        .map(|k| k.encode())
+        .collect();
+
+    if let Some(lost) = generated.difference(&known).next() {
+        panic!("Key lost by keystore: {}", hex::encode(lost));
+    }
+
+    // Print result
+    println!("{} keys, {} valid signatures", known.len(), results.iter().filter(|(_, v)| *v).count());
+}
+
+pub fn test_crypto_ed25519_keystore_concurrency(rtm: Runtime, input: ParsedInput) {
+    test_keystore_concurrency::<ed25519::Public, ed25519::Signature>(rtm, "ed25519", input)
+}
+
+pub fn test_crypto_sr25519_keystore_concurrency(rtm: Runtime, input: ParsedInput) {
+    test_keystore_concurrency::<sr25519::Public, sr25519::Signature>(rtm, "sr25519", input)
+}
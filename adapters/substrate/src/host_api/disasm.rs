@@ -0,0 +1,120 @@
+use wasmparser::{ExternalKind, ImportSectionEntryType, Parser, Payload, Result};
+
+/// Number of instructions to show before and after the faulting instruction
+const CONTEXT: usize = 8;
+
+/// Maximum number of instructions to show if no faulting instruction is known
+const LIMIT: usize = 64;
+
+/// A disassembled function body of the blob
+struct Function {
+    index: u32,
+    range: std::ops::Range<usize>,
+    instructions: Vec<(usize, String)>,
+}
+
+/// Extract offset of innermost frame from wasm backtrace of trap (wasmtime only)
+fn trap_offset(error: &str) -> Option<usize> {
+    error
+        .lines()
+        .skip_while(|l| !l.contains("wasm backtrace:"))
+        .filter_map(|l| l.trim().split(" - ").next())
+        .filter_map(|l| l.split("0x").nth(1))
+        .find_map(|o| usize::from_str_radix(o.trim(), 16).ok())
+}
+
+/// Disassemble all function bodies of blob, return them with the exports
+fn disassemble(blob: &[u8]) -> Result<(Vec<Function>, Vec<(String, u32)>)> {
+    let mut imported = 0;
+    let mut functions = Vec::new();
+    let mut exports = Vec::new();
+
+    for payload in Parser::new(0).parse_all(blob) {
+        match payload? {
+            Payload::ImportSection(reader) => {
+                for import in reader {
+                    if let ImportSectionEntryType::Function(_) = import?.ty {
+                        imported += 1;
+                    }
+                }
+            }
+            Payload::ExportSection(reader) => {
+                for export in reader {
+                    let export = export?;
+                    if let ExternalKind::Function = export.kind {
+                        exports.push((export.field.to_string(), export.index));
+                    }
+                }
+            }
+            Payload::CodeSectionEntry(body) => {
+                let mut reader = body.get_operators_reader()?;
+                let mut instructions = Vec::new();
+                while !reader.eof() {
+                    let (op, offset) = reader.read_with_offset()?;
+                    instructions.push((offset, format!("{:?}", op)));
+                }
+                let range = body.range();
+                functions.push(Function {
+                    index: imported + functions.len() as u32,
+                    range: range.start..range.end,
+                    instructions,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    Ok((functions, exports))
+}
+
+/// Print disassembly of the faulting function to help tell apart blob and host errors:
+/// Around the trap location if known, otherwise the start of the called export.
+pub fn print_trap_context(blob: &[u8], func: &str, error: &str) {
+    let (functions, exports) = match disassemble(blob) {
+        Ok(res) => res,
+        Err(e) => {
+            eprintln!("Failed to disassemble wasm blob: {}", e);
+            return;
+        }
+    };
+
+    let name = |index: u32| {
+        exports
+            .iter()
+            .find(|(_, i)| *i == index)
+            .map(|(n, _)| n.as_str())
+            .unwrap_or("<unknown>")
+    };
+
+    if let Some(offset) = trap_offset(error) {
+        if let Some(f) = functions.iter().find(|f| f.range.contains(&offset)) {
+            eprintln!("Disassembly of function {} ({}) at offset {:#x}:", f.index, name(f.index), offset);
+            let pos = f
+                .instructions
+                .iter()
+                .rposition(|(o, _)| *o <= offset)
+                .unwrap_or(0);
+            let start = pos.saturating_sub(CONTEXT);
+            let end = (pos + CONTEXT + 1).min(f.instructions.len());
+            for (i, (o, op)) in f.instructions[start..end].iter().enumerate() {
+                let marker = if start + i == pos { ">" } else { " " };
+                eprintln!("{} {:#08x}: {}", marker, o, op);
+            }
+            return;
+        }
+    }
+
+    let index = exports.iter().find(|(n, _)| n == func).map(|(_, i)| *i);
+    match index.and_then(|i| functions.iter().find(|f| f.index == i)) {
+        Some(f) => {
+            eprintln!("Disassembly of function {} ({}), trap location unknown:", f.index, func);
+            for (o, op) in f.instructions.iter().take(LIMIT) {
+                eprintln!("  {:#08x}: {}", o, op);
+            }
+            if f.instructions.len() > LIMIT {
+                eprintln!("  ... ({} more)", f.instructions.len() - LIMIT);
+            }
+        }
+        None => eprintln!("Function {} not found in wasm blob", func),
+    }
+}
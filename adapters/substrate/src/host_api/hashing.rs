@@ -12,3 +12,18 @@ pub fn ext_hashing_version_1(mut rtm: Runtime, func: &str, input: ParsedInput) {
 
     println!("{}", hex::encode(hash));
 }
+
+/// Hash repeated byte, to cover empty inputs and inputs too large to pass as argument
+pub fn test_hashing_repeated(mut rtm: Runtime, func: &str, input: ParsedInput) {
+    // Parse inputs
+    let byte = hex::decode(input.get(0)).expect("Invalid byte");
+    let count = input.get_u32(1) as usize;
+    let data = byte.repeat(count);
+
+    let hash = rtm.call_and_decode::<Vec<u8>>(
+        &["rtm_ext_hashing_", func, "_version_1"].join(""),
+        &data.encode()
+    );
+
+    println!("{}", hex::encode(hash));
+}
@@ -0,0 +1,299 @@
+use sp_core::offchain::{
+    testing::TestOffchainExt, Externalities, HttpError, HttpRequestId, HttpRequestStatus,
+    OpaqueNetworkState, OpaquePeerId, Timestamp,
+};
+use std::collections::BTreeMap;
+use std::io::{ErrorKind, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+struct Response {
+    status: u16,
+    headers: Vec<(Vec<u8>, Vec<u8>)>,
+    body: Vec<u8>,
+    read: usize,
+}
+
+/// Progress of a request: Headers can be added until the first body chunk is
+/// written, which dispatches it. Once its body is finished the response is
+/// received in the background, independent of any deadline (like the http
+/// worker of sc-offchain), so a reached deadline is never final.
+enum State {
+    NotDispatched(Vec<(String, String)>),
+    Dispatched(TcpStream),
+    Waiting(Receiver<Result<Response, HttpRequestStatus>>),
+    Received(Result<Response, HttpRequestStatus>),
+}
+
+struct Request {
+    method: String,
+    uri: String,
+    state: State,
+}
+
+/// Offchain externalities performing real HTTP requests (plain HTTP/1.1 only),
+/// to test the http host functions against the local mock server of the
+/// testsuite. Like a node it keeps wall clock time, i.e. sleeping blocks until
+/// the deadline. All other offchain functions are provided by `TestOffchainExt`.
+pub struct HttpOffchainExt {
+    inner: TestOffchainExt,
+    requests: BTreeMap<u16, Request>,
+    next_id: u16,
+}
+
+impl HttpOffchainExt {
+    pub fn new() -> Self {
+        let (inner, _) = TestOffchainExt::new();
+        HttpOffchainExt {
+            inner,
+            requests: BTreeMap::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Time left until deadline, none if there is no deadline
+    fn timeout(&mut self, deadline: Option<Timestamp>) -> Option<Duration> {
+        let now = self.timestamp().unix_millis();
+        deadline.map(|d| Duration::from_millis(d.unix_millis().saturating_sub(now)))
+    }
+
+    /// Wait for response of request until deadline, none if the id is unknown
+    fn respond(&mut self, id: u16, deadline: Option<Timestamp>) -> Option<Result<&mut Response, HttpRequestStatus>> {
+        let timeout = self.timeout(deadline);
+        let request = self.requests.get_mut(&id)?;
+
+        let response = match &request.state {
+            State::Waiting(receiver) => match timeout.map_or_else(
+                || receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+                |t| receiver.recv_timeout(t),
+            ) {
+                Ok(response) => Some(response),
+                Err(RecvTimeoutError::Timeout) => return Some(Err(HttpRequestStatus::DeadlineReached)),
+                Err(RecvTimeoutError::Disconnected) => Some(Err(HttpRequestStatus::IoError)),
+            },
+            // Without a finished body no response arrives before the deadline
+            State::NotDispatched(_) | State::Dispatched(_) => {
+                std::thread::sleep(timeout.expect("Waiting for unfinished request without deadline"));
+                return Some(Err(HttpRequestStatus::DeadlineReached));
+            }
+            State::Received(_) => None,
+        };
+
+        if let Some(response) = response {
+            request.state = State::Received(response);
+        }
+        Some(received(&mut request.state))
+    }
+}
+
+fn received(state: &mut State) -> Result<&mut Response, HttpRequestStatus> {
+    match state {
+        State::Received(Ok(response)) => Ok(response),
+        State::Received(Err(status)) => Err(status.clone()),
+        _ => unreachable!("Response not received yet"),
+    }
+}
+
+fn io_error(e: std::io::Error) -> HttpError {
+    match e.kind() {
+        ErrorKind::WouldBlock | ErrorKind::TimedOut => HttpError::DeadlineReached,
+        _ => HttpError::IoError,
+    }
+}
+
+/// Connect to server of request and send its head, within the timeout
+fn dispatch(request: &Request, headers: &[(String, String)], timeout: Option<Duration>) -> Result<TcpStream, HttpError> {
+    // Split uri into authority and path
+    let rest = request.uri.strip_prefix("http://").ok_or(HttpError::IoError)?;
+    let (authority, path) = match rest.find('/') {
+        Some(i) => (&rest[..i], &rest[i..]),
+        None => (rest, "/"),
+    };
+    let addr = authority
+        .to_socket_addrs()
+        .map_err(io_error)?
+        .next()
+        .ok_or(HttpError::IoError)?;
+
+    let mut stream = match timeout {
+        Some(timeout) => TcpStream::connect_timeout(&addr, timeout),
+        None => TcpStream::connect(addr),
+    }
+    .map_err(io_error)?;
+
+    // Body is streamed in chunks as written, with the connection closed after the response
+    let mut head = format!("{} {} HTTP/1.1\r\nHost: {}\r\n", request.method, path, authority);
+    for (name, value) in headers {
+        head += &format!("{}: {}\r\n", name, value);
+    }
+    head += "Transfer-Encoding: chunked\r\nConnection: close\r\n\r\n";
+
+    stream.set_write_timeout(timeout).map_err(io_error)?;
+    stream.write_all(head.as_bytes()).map_err(io_error)?;
+    Ok(stream)
+}
+
+/// Read response until the connection is closed by the server
+fn receive(mut stream: TcpStream) -> Result<Response, HttpRequestStatus> {
+    let mut raw = Vec::new();
+    stream.read_to_end(&mut raw).map_err(|_| HttpRequestStatus::IoError)?;
+
+    parse_response(&raw).ok_or(HttpRequestStatus::IoError)
+}
+
+/// Parse status line, headers and body (until connection close) of response
+fn parse_response(raw: &[u8]) -> Option<Response> {
+    let end = raw.windows(4).position(|w| w == b"\r\n\r\n")?;
+    let head = std::str::from_utf8(&raw[..end]).ok()?;
+
+    let mut lines = head.split("\r\n");
+    let status = lines.next()?.split(' ').nth(1)?.parse().ok()?;
+    let headers = lines
+        .filter_map(|line| {
+            let mut parts = line.splitn(2, ':');
+            let name = parts.next()?.trim();
+            let value = parts.next()?.trim();
+            Some((name.as_bytes().to_vec(), value.as_bytes().to_vec()))
+        })
+        .collect();
+
+    Some(Response {
+        status,
+        headers,
+        body: raw[end + 4..].to_vec(),
+        read: 0,
+    })
+}
+
+impl Externalities for HttpOffchainExt {
+    fn is_validator(&self) -> bool {
+        self.inner.is_validator()
+    }
+
+    fn network_state(&self) -> Result<OpaqueNetworkState, ()> {
+        self.inner.network_state()
+    }
+
+    fn timestamp(&mut self) -> Timestamp {
+        let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("Clock before unix epoch");
+        Timestamp::from_unix_millis(now.as_millis() as u64)
+    }
+
+    fn sleep_until(&mut self, deadline: Timestamp) {
+        let now = self.timestamp().unix_millis();
+        std::thread::sleep(Duration::from_millis(deadline.unix_millis().saturating_sub(now)));
+    }
+
+    fn random_seed(&mut self) -> [u8; 32] {
+        self.inner.random_seed()
+    }
+
+    fn http_request_start(&mut self, method: &str, uri: &str, _meta: &[u8]) -> Result<HttpRequestId, ()> {
+        let id = self.next_id;
+        self.next_id = id.checked_add(1).ok_or(())?;
+
+        self.requests.insert(id, Request {
+            method: method.to_string(),
+            uri: uri.to_string(),
+            state: State::NotDispatched(Vec::new()),
+        });
+        Ok(HttpRequestId(id))
+    }
+
+    fn http_request_add_header(&mut self, request_id: HttpRequestId, name: &str, value: &str) -> Result<(), ()> {
+        match self.requests.get_mut(&request_id.0).map(|r| &mut r.state) {
+            Some(State::NotDispatched(headers)) => {
+                headers.push((name.to_string(), value.to_string()));
+                Ok(())
+            }
+            _ => Err(()),
+        }
+    }
+
+    fn http_request_write_body(
+        &mut self,
+        request_id: HttpRequestId,
+        chunk: &[u8],
+        deadline: Option<Timestamp>,
+    ) -> Result<(), HttpError> {
+        let timeout = self.timeout(deadline);
+        let request = self.requests.get_mut(&request_id.0).ok_or(HttpError::Invalid)?;
+
+        // Chunks have to be sent before the deadline, which can not be met once reached
+        if timeout == Some(Duration::from_millis(0)) {
+            return match request.state {
+                State::NotDispatched(_) | State::Dispatched(_) => Err(HttpError::DeadlineReached),
+                _ => Err(HttpError::Invalid),
+            };
+        }
+
+        let mut stream = match &request.state {
+            State::NotDispatched(headers) => dispatch(request, headers, timeout)?,
+            State::Dispatched(stream) => stream.try_clone().map_err(|_| HttpError::IoError)?,
+            _ => return Err(HttpError::Invalid),
+        };
+
+        stream.set_write_timeout(timeout).map_err(io_error)?;
+        stream.write_all(format!("{:x}\r\n", chunk.len()).as_bytes()).map_err(io_error)?;
+        stream.write_all(chunk).map_err(io_error)?;
+        stream.write_all(b"\r\n").map_err(io_error)?;
+
+        // Empty chunk finishes the body, after which the response is awaited
+        request.state = if chunk.is_empty() {
+            let (sender, receiver) = channel();
+            std::thread::spawn(move || sender.send(receive(stream)));
+            State::Waiting(receiver)
+        } else {
+            State::Dispatched(stream)
+        };
+        Ok(())
+    }
+
+    fn http_response_wait(&mut self, ids: &[HttpRequestId], deadline: Option<Timestamp>) -> Vec<HttpRequestStatus> {
+        ids.iter()
+            .map(|id| match self.respond(id.0, deadline) {
+                Some(Ok(response)) => HttpRequestStatus::Finished(response.status),
+                Some(Err(status)) => status,
+                None => HttpRequestStatus::Invalid,
+            })
+            .collect()
+    }
+
+    fn http_response_headers(&mut self, request_id: HttpRequestId) -> Vec<(Vec<u8>, Vec<u8>)> {
+        match self.requests.get(&request_id.0).map(|r| &r.state) {
+            Some(State::Received(Ok(response))) => response.headers.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn http_response_read_body(
+        &mut self,
+        request_id: HttpRequestId,
+        buffer: &mut [u8],
+        deadline: Option<Timestamp>,
+    ) -> Result<usize, HttpError> {
+        let res = match self.respond(request_id.0, deadline) {
+            Some(Ok(response)) => {
+                let n = buffer.len().min(response.body.len() - response.read);
+                buffer[..n].copy_from_slice(&response.body[response.read..response.read + n]);
+                response.read += n;
+                Ok(n)
+            }
+            Some(Err(HttpRequestStatus::DeadlineReached)) => Err(HttpError::DeadlineReached),
+            Some(Err(HttpRequestStatus::IoError)) => Err(HttpError::IoError),
+            _ => Err(HttpError::Invalid),
+        };
+
+        // As in the http worker of sc-offchain, the request id is invalid once
+        // its response body has been consumed or reading it failed
+        if matches!(res, Ok(0) | Err(HttpError::IoError)) {
+            self.requests.remove(&request_id.0);
+        }
+        res
+    }
+
+    fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
+        self.inner.set_authorized_nodes(nodes, authorized_only)
+    }
+}
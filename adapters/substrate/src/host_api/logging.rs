@@ -0,0 +1,39 @@
+use crate::host_api::utils::{str, LogSink, ParsedInput, Runtime};
+use parity_scale_codec::Encode;
+
+/// Log externally provided (possibly invalid UTF-8) message, which must not trap
+pub fn test_logging_log_encodings(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let level = input.get_u32(0);
+    let target = input.get(1);
+    let message = hex::decode(input.get(2)).expect("Invalid message");
+
+    // Log message, invalid UTF-8 is not logged but must be tolerated
+    let res = rtm.try_call("rtm_ext_logging_log_version_1", &(level, target, message).encode());
+
+    // Print result
+    println!("{}", if res.is_ok() { "ok" } else { "trap" });
+}
+
+/// Log message and print the captured records with their level and target
+pub fn test_logging_log(rtm: Runtime, input: ParsedInput) {
+    let sink = LogSink::default();
+    let mut rtm = rtm.with_log_sink(sink.clone());
+
+    // Parse inputs
+    let level = input.get_u32(0);
+    let target = str(input.get(1));
+    let message = hex::decode(input.get(2)).expect("Invalid message");
+
+    // Log message, only valid UTF-8 is logged
+    rtm.call("rtm_ext_logging_log_version_1", &(level, target.as_bytes(), message).encode());
+
+    // Print captured records
+    let logged = sink.take();
+    if logged.is_empty() {
+        println!("(nothing logged)");
+    }
+    for (level, target, message) in logged {
+        println!("log: {} {}: {}", level, target, message);
+    }
+}
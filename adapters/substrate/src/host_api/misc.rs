@@ -0,0 +1,133 @@
+use crate::host_api::utils::{str, ParsedInput, PrintSink, Runtime, LARGE_DATA_HEAP_PAGES};
+use parity_scale_codec::Encode;
+
+/// Print captured output, one line per print call
+fn print_captured(sink: &PrintSink) {
+    let printed = sink.take();
+    if printed.is_empty() {
+        println!("(nothing printed)");
+    }
+    for line in printed {
+        println!("print: {}", line);
+    }
+}
+
+/// Print externally provided (possibly invalid UTF-8) data, which must not trap
+pub fn test_misc_print_utf8_encodings(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let data = hex::decode(input.get(0)).expect("Invalid data");
+
+    // Print data, invalid UTF-8 is not printed but must be tolerated
+    let res = rtm.try_call("rtm_ext_misc_print_utf8_version_1", &data.encode());
+
+    // Print result
+    println!("{}", if res.is_ok() { "ok" } else { "trap" });
+}
+
+pub fn test_misc_print_utf8(rtm: Runtime, input: ParsedInput) {
+    let sink = PrintSink::default();
+    let mut rtm = rtm.with_print_sink(sink.clone());
+
+    // Parse inputs
+    let data = hex::decode(input.get(0)).expect("Invalid data");
+
+    // Print data, only valid UTF-8 is printed
+    rtm.call("rtm_ext_misc_print_utf8_version_1", &data.encode());
+
+    // Print captured output
+    print_captured(&sink);
+}
+
+/// Check if printed line is a shortened form (start ... end) of the data
+fn is_truncated(line: &str, data: &str) -> bool {
+    match line.find("...") {
+        Some(i) => line.len() < data.len() && data.starts_with(&line[..i]) && data.ends_with(&line[i + 3..]),
+        None => false,
+    }
+}
+
+/// Print long data (count of `a` followed by a hex encoded tail) as UTF-8 or
+/// hex, then print whether the call trapped and for every printed line if it
+/// is complete, shortened for display, sanitized or otherwise altered.
+pub fn test_misc_print_long(rtm: Runtime, input: ParsedInput) {
+    let sink = PrintSink::default();
+    let mut rtm = rtm.with_print_sink(sink.clone()).with_heap_pages(LARGE_DATA_HEAP_PAGES);
+
+    // Parse inputs
+    let kind = str(input.get(0));
+    let size: usize = str(input.get(1)).parse().expect("Invalid size");
+    let mut data = vec![b'a'; size];
+    data.extend(hex::decode(input.get(2)).expect("Invalid tail"));
+
+    let (func, expected) = match kind {
+        "utf8" => ("rtm_ext_misc_print_utf8_version_1", String::from_utf8(data.clone()).ok()),
+        "hex" => ("rtm_ext_misc_print_hex_version_1", Some(hex::encode(&data))),
+        _ => panic!("Unknown print kind: {}", kind),
+    };
+    let sanitized = String::from_utf8_lossy(&data).into_owned();
+
+    // Print data, which must not trap however long it is
+    let res = rtm.try_call(func, &data.encode());
+    println!("{}", if res.is_ok() { "ok" } else { "trap" });
+
+    // Print summary of captured output
+    let printed = sink.take();
+    if printed.is_empty() {
+        println!("(nothing printed)");
+    }
+    for line in printed {
+        match &expected {
+            Some(expected) if line == *expected => println!("print: {} bytes, complete", line.len()),
+            Some(expected) if is_truncated(&line, expected) => println!("print: truncated"),
+            None if line == sanitized => println!("print: {} bytes, sanitized", line.len()),
+            _ => println!("print: {} bytes, altered", line.len()),
+        }
+    }
+}
+
+pub fn test_misc_print_hex(rtm: Runtime, input: ParsedInput) {
+    let sink = PrintSink::default();
+    let mut rtm = rtm.with_print_sink(sink.clone());
+
+    // Parse inputs
+    let data = hex::decode(input.get(0)).expect("Invalid data");
+
+    // Print data as hex
+    rtm.call("rtm_ext_misc_print_hex_version_1", &data.encode());
+
+    // Print captured output
+    print_captured(&sink);
+}
+
+pub fn test_misc_print_num(rtm: Runtime, input: ParsedInput) {
+    let sink = PrintSink::default();
+    let mut rtm = rtm.with_print_sink(sink.clone());
+
+    // Parse inputs
+    let value: u64 = str(input.get(0)).parse().expect("Invalid number");
+
+    // Print number
+    rtm.call("rtm_ext_misc_print_num_version_1", &value.encode());
+
+    // Print captured output
+    print_captured(&sink);
+}
+
+pub fn test_misc_runtime_version(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_wasm_executor();
+
+    // Parse inputs
+    let wasm = hex::decode(input.get(0)).expect("Invalid wasm blob");
+
+    // Read runtime version of blob
+    let version = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_misc_runtime_version_version_1",
+        &wasm.encode(),
+    );
+
+    // Print result
+    match version {
+        Some(version) => println!("{}", hex::encode(version)),
+        None => println!("none"),
+    }
+}
@@ -1,80 +1,277 @@
 mod allocator;
+mod block_builder;
 mod child_storage;
 mod crypto;
+mod disasm;
 mod hashing;
+mod http;
+mod logging;
+mod misc;
+mod native;
+mod offchain;
+mod raw;
+mod sandbox;
 mod storage;
 mod trie;
 mod utils;
+mod wasm;
+
+use std::panic::{self, AssertUnwindSafe};
 
 use clap::ArgMatches;
 use utils::ParsedInput;
 
 pub fn process_host_api_tests(subcmd_matches: &ArgMatches) {
     if let Some(func) = subcmd_matches.value_of("function") {
-        let input : ParsedInput = subcmd_matches.values_of("input").into();
+        if subcmd_matches.is_present("native") && subcmd_matches.is_present("raw") {
+            eprintln!("Raw calls have no native equivalent");
+            std::process::exit(native::EXIT_NOT_SUPPORTED);
+        }
 
-        let mut rtm = utils::Runtime::new();
+        // Run test repeatedly in the same process, to detect state leaking
+        // from one run into the next
+        let repeat: usize = subcmd_matches
+            .value_of("repeat")
+            .map_or(1, |n| n.parse().expect("Invalid repeat count"));
+        for _ in 0..repeat {
+            run_host_api_test(subcmd_matches, func);
 
-        if let Some(env) = subcmd_matches.value_of("environment") {
-            match env {
-                "wasmi" => rtm = rtm.using_wasmi(),
-                "wasmtime" => rtm = rtm.using_wasmtime(),
-                _ => unreachable!(), // Clap should not allow other values
+            // Tests calling a function without native equivalent are skipped
+            if native::unsupported() {
+                std::process::exit(native::EXIT_NOT_SUPPORTED);
             }
         }
+    }
+}
+
+/// Run a single host api test from scratch, i.e. with a new runtime
+fn run_host_api_test(subcmd_matches: &ArgMatches, func: &str) {
+    // Large inputs are passed in a file instead of the argument list, one
+    // argument per line
+    let contents;
+    let input : ParsedInput = match subcmd_matches.value_of("input-file") {
+        Some(path) => {
+            contents = std::fs::read_to_string(path).expect("Failed to read input file");
+            contents.lines().collect::<Vec<_>>().into()
+        }
+        None => subcmd_matches.values_of("input").into(),
+    };
+
+    let mut rtm = utils::Runtime::new();
+
+    if let Some(env) = subcmd_matches.value_of("environment") {
+        match env {
+            "wasmi" => rtm = rtm.using_wasmi(),
+            "wasmtime" => rtm = rtm.using_wasmtime(),
+            _ => unreachable!(), // Clap should not allow other values
+        }
+    }
+
+    // Initialize state as declared by the test vector
+    for entry in subcmd_matches.values_of("storage").into_iter().flatten() {
+        let kv = utils::parse_state_entry(entry, 2);
+        rtm = rtm.with_storage(&kv[0], &kv[1]);
+    }
+    for entry in subcmd_matches.values_of("child-storage").into_iter().flatten() {
+        let ckv = utils::parse_state_entry(entry, 3);
+        rtm = rtm.with_child_storage(&ckv[0], &ckv[1], &ckv[2]);
+    }
+
+    // Use test runtime of other implementations if requested
+    let wasm = subcmd_matches
+        .value_of("wasm")
+        .map(String::from)
+        .or_else(|| std::env::var("WASM_BLOB_PATH").ok());
+    if let Some(path) = wasm {
+        let blob = std::fs::read(&path)
+            .unwrap_or_else(|e| panic!("Failed to read wasm blob {}: {}", path, e));
+        rtm = rtm.with_blob(blob);
+    }
+
+    // Record all calls into the wasm blob for debugging
+    if let Some(path) = subcmd_matches.value_of("export-calls") {
+        rtm = rtm.with_call_export(path);
+    }
+
+    // Cross-check storage root of the test externalities after the test
+    if subcmd_matches.is_present("verify-root") {
+        rtm = rtm.with_root_verification();
+    }
+
+    // Print disassembly of the faulting function on traps
+    if subcmd_matches.is_present("disassemble") {
+        rtm = rtm.with_disassembly();
+    }
+
+    // Call host functions through sp-io directly instead of the wasm blob
+    if subcmd_matches.is_present("native") {
+        rtm = rtm.with_native();
+    }
+
+    // Call host function with raw arguments, bypassing argument encoding
+    if subcmd_matches.is_present("raw") {
+        raw::call_raw(rtm, func, input, subcmd_matches.is_present("classify-trap"));
+        return;
+    }
+
+    // Tests calling a function without native equivalent are skipped, the
+    // same whether they returned or panicked on the failed call
+    let res = panic::catch_unwind(AssertUnwindSafe(move || match func {
+        // storage api
+        "test_storage_init"                  => storage::test_storage_init(rtm),
+        "ext_storage_set_version_1"          => storage::ext_storage_set_version_1(rtm, input),
+        "ext_storage_get_version_1"          => storage::ext_storage_get_version_1(rtm, input),
+        "ext_storage_read_version_1"         => storage::ext_storage_read_version_1(rtm, input),
+        "ext_storage_clear_version_1"        => storage::ext_storage_clear_version_1(rtm, input),
+        "ext_storage_exists_version_1"       => storage::ext_storage_exists_version_1(rtm, input),
+        "ext_storage_clear_prefix_version_1" => storage::ext_storage_clear_prefix_version_1(rtm, input),
+        "ext_storage_append_version_1"       => storage::ext_storage_append_version_1(rtm, input),
+        "ext_storage_root_version_1"         => storage::ext_storage_root_version_1(rtm, input),
+        "ext_storage_next_key_version_1"     => storage::ext_storage_next_key_version_1(rtm, input),
+
+        "test_storage_clear_prefix_limit_root"  => storage::test_storage_clear_prefix_limit_root(rtm, input),
+        "test_storage_root_changes_root"        => storage::test_storage_root_changes_root(rtm, input),
+        "test_storage_append_existing"          => storage::test_storage_append_existing(rtm, input),
+        "test_storage_next_key_order"           => storage::test_storage_next_key_order(rtm, input),
+        "test_storage_clear_prefix_limit_count" => storage::test_storage_clear_prefix_limit_count(rtm, input),
+        "test_storage_exists_read_partial"      => storage::test_storage_exists_read_partial(rtm, input),
+
+        "test_storage_transaction_depth"               => storage::test_storage_transaction_depth(rtm, input),
+        "test_storage_transaction_commit_unbalanced"   => storage::test_storage_transaction_commit_unbalanced(rtm, input),
+        "test_storage_transaction_rollback_unbalanced" => storage::test_storage_transaction_rollback_unbalanced(rtm, input),
+        "test_storage_transaction_restore"             => storage::test_storage_transaction_restore(rtm, input),
+
+        "test_storage_overlay_reads" => storage::test_storage_overlay_reads(rtm, input),
+        "test_storage_long_key"      => storage::test_storage_long_key(rtm, input),
+
+        // child storage api
+        "ext_default_child_storage_set_version_1" => child_storage::ext_default_child_storage_set_version_1(rtm, input),
+        "ext_default_child_storage_get_version_1" => child_storage::ext_default_child_storage_get_version_1(rtm, input),
+        "ext_default_child_storage_read_version_1" => child_storage::ext_default_child_storage_read_version_1(rtm, input),
+        "ext_default_child_storage_clear_version_1" => child_storage::ext_default_child_storage_clear_version_1(rtm, input),
+        "ext_default_child_storage_storage_kill_version_1" => child_storage::ext_default_child_storage_storage_kill_version_1(rtm, input),
+        "ext_default_child_storage_exists_version_1" => child_storage::ext_default_child_storage_exists_version_1(rtm, input),
+        "ext_default_child_storage_clear_prefix_version_1" => child_storage::ext_default_child_storage_clear_prefix_version_1(rtm, input),
+        "ext_default_child_storage_root_version_1" => child_storage::ext_default_child_storage_root_version_1(rtm, input),
+        "ext_default_child_storage_next_key_version_1" => child_storage::ext_default_child_storage_next_key_version_1(rtm, input),
+
+        "test_child_storage_prefix_root" => child_storage::test_child_storage_prefix_root(rtm, input),
+        "test_child_storage_kill_root"   => child_storage::test_child_storage_kill_root(rtm, input),
+
+        // crypto api
+        "ext_crypto_ed25519_public_keys_version_1" => crypto::ext_crypto_ed25519_public_keys_version_1(rtm, input),
+        "ext_crypto_ed25519_generate_version_1" => crypto::ext_crypto_ed25519_generate_version_1(rtm, input),
+        "ext_crypto_ed25519_sign_version_1" => crypto::ext_crypto_ed25519_sign_version_1(rtm, input),
+        "ext_crypto_ed25519_verify_version_1" => crypto::ext_crypto_ed25519_verify_version_1(rtm, input),
+        "test_crypto_ed25519_verify_encodings" => crypto::test_crypto_ed25519_verify_encodings(rtm, input),
+
+        "ext_crypto_sr25519_public_keys_version_1" => crypto::ext_crypto_sr25519_public_keys_version_1(rtm, input),
+        "ext_crypto_sr25519_generate_version_1" => crypto::ext_crypto_sr25519_generate_version_1(rtm, input),
+        "ext_crypto_sr25519_sign_version_1" => crypto::ext_crypto_sr25519_sign_version_1(rtm, input),
+        "ext_crypto_sr25519_verify_version_1" => crypto::ext_crypto_sr25519_verify_version_1(rtm, input),
+
+        "test_crypto_secp256k1_ecdsa_recover" => crypto::test_crypto_secp256k1_ecdsa_recover(rtm, input),
+        "test_crypto_secp256k1_ecdsa_recover_compressed" => crypto::test_crypto_secp256k1_ecdsa_recover_compressed(rtm, input),
+
+        "test_crypto_batch_verify" => crypto::test_crypto_batch_verify(rtm, input),
+
+        "test_crypto_ed25519_generate_randomness" => crypto::test_crypto_ed25519_generate_randomness(rtm, input),
+        "test_crypto_sr25519_generate_randomness" => crypto::test_crypto_sr25519_generate_randomness(rtm, input),
+        "test_crypto_ed25519_generate_seeds" => crypto::test_crypto_ed25519_generate_seeds(rtm, input),
+        "test_crypto_sr25519_generate_seeds" => crypto::test_crypto_sr25519_generate_seeds(rtm, input),
+        "test_crypto_ed25519_keystore_concurrency" => crypto::test_crypto_ed25519_keystore_concurrency(rtm, input),
+        "test_crypto_sr25519_keystore_concurrency" => crypto::test_crypto_sr25519_keystore_concurrency(rtm, input),
+
+        // hashing api
+        "ext_hashing_blake2_128_version_1" => hashing::ext_hashing_version_1(rtm, "blake2_128", input),
+        "ext_hashing_blake2_256_version_1" => hashing::ext_hashing_version_1(rtm, "blake2_256", input),
+        "ext_hashing_keccak_256_version_1" => hashing::ext_hashing_version_1(rtm, "keccak_256", input),
+        "ext_hashing_sha2_256_version_1"   => hashing::ext_hashing_version_1(rtm, "sha2_256", input),
+        "ext_hashing_twox_64_version_1"    => hashing::ext_hashing_version_1(rtm, "twox_64", input),
+        "ext_hashing_twox_128_version_1"   => hashing::ext_hashing_version_1(rtm, "twox_128", input),
+        "ext_hashing_twox_256_version_1"   => hashing::ext_hashing_version_1(rtm, "twox_256", input),
+
+        "test_hashing_blake2_128_repeated" => hashing::test_hashing_repeated(rtm, "blake2_128", input),
+        "test_hashing_blake2_256_repeated" => hashing::test_hashing_repeated(rtm, "blake2_256", input),
+        "test_hashing_keccak_256_repeated" => hashing::test_hashing_repeated(rtm, "keccak_256", input),
+        "test_hashing_sha2_256_repeated"   => hashing::test_hashing_repeated(rtm, "sha2_256", input),
+        "test_hashing_twox_64_repeated"    => hashing::test_hashing_repeated(rtm, "twox_64", input),
+        "test_hashing_twox_128_repeated"   => hashing::test_hashing_repeated(rtm, "twox_128", input),
+        "test_hashing_twox_256_repeated"   => hashing::test_hashing_repeated(rtm, "twox_256", input),
+
+        // allocator api
+        "ext_allocator_malloc_version_1" => allocator::ext_allocator_malloc_version_1(rtm, input),
+        "ext_allocator_free_version_1" => allocator::ext_allocator_free_version_1(rtm, input),
+
+        "test_allocator_zero_size"     => allocator::test_allocator_zero_size(rtm),
+        "test_allocator_roundtrip"     => allocator::test_allocator_roundtrip(rtm, input),
+        "test_allocator_exhaust"       => allocator::test_allocator_exhaust(rtm, input),
+        "test_allocator_double_free"   => allocator::test_allocator_double_free(rtm, input),
+        "test_allocator_fragmentation" => allocator::test_allocator_fragmentation(rtm, input),
+
+        // trie api
+        "ext_trie_blake2_256_root_version_1" => trie::ext_trie_blake2_256_root_version_1(rtm, input),
+        "ext_trie_blake2_256_ordered_root_version_1" => trie::ext_trie_blake2_256_ordered_root_version_1(rtm, input),
+        "ext_trie_blake2_256_verify_proof_version_1" => trie::ext_trie_blake2_256_verify_proof_version_1(rtm, input),
+        "ext_trie_keccak_256_verify_proof_version_1" => trie::ext_trie_keccak_256_verify_proof_version_1(rtm, input),
+
+        "test_trie_blake2_256_root_generated"         => trie::test_trie_blake2_256_root_generated(rtm, input),
+        "test_trie_blake2_256_ordered_root_generated" => trie::test_trie_blake2_256_ordered_root_generated(rtm, input),
+        "test_trie_long_key"                          => trie::test_trie_long_key(rtm, input),
+
+        // misc api
+        "test_misc_print_utf8_encodings" => misc::test_misc_print_utf8_encodings(rtm, input),
+        "test_misc_print_long"           => misc::test_misc_print_long(rtm, input),
+
+        "test_misc_print_utf8"      => misc::test_misc_print_utf8(rtm, input),
+        "test_misc_print_hex"       => misc::test_misc_print_hex(rtm, input),
+        "test_misc_print_num"       => misc::test_misc_print_num(rtm, input),
+        "test_misc_runtime_version" => misc::test_misc_runtime_version(rtm, input),
+
+        // logging api
+        "test_logging_log_encodings" => logging::test_logging_log_encodings(rtm, input),
+        "test_logging_log"           => logging::test_logging_log(rtm, input),
+
+        // offchain api
+        "ext_offchain_is_validator_version_1"                  => offchain::ext_offchain_is_validator_version_1(rtm, input),
+        "ext_offchain_random_seed_version_1"                   => offchain::ext_offchain_random_seed_version_1(rtm, input),
+        "ext_offchain_timestamp_version_1"                     => offchain::ext_offchain_timestamp_version_1(rtm, input),
+        "ext_offchain_sleep_until_version_1"                   => offchain::ext_offchain_sleep_until_version_1(rtm, input),
+        "ext_offchain_local_storage_set_version_1"             => offchain::ext_offchain_local_storage_set_version_1(rtm, input),
+        "ext_offchain_local_storage_compare_and_set_version_1" => offchain::ext_offchain_local_storage_compare_and_set_version_1(rtm, input),
+
+        "test_offchain_capabilities"   => offchain::test_offchain_capabilities(rtm, input),
+        "test_offchain_http"           => offchain::test_offchain_http(rtm, input),
+        "test_offchain_http_deadlines" => offchain::test_offchain_http_deadlines(rtm, input),
+
+        // sandbox api
+        "ext_sandbox_instantiate_version_1"       => sandbox::ext_sandbox_instantiate_version_1(rtm, input),
+        "ext_sandbox_invoke_version_1"            => sandbox::ext_sandbox_invoke_version_1(rtm, input),
+        "ext_sandbox_instance_teardown_version_1" => sandbox::ext_sandbox_instance_teardown_version_1(rtm, input),
+        "ext_sandbox_memory_new_version_1"        => sandbox::ext_sandbox_memory_new_version_1(rtm, input),
+        "ext_sandbox_memory_get_version_1"        => sandbox::ext_sandbox_memory_get_version_1(rtm, input),
+        "ext_sandbox_memory_set_version_1"        => sandbox::ext_sandbox_memory_set_version_1(rtm, input),
+        "ext_sandbox_memory_teardown_version_1"   => sandbox::ext_sandbox_memory_teardown_version_1(rtm, input),
+
+        "test_sandbox_memory_limit" => sandbox::test_sandbox_memory_limit(rtm, input),
+        "test_sandbox_nested"       => sandbox::test_sandbox_nested(rtm, input),
+
+        // module shape of the runtime
+        "test_wasm_probe" => wasm::test_wasm_probe(rtm, input),
+
+        // block builder (tester runtime)
+        "test_block_weight_accounting" => block_builder::test_block_weight_accounting(rtm, input),
+        "test_block_length_accounting" => block_builder::test_block_length_accounting(rtm, input),
+        "test_block_initialization"    => block_builder::test_block_initialization(rtm, input),
+        "test_transactional_depth"     => block_builder::test_transactional_depth(rtm, input),
+        "test_tester_api_version"      => block_builder::test_tester_api_version(rtm, input),
+
+        _ => panic!("specified function not available"),
+    }));
 
-        match func {
-            // storage api
-            "test_storage_init"                  => storage::test_storage_init(rtm),
-            "ext_storage_set_version_1"          => storage::ext_storage_set_version_1(rtm, input),
-            "ext_storage_get_version_1"          => storage::ext_storage_get_version_1(rtm, input),
-            "ext_storage_read_version_1"         => storage::ext_storage_read_version_1(rtm, input),
-            "ext_storage_clear_version_1"        => storage::ext_storage_clear_version_1(rtm, input),
-            "ext_storage_exists_version_1"       => storage::ext_storage_exists_version_1(rtm, input),
-            "ext_storage_clear_prefix_version_1" => storage::ext_storage_clear_prefix_version_1(rtm, input),
-            "ext_storage_append_version_1"       => storage::ext_storage_append_version_1(rtm, input),
-            "ext_storage_root_version_1"         => storage::ext_storage_root_version_1(rtm, input),
-            "ext_storage_next_key_version_1"     => storage::ext_storage_next_key_version_1(rtm, input),
-
-            // child storage api
-            "ext_default_child_storage_set_version_1" => child_storage::ext_default_child_storage_set_version_1(rtm, input),
-            "ext_default_child_storage_get_version_1" => child_storage::ext_default_child_storage_get_version_1(rtm, input),
-            "ext_default_child_storage_read_version_1" => child_storage::ext_default_child_storage_read_version_1(rtm, input),
-            "ext_default_child_storage_clear_version_1" => child_storage::ext_default_child_storage_clear_version_1(rtm, input),
-            "ext_default_child_storage_storage_kill_version_1" => child_storage::ext_default_child_storage_storage_kill_version_1(rtm, input),
-            "ext_default_child_storage_exists_version_1" => child_storage::ext_default_child_storage_exists_version_1(rtm, input),
-            "ext_default_child_storage_clear_prefix_version_1" => child_storage::ext_default_child_storage_clear_prefix_version_1(rtm, input),
-            "ext_default_child_storage_root_version_1" => child_storage::ext_default_child_storage_root_version_1(rtm, input),
-            "ext_default_child_storage_next_key_version_1" => child_storage::ext_default_child_storage_next_key_version_1(rtm, input),
-
-            // crypto api
-            "ext_crypto_ed25519_public_keys_version_1" => crypto::ext_crypto_ed25519_public_keys_version_1(rtm, input),
-            "ext_crypto_ed25519_generate_version_1" => crypto::ext_crypto_ed25519_generate_version_1(rtm, input),
-            "ext_crypto_ed25519_sign_version_1" => crypto::ext_crypto_ed25519_sign_version_1(rtm, input),
-            "ext_crypto_ed25519_verify_version_1" => crypto::ext_crypto_ed25519_verify_version_1(rtm, input),
-            "ext_crypto_sr25519_public_keys_version_1" => crypto::ext_crypto_sr25519_public_keys_version_1(rtm, input),
-            "ext_crypto_sr25519_generate_version_1" => crypto::ext_crypto_sr25519_generate_version_1(rtm, input),
-            "ext_crypto_sr25519_sign_version_1" => crypto::ext_crypto_sr25519_sign_version_1(rtm, input),
-            "ext_crypto_sr25519_verify_version_1" => crypto::ext_crypto_sr25519_verify_version_1(rtm, input),
-
-            // hashing api
-            "ext_hashing_blake2_128_version_1" => hashing::ext_hashing_version_1(rtm, "blake2_128", input),
-            "ext_hashing_blake2_256_version_1" => hashing::ext_hashing_version_1(rtm, "blake2_256", input),
-            "ext_hashing_keccak_256_version_1" => hashing::ext_hashing_version_1(rtm, "keccak_256", input),
-            "ext_hashing_sha2_256_version_1"   => hashing::ext_hashing_version_1(rtm, "sha2_256", input),
-            "ext_hashing_twox_64_version_1"    => hashing::ext_hashing_version_1(rtm, "twox_64", input),
-            "ext_hashing_twox_128_version_1"   => hashing::ext_hashing_version_1(rtm, "twox_128", input),
-            "ext_hashing_twox_256_version_1"   => hashing::ext_hashing_version_1(rtm, "twox_256", input),
-
-            // allocator api
-            "ext_allocator_malloc_version_1" => allocator::ext_allocator_malloc_version_1(rtm, input),
-            "ext_allocator_free_version_1" => allocator::ext_allocator_free_version_1(rtm, input),
-
-            // trie api
-            "ext_trie_blake2_256_root_version_1" => trie::ext_trie_blake2_256_root_version_1(rtm, input),
-            "ext_trie_blake2_256_ordered_root_version_1" => trie::ext_trie_blake2_256_ordered_root_version_1(rtm, input),
-
-            _ => panic!("specified function not available"),
+    if let Err(e) = res {
+        if !native::unsupported() {
+            panic::resume_unwind(e);
         }
     }
 }
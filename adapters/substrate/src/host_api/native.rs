@@ -0,0 +1,297 @@
+//! Native oracle: Test functions of the runtime executed natively, i.e. the
+//! host functions they wrap are called through sp-io directly instead of from
+//! within the wasm blob. Arguments and results are SCALE encoded exactly like
+//! those of the exported functions of the runtime, so all tests can be run
+//! unchanged against either of them.
+
+use std::convert::{TryFrom, TryInto};
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{
+    crypto::KeyTypeId,
+    ecdsa, ed25519,
+    offchain::{HttpError, HttpRequestId, HttpRequestStatus, StorageKind, Timestamp},
+    sr25519, LogLevel, H256,
+};
+use sp_io::{crypto, default_child_storage, storage, KillStorageResult};
+
+/// Exit code of tests not supported by the adapter (ENOTSUP), which are
+/// skipped by the testsuite
+pub const EXIT_NOT_SUPPORTED: i32 = 95;
+
+/// Set once a function without native equivalent was called, which makes the
+/// whole test unsupported no matter how it handled the failed call
+static UNSUPPORTED: AtomicBool = AtomicBool::new(false);
+
+/// Whether a function without native equivalent was called
+pub fn unsupported() -> bool {
+    UNSUPPORTED.load(Ordering::SeqCst)
+}
+
+/// Decode arguments in order of declaration and encode result of body
+macro_rules! native {
+    ($input:ident, || $body:expr) => {
+        Encode::encode(&$body)
+    };
+    ($input:ident, |$($arg:ident: $ty:ty),*| $body:expr) => {{
+        $( let $arg = <$ty>::decode($input).expect("Failed to decode arguments"); )*
+        Encode::encode(&$body)
+    }};
+}
+
+/// Conversion of a parameter as declared in host-functions.txt into the type
+/// taken by sp-io, which is inferred from the called function
+trait Arg<T> {
+    fn arg(self) -> T;
+}
+
+impl<T: Clone> Arg<T> for &T {
+    fn arg(self) -> T {
+        self.clone()
+    }
+}
+
+impl<'a, T> Arg<&'a [T]> for &'a Vec<T> {
+    fn arg(self) -> &'a [T] {
+        self
+    }
+}
+
+impl<'a> Arg<&'a str> for &'a Vec<u8> {
+    fn arg(self) -> &'a str {
+        std::str::from_utf8(self).expect("Invalid utf8 string")
+    }
+}
+
+/// Fixed size data (e.g. keys) taken as its wrapper type
+macro_rules! from_raw {
+    ($($ty:ty: $n:expr => $from:expr),*) => {
+        $( impl Arg<$ty> for &[u8; $n] {
+            fn arg(self) -> $ty {
+                ($from)(*self)
+            }
+        } )*
+    };
+}
+
+from_raw!(
+    KeyTypeId: 4 => KeyTypeId,
+    H256: 32 => H256::from,
+    ed25519::Public: 32 => ed25519::Public::from_raw,
+    sr25519::Public: 32 => sr25519::Public::from_raw,
+    ed25519::Signature: 64 => ed25519::Signature::from_raw,
+    sr25519::Signature: 64 => sr25519::Signature::from_raw
+);
+
+impl Arg<LogLevel> for &u32 {
+    fn arg(self) -> LogLevel {
+        LogLevel::from(*self)
+    }
+}
+
+impl Arg<StorageKind> for &u32 {
+    fn arg(self) -> StorageKind {
+        StorageKind::try_from(*self).expect("Invalid storage kind")
+    }
+}
+
+impl Arg<HttpRequestId> for &u32 {
+    fn arg(self) -> HttpRequestId {
+        HttpRequestId(u16::try_from(*self).expect("Invalid request id"))
+    }
+}
+
+impl Arg<Vec<HttpRequestId>> for &Vec<u16> {
+    fn arg(self) -> Vec<HttpRequestId> {
+        self.iter().copied().map(HttpRequestId).collect()
+    }
+}
+
+impl Arg<Timestamp> for &u64 {
+    fn arg(self) -> Timestamp {
+        Timestamp::from_unix_millis(*self)
+    }
+}
+
+impl Arg<Option<Timestamp>> for &Option<u64> {
+    fn arg(self) -> Option<Timestamp> {
+        self.map(Timestamp::from_unix_millis)
+    }
+}
+
+/// Conversion of a plain value returned by sp-io into the declared one
+trait Value<T> {
+    fn value(self) -> T;
+}
+
+impl<T> Value<T> for T {
+    fn value(self) -> T {
+        self
+    }
+}
+
+impl Value<u32> for bool {
+    fn value(self) -> u32 {
+        self as u32
+    }
+}
+
+impl Value<u64> for Timestamp {
+    fn value(self) -> u64 {
+        self.unix_millis()
+    }
+}
+
+/// Result of sp-io as passed by the host to the runtime, i.e. SCALE encoded
+/// unless it is raw data
+trait Encoded {
+    fn encoded(self) -> Vec<u8>;
+}
+
+impl Encoded for Vec<u8> {
+    fn encoded(self) -> Vec<u8> {
+        self
+    }
+}
+
+macro_rules! encoded {
+    ($($ty:ty),*) => {
+        $( impl Encoded for $ty {
+            fn encoded(self) -> Vec<u8> {
+                Encode::encode(&self)
+            }
+        } )*
+    };
+}
+
+encoded!(
+    KillStorageResult,
+    Result<HttpRequestId, ()>,
+    Result<(), ()>,
+    Result<(), HttpError>,
+    Vec<HttpRequestStatus>,
+    Vec<(Vec<u8>, Vec<u8>)>
+);
+
+// Test functions of all host functions with a native equivalent, generated
+// from host-functions.txt by the build script of the runtime
+runtime::native_functions!();
+
+/// Tests combining several calls, the same as within the runtime
+mod combined {
+    use super::host;
+    runtime::combined_tests!();
+}
+
+/// Read value at offset into buffer of given size, trimmed to the bytes written
+fn read_trimmed(read: impl FnOnce(&mut [u8]) -> Option<u32>, buffer_size: u32) -> Option<Vec<u8>> {
+    let mut buffer = vec![0u8; buffer_size as usize];
+    read(&mut buffer).map(|n| buffer[..(n.min(buffer_size) as usize)].to_vec())
+}
+
+/// Natively execute test function of the runtime against the current
+/// externalities, returns none if it has no native equivalent.
+fn dispatch(func: &str, input: &mut &[u8]) -> Option<Vec<u8>> {
+    if let Some(output) = dispatch_declared(func, input) {
+        return Some(output);
+    }
+
+    // Test functions written by hand within the runtime
+    Some(match func {
+        // Storage API
+        "rtm_ext_storage_read_version_1" => native!(input, |key: Vec<u8>, offset: u32, buffer_size: u32| {
+            read_trimmed(|buffer| storage::read(&key, buffer, offset), buffer_size)
+        }),
+        "rtm_ext_storage_read_partial_version_1" => native!(input, |key: Vec<u8>, offset: u32, buffer_size: u32| {
+            let mut buffer = vec![0xffu8; buffer_size as usize];
+            (storage::read(&key, &mut buffer, offset), buffer)
+        }),
+        "rtm_ext_storage_nested_transactions_version_1" => {
+            native!(input, |depth: u32, key: Vec<u8>, value: Vec<u8>, commit: bool| {
+                combined::nested_transactions(depth, key, value, commit)
+            })
+        }
+        "rtm_ext_storage_overlay_reads_version_1" => native!(input, |key: Vec<u8>, value: Vec<u8>, new_value: Vec<u8>| {
+            combined::overlay_reads(key, value, new_value)
+        }),
+        "rtm_ext_storage_transaction_overwrite_version_1" => {
+            native!(input, |key: Vec<u8>, value: Option<Vec<u8>>, commit: bool| {
+                combined::transaction_overwrite(key, value, commit)
+            })
+        }
+        "rtm_ext_storage_unbalanced_transactions_version_1" => native!(input, |depth: u32, commit: bool| {
+            combined::unbalanced_transactions(depth, commit)
+        }),
+
+        // Default child storage API
+        "rtm_ext_default_child_storage_read_version_1" => {
+            native!(input, |child: Vec<u8>, key: Vec<u8>, offset: u32, buffer_size: u32| {
+                read_trimmed(|buffer| default_child_storage::read(&child, &key, buffer, offset), buffer_size)
+            })
+        }
+
+        // Crypto API, results of the recovery are returned SCALE encoded like
+        // by the runtime
+        "rtm_ext_crypto_secp256k1_ecdsa_recover_version_1" => native!(input, |sig: Vec<u8>, msg: Vec<u8>| {
+            let sig: [u8; 65] = sig[..].try_into().expect("Invalid signature size");
+            let msg: [u8; 32] = msg[..].try_into().expect("Invalid message size");
+            crypto::secp256k1_ecdsa_recover(&sig, &msg).encode()
+        }),
+        "rtm_ext_crypto_secp256k1_ecdsa_recover_compressed_version_1" => native!(input, |sig: Vec<u8>, msg: Vec<u8>| {
+            let sig: [u8; 65] = sig[..].try_into().expect("Invalid signature size");
+            let msg: [u8; 32] = msg[..].try_into().expect("Invalid message size");
+            crypto::secp256k1_ecdsa_recover_compressed(&sig, &msg).encode()
+        }),
+
+        "rtm_ext_crypto_batch_verify_version_1" => {
+            native!(input, |ed25519_sigs: Vec<([u8; 64], Vec<u8>, [u8; 32])>,
+                            sr25519_sigs: Vec<([u8; 64], Vec<u8>, [u8; 32])>,
+                            ecdsa_sigs: Vec<([u8; 65], Vec<u8>, [u8; 33])>| {
+                crypto::start_batch_verify();
+                for (sig, msg, pubkey) in ed25519_sigs {
+                    crypto::ed25519_batch_verify(&ed25519::Signature::from_raw(sig), &msg, &ed25519::Public::from_raw(pubkey));
+                }
+                for (sig, msg, pubkey) in sr25519_sigs {
+                    crypto::sr25519_batch_verify(&sr25519::Signature::from_raw(sig), &msg, &sr25519::Public::from_raw(pubkey));
+                }
+                for (sig, msg, pubkey) in ecdsa_sigs {
+                    crypto::ecdsa_batch_verify(&ecdsa::Signature::from_raw(sig), &msg, &ecdsa::Public::from_raw(pubkey));
+                }
+                crypto::finish_batch_verify() as u32
+            })
+        }
+
+        // All others (e.g. allocator and sandbox) only exist within wasm
+        _ => return None,
+    })
+}
+
+/// Call test function natively, failures are reported like traps of the wasm
+/// blob. Returns none if the function has no native equivalent, which is
+/// remembered for `unsupported`.
+pub fn call(func: &str, args: &[u8]) -> Option<Result<Vec<u8>, String>> {
+    // Panics of the host functions are the native equivalent of traps, which
+    // are only reported by the tests themselves
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let res = panic::catch_unwind(AssertUnwindSafe(|| dispatch(func, &mut &args[..])));
+    panic::set_hook(hook);
+
+    match res {
+        Ok(None) => {
+            UNSUPPORTED.store(true, Ordering::SeqCst);
+            None
+        }
+        Ok(output) => output.map(Ok),
+        Err(e) => {
+            let message = e
+                .downcast_ref::<&str>()
+                .map(|m| m.to_string())
+                .or_else(|| e.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Some(Err(format!("Native call panicked: {}", message)))
+        }
+    }
+}
@@ -0,0 +1,387 @@
+use crate::host_api::utils::{str, ParsedInput, Runtime};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::offchain::{Capabilities, HttpError, HttpRequestStatus, StorageKind};
+use std::time::{Duration, Instant};
+
+/// Kinds of offchain local storage, which are independent of each other
+const STORAGE_KINDS: [(&str, StorageKind); 2] = [
+    ("persistent", StorageKind::PERSISTENT),
+    ("local", StorageKind::LOCAL),
+];
+
+/// Configure offchain context of runtime: A validator with all offchain
+/// capabilities, one without any capabilities, a node without any capabilities
+/// which is not a validator or no offchain context at all.
+fn with_context(rtm: Runtime, context: &str) -> Runtime {
+    match context {
+        "validator" => rtm.with_offchain(),
+        "restricted" => rtm.with_offchain_capabilities(Capabilities::none()),
+        "restricted-non-validator" => rtm.with_offchain_non_validator(Capabilities::none()),
+        "unavailable" => rtm,
+        _ => panic!("Unknown offchain context: {}", context),
+    }
+}
+
+/// The Wasm function tests whether the host reports to be a validator
+pub fn ext_offchain_is_validator_version_1(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let context = str(input.get(0));
+
+    let mut rtm = with_context(rtm, context);
+
+    let res = rtm.call_and_decode::<u32>("rtm_ext_offchain_is_validator_version_1", &[]);
+    assert!(res <= 1);
+
+    println!("{}", res);
+}
+
+/// Call offchain host functions in the configured context and report which
+/// of them are not available, i.e. fail instead of returning a result.
+pub fn test_offchain_capabilities(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let context = str(input.get(0));
+
+    let mut rtm = with_context(rtm, context);
+
+    // Unavailable functions panic in the host, only their outcome is of interest
+    match rtm.try_call_silent("rtm_ext_offchain_is_validator_version_1", &[]) {
+        Ok(res) => println!("is_validator: {}", u32::decode(&mut res.as_slice()).unwrap()),
+        Err(_) => println!("is_validator: error"),
+    }
+
+    // Random seed and timestamp differ between runs, only report availability
+    for func in &["random_seed", "timestamp"] {
+        match rtm.try_call_silent(&format!("rtm_ext_offchain_{}_version_1", func), &[]) {
+            Ok(_) => println!("{}: ok", func),
+            Err(_) => println!("{}: error", func),
+        }
+    }
+}
+
+/// The Wasm function returns a random seed, which differs between hosts and runs
+pub fn ext_offchain_random_seed_version_1(rtm: Runtime, _input: ParsedInput) {
+    let mut rtm = rtm.with_offchain();
+
+    let seed = rtm.call_and_decode::<Vec<u8>>("rtm_ext_offchain_random_seed_version_1", &[]);
+    assert_eq!(seed.len(), 32);
+
+    println!("random_seed: {}", hex::encode(seed));
+}
+
+/// The Wasm function returns the current timestamp in milliseconds
+pub fn ext_offchain_timestamp_version_1(rtm: Runtime, _input: ParsedInput) {
+    let mut rtm = rtm.with_offchain();
+
+    let timestamp = rtm.call_and_decode::<u64>("rtm_ext_offchain_timestamp_version_1", &[]);
+
+    println!("timestamp: {}", timestamp);
+}
+
+/// The Wasm function sleeps until the deadline (absolute in milliseconds since
+/// the unix epoch, or relative to now if prefixed with `+`), blocking until it
+/// is reached, after which the timestamp must not be earlier than the deadline
+pub fn ext_offchain_sleep_until_version_1(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_offchain_http();
+
+    // Parse inputs
+    let deadline = str(input.get(0));
+
+    let now = rtm.call_and_decode::<u64>("rtm_ext_offchain_timestamp_version_1", &[]);
+    let deadline: u64 = match deadline.strip_prefix('+') {
+        Some(offset) => now + offset.parse::<u64>().expect("Invalid deadline"),
+        None => deadline.parse().expect("Invalid deadline"),
+    };
+
+    let start = Instant::now();
+    rtm.call("rtm_ext_offchain_sleep_until_version_1", &deadline.encode());
+    let elapsed = start.elapsed();
+    let timestamp = rtm.call_and_decode::<u64>("rtm_ext_offchain_timestamp_version_1", &[]);
+
+    // Timestamps are truncated to milliseconds, so allow for one of them
+    let blocked = elapsed + Duration::from_millis(1) >= Duration::from_millis(deadline.saturating_sub(now));
+
+    if !blocked {
+        println!("deadline not awaited");
+    } else if timestamp >= deadline {
+        println!("deadline reached");
+    } else {
+        println!("deadline missed");
+    }
+}
+
+fn local_storage_get(rtm: &mut Runtime, kind: StorageKind, key: &[u8]) -> String {
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_offchain_local_storage_get_version_1",
+        &(kind as u32, key).encode(),
+    );
+    match res {
+        Some(value) => str(&value).to_string(),
+        None => String::from("none"),
+    }
+}
+
+/// The Wasm function sets a value in each kind of local storage, which must
+/// only be visible in the storage kind it was set in
+pub fn ext_offchain_local_storage_set_version_1(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_offchain();
+
+    // Parse inputs
+    let key = input.get(0);
+    let value = input.get(1);
+
+    for (name, kind) in &STORAGE_KINDS {
+        rtm.call(
+            "rtm_ext_offchain_local_storage_set_version_1",
+            &(*kind as u32, key, value).encode(),
+        );
+
+        // Print value in all storage kinds after each set
+        for (other, other_kind) in &STORAGE_KINDS {
+            let res = local_storage_get(&mut rtm, *other_kind, key);
+            println!("{} after set in {}: {}", other, name, res);
+        }
+    }
+}
+
+/// The Wasm function only sets a value if the expected old value matches
+pub fn ext_offchain_local_storage_compare_and_set_version_1(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_offchain();
+
+    // Parse inputs
+    let key = input.get(0);
+    let value1 = input.get(1);
+    let value2 = input.get(2);
+
+    // Swaps from missing, missing again, wrong and expected value
+    let swaps: [(Option<&[u8]>, &[u8]); 4] = [
+        (None, value1),
+        (None, value2),
+        (Some(value2), value2),
+        (Some(value1), value2),
+    ];
+
+    for (name, kind) in &STORAGE_KINDS {
+        for (old, new) in &swaps {
+            let res = rtm.call_and_decode::<u32>(
+                "rtm_ext_offchain_local_storage_compare_and_set_version_1",
+                &(*kind as u32, key, old.map(|v| v.to_vec()), new).encode(),
+            );
+            assert!(res <= 1);
+
+            let old = old.map(str).unwrap_or("none");
+            println!("{}: {} -> {}: {}", name, old, str(new), res);
+        }
+        println!("{}: {}", name, local_storage_get(&mut rtm, *kind, key));
+    }
+}
+
+/// Call wrapper returning the SCALE encoded result of a host function
+fn call_and_decode_result<T: Decode>(rtm: &mut Runtime, func: &str, args: &[u8]) -> T {
+    let res = rtm.call_and_decode::<Vec<u8>>(func, args);
+    Decode::decode(&mut res.as_slice()).expect("Failed to decode result of host function")
+}
+
+/// Perform a HTTP request with method, path (relative to the mock server of
+/// the testsuite in `HTTP_MOCK_URL`), body and timeout in milliseconds, then
+/// print its status, the headers set by the mock server and the body.
+pub fn test_offchain_http(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_offchain_http();
+
+    // Parse inputs
+    let method = input.get(0);
+    let path = str(input.get(1));
+    let body = input.get(2);
+    let timeout: u64 = str(input.get(3)).parse().expect("Invalid timeout");
+
+    let server = std::env::var("HTTP_MOCK_URL").expect("HTTP_MOCK_URL is not set");
+    let uri = format!("{}{}", server, path);
+
+    let now = rtm.call_and_decode::<u64>("rtm_ext_offchain_timestamp_version_1", &[]);
+    let deadline = Some(now + timeout);
+
+    // Start request with header, then write body and finish it with an empty chunk
+    let id = call_and_decode_result::<Result<u16, ()>>(
+        &mut rtm,
+        "rtm_ext_offchain_http_request_start_version_1",
+        &(method, uri.as_bytes(), &b""[..]).encode(),
+    )
+    .expect("Failed to start request");
+
+    call_and_decode_result::<Result<(), ()>>(
+        &mut rtm,
+        "rtm_ext_offchain_http_request_add_header_version_1",
+        &(id as u32, &b"X-Test"[..], &b"polkadot"[..]).encode(),
+    )
+    .expect("Failed to add header");
+
+    for chunk in &[body, &b""[..]] {
+        call_and_decode_result::<Result<(), HttpError>>(
+            &mut rtm,
+            "rtm_ext_offchain_http_request_write_body_version_1",
+            &(id as u32, chunk, deadline).encode(),
+        )
+        .expect("Failed to write body");
+    }
+
+    // Wait for response
+    let status = call_and_decode_result::<Vec<HttpRequestStatus>>(
+        &mut rtm,
+        "rtm_ext_offchain_http_response_wait_version_1",
+        &(vec![id], deadline).encode(),
+    );
+    match status.as_slice() {
+        [HttpRequestStatus::Finished(code)] => println!("status: {}", code),
+        [HttpRequestStatus::DeadlineReached] => {
+            println!("status: deadline reached");
+            return;
+        }
+        [HttpRequestStatus::IoError] => {
+            println!("status: io error");
+            return;
+        }
+        _ => panic!("Invalid status of request: {:?}", status),
+    }
+
+    // Only headers of the mock server are deterministic
+    let mut headers: Vec<(String, String)> = call_and_decode_result::<Vec<(Vec<u8>, Vec<u8>)>>(
+        &mut rtm,
+        "rtm_ext_offchain_http_response_headers_version_1",
+        &(id as u32).encode(),
+    )
+    .into_iter()
+    .map(|(name, value)| (str(&name).to_lowercase(), str(&value).to_string()))
+    .filter(|(name, _)| name.starts_with("x-mock"))
+    .collect();
+    headers.sort();
+
+    for (name, value) in headers {
+        println!("header: {}: {}", name, value);
+    }
+
+    // Read body in small chunks, until none is left
+    let mut response = Vec::new();
+    loop {
+        let (res, buffer) = rtm.call_and_decode::<(Vec<u8>, Vec<u8>)>(
+            "rtm_ext_offchain_http_response_read_body_version_1",
+            &(id as u32, 8u32, deadline).encode(),
+        );
+        let n = Result::<u32, HttpError>::decode(&mut res.as_slice())
+            .expect("Failed to decode result of host function")
+            .expect("Failed to read body");
+        if n == 0 {
+            break;
+        }
+        response.extend_from_slice(&buffer[..n as usize]);
+    }
+
+    println!("body: {}", String::from_utf8_lossy(&response));
+}
+
+/// Read next chunk of response body into a small buffer
+fn read_body(rtm: &mut Runtime, id: u16, deadline: Option<u64>) -> Result<Vec<u8>, HttpError> {
+    let (res, buffer) = rtm.call_and_decode::<(Vec<u8>, Vec<u8>)>(
+        "rtm_ext_offchain_http_response_read_body_version_1",
+        &(id as u32, 8u32, deadline).encode(),
+    );
+    Result::<u32, HttpError>::decode(&mut res.as_slice())
+        .expect("Failed to decode result of host function")
+        .map(|n| buffer[..n as usize].to_vec())
+}
+
+fn http_error(error: &HttpError) -> &'static str {
+    match error {
+        HttpError::DeadlineReached => "deadline reached",
+        HttpError::IoError => "io error",
+        HttpError::Invalid => "invalid",
+    }
+}
+
+/// Perform requests to paths (semicolon separated, `invalid` for an unknown
+/// request id) of the mock server and wait for all of them with a single
+/// deadline in milliseconds, printing the status of every request. Afterwards
+/// for every request a late body chunk is written and its body read with the
+/// same (now reached) deadline, until it is consumed and the request id thereby
+/// invalidated or an error is returned.
+pub fn test_offchain_http_deadlines(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_offchain_http();
+
+    // Parse inputs
+    let paths: Vec<&str> = str(input.get(0)).split(';').collect();
+    let timeout: u64 = str(input.get(1)).parse().expect("Invalid timeout");
+
+    let server = std::env::var("HTTP_MOCK_URL").expect("HTTP_MOCK_URL is not set");
+
+    let now = rtm.call_and_decode::<u64>("rtm_ext_offchain_timestamp_version_1", &[]);
+    let deadline = Some(now + timeout);
+
+    // Start and finish all requests, whose responses are received in the background
+    let ids: Vec<u16> = paths
+        .iter()
+        .map(|path| {
+            if *path == "invalid" {
+                return u16::MAX;
+            }
+
+            let uri = format!("{}{}", server, path);
+            let id = call_and_decode_result::<Result<u16, ()>>(
+                &mut rtm,
+                "rtm_ext_offchain_http_request_start_version_1",
+                &(&b"GET"[..], uri.as_bytes(), &b""[..]).encode(),
+            )
+            .expect("Failed to start request");
+
+            call_and_decode_result::<Result<(), HttpError>>(
+                &mut rtm,
+                "rtm_ext_offchain_http_request_write_body_version_1",
+                &(id as u32, &b""[..], deadline).encode(),
+            )
+            .expect("Failed to finish body");
+            id
+        })
+        .collect();
+
+    let statuses = call_and_decode_result::<Vec<HttpRequestStatus>>(
+        &mut rtm,
+        "rtm_ext_offchain_http_response_wait_version_1",
+        &(ids.clone(), deadline).encode(),
+    );
+    assert_eq!(statuses.len(), ids.len());
+
+    for (path, status) in paths.iter().zip(statuses) {
+        let status = match status {
+            HttpRequestStatus::Finished(code) => format!("finished {}", code),
+            HttpRequestStatus::DeadlineReached => String::from("deadline reached"),
+            HttpRequestStatus::IoError => String::from("io error"),
+            HttpRequestStatus::Invalid => String::from("invalid"),
+        };
+        println!("wait {}: {}", path, status);
+    }
+
+    for (path, id) in paths.iter().zip(ids) {
+        // Bodies of requests can no longer be written once finished
+        let write = call_and_decode_result::<Result<(), HttpError>>(
+            &mut rtm,
+            "rtm_ext_offchain_http_request_write_body_version_1",
+            &(id as u32, &b"late"[..], deadline).encode(),
+        );
+        let write = write.err().map_or("ok", |e| http_error(&e));
+
+        // Read body until consumed, after which the request id is invalid
+        let mut body = Vec::new();
+        let read = loop {
+            match read_body(&mut rtm, id, deadline) {
+                Ok(chunk) if chunk.is_empty() => break Ok(String::from_utf8_lossy(&body).into_owned()),
+                Ok(chunk) => body.extend_from_slice(&chunk),
+                Err(e) => break Err(http_error(&e)),
+            }
+        };
+
+        match read {
+            Ok(body) => {
+                let again = read_body(&mut rtm, id, deadline).err().map_or("ok", |e| http_error(&e));
+                println!("{}: write {}, body {}, then {}", path, write, body, again);
+            }
+            Err(e) => println!("{}: write {}, body {}", path, write, e),
+        }
+    }
+}
@@ -0,0 +1,195 @@
+use crate::host_api::utils::{str, ParsedInput, Runtime, Trap};
+
+use runtime::{Return, ValueType};
+
+use ValueType::{I32, I64};
+
+/// Wasm encoding of value type
+fn type_code(value_type: &ValueType) -> u8 {
+    match value_type {
+        I32 => 0x7f,
+        I64 => 0x7e,
+    }
+}
+
+/// Address in linear memory the shim stores the call result at
+const RESULT_ADDRESS: u32 = 0;
+
+/// Start of the heap used by the host allocator (above the result)
+const HEAP_BASE: u32 = 1024;
+
+/// Append unsigned LEB128 encoded value
+fn leb128(mut value: u64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Append signed LEB128 encoded value
+fn sleb128(mut value: i64, out: &mut Vec<u8>) {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if (value == 0 && byte & 0x40 == 0) || (value == -1 && byte & 0x40 != 0) {
+            out.push(byte);
+            return;
+        }
+        out.push(byte | 0x80);
+    }
+}
+
+/// Append length prefixed name
+fn name(name: &str, out: &mut Vec<u8>) {
+    leb128(name.len() as u64, out);
+    out.extend_from_slice(name.as_bytes());
+}
+
+/// Append section with given id and content
+fn section(id: u8, content: Vec<u8>, out: &mut Vec<u8>) {
+    out.push(id);
+    leb128(content.len() as u64, out);
+    out.extend(content);
+}
+
+/// Generate minimal wasm module importing only the given host function and
+/// exporting a `call` function forwarding raw arguments to it.
+///
+/// The arguments are read from the input data passed to `call`, with 8 bytes
+/// (little endian) per argument. The result, if any, is stored as 8 byte
+/// little endian value and returned as call output.
+fn shim(function: &str, params: &[ValueType], result: Option<ValueType>) -> Vec<u8> {
+    let mut module = b"\0asm".to_vec();
+    module.extend_from_slice(&[1, 0, 0, 0]);
+
+    // Types: host function and (data_ptr: i32, data_len: i32) -> i64
+    let mut types = vec![2, 0x60];
+    leb128(params.len() as u64, &mut types);
+    types.extend(params.iter().map(type_code));
+    match result {
+        Some(t) => types.extend_from_slice(&[1, type_code(&t)]),
+        None => types.push(0),
+    }
+    types.extend_from_slice(&[0x60, 2, 0x7f, 0x7f, 1, 0x7e]);
+    section(1, types, &mut module);
+
+    // Imports: linear memory and the host function
+    let mut imports = vec![2];
+    name("env", &mut imports);
+    name("memory", &mut imports);
+    imports.extend_from_slice(&[0x02, 0x00, 1]);
+    name("env", &mut imports);
+    name(function, &mut imports);
+    imports.extend_from_slice(&[0x00, 0]);
+    section(2, imports, &mut module);
+
+    // Functions: call with type index 1
+    section(3, vec![1, 1], &mut module);
+
+    // Globals: immutable heap base
+    let mut globals = vec![1, 0x7f, 0x00, 0x41];
+    sleb128(HEAP_BASE as i64, &mut globals);
+    globals.push(0x0b);
+    section(6, globals, &mut module);
+
+    // Exports: call function and heap base global
+    let mut exports = vec![2];
+    name("call", &mut exports);
+    exports.extend_from_slice(&[0x00, 1]);
+    name("__heap_base", &mut exports);
+    exports.extend_from_slice(&[0x03, 0]);
+    section(7, exports, &mut module);
+
+    // Code: load each argument from input data, call and store result
+    let mut code = vec![0];
+    if result.is_some() {
+        code.push(0x41);
+        sleb128(RESULT_ADDRESS as i64, &mut code);
+    }
+    for (i, param) in params.iter().enumerate() {
+        code.extend_from_slice(&[0x20, 0]); // local.get 0
+        code.push(match param {
+            I32 => 0x28, // i32.load
+            I64 => 0x29, // i64.load
+        });
+        code.push(0); // alignment
+        leb128(8 * i as u64, &mut code); // offset
+    }
+    code.extend_from_slice(&[0x10, 0]); // call 0
+    let len = match result {
+        Some(t) => {
+            if let I32 = t {
+                code.push(0xad); // i64.extend_i32_u
+            }
+            code.extend_from_slice(&[0x37, 0, 0]); // i64.store
+            8
+        }
+        None => 0,
+    };
+    code.push(0x42); // i64.const
+    sleb128(((len as i64) << 32) | RESULT_ADDRESS as i64, &mut code);
+    code.push(0x0b); // end
+
+    let mut body = Vec::new();
+    leb128(code.len() as u64, &mut body);
+    body.extend(code);
+
+    let mut codes = vec![1];
+    codes.extend(body);
+    section(10, codes, &mut module);
+
+    module
+}
+
+/// Parse raw argument, pointer-sizes can be provided as `ptr:len`
+fn parse_argument(arg: &str) -> u64 {
+    let mut parts = arg.splitn(2, ':');
+    let first = parts.next().unwrap();
+
+    match parts.next() {
+        Some(len) => {
+            let ptr: u32 = first.parse().expect("failed to parse pointer");
+            let len: u32 = len.parse().expect("failed to parse length");
+            (len as u64) << 32 | ptr as u64
+        }
+        None => first.parse().expect("failed to parse raw argument"),
+    }
+}
+
+/// Call host function directly through generated shim with raw arguments,
+/// printing whether the call trapped (optionally with the class of the trap)
+/// and the returned value (unless it is allocation specific).
+pub fn call_raw(rtm: Runtime, function: &str, input: ParsedInput, classify: bool) {
+    // Signatures of all imported host functions are generated by the runtime
+    let (params, result) = match runtime::host_function(function) {
+        Some(f) => (f.params, f.result),
+        None => panic!("unknown host function signature: {}", function),
+    };
+
+    // Encode arguments as 8 byte values each
+    let mut data = Vec::new();
+    for i in 0..params.len() {
+        let arg = parse_argument(str(input.get(i)));
+        data.extend_from_slice(&arg.to_le_bytes());
+    }
+
+    let mut rtm = rtm.with_blob(shim(function, params, result.value_type()));
+
+    match rtm.try_call("call", &data) {
+        Ok(res) => {
+            println!("ok");
+            if let Return::Value(_) = result {
+                let mut value = [0u8; 8];
+                value.copy_from_slice(&res);
+                println!("result: {}", u64::from_le_bytes(value));
+            }
+        }
+        Err(e) if classify => println!("trap: {}", Trap::classify(&e)),
+        Err(_) => println!("trap"),
+    }
+}
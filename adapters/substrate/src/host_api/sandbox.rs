@@ -0,0 +1,152 @@
+use crate::host_api::utils::{str, ParsedInput, Runtime};
+use parity_scale_codec::Encode;
+
+/// Size of a page of sandbox memory
+const PAGE_SIZE: u32 = 65536;
+
+/// Guest returning a constant:
+/// `(module (func (export "call") (result i32) i32.const 42))`
+const GUEST_CONSTANT: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+    0x03, 0x02, 0x01, 0x00,
+    0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x00,
+    0x0a, 0x06, 0x01, 0x04, 0x00, 0x41, 0x2a, 0x0b,
+];
+
+/// Guest calling back into the runtime:
+/// `(module (import "env" "supervisor" (func)) (func (export "call") call 0))`
+const GUEST_NESTED: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+    0x02, 0x12, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x0a, 0x73, 0x75, 0x70, 0x65,
+          0x72, 0x76, 0x69, 0x73, 0x6f, 0x72, 0x00, 0x00,
+    0x03, 0x02, 0x01, 0x00,
+    0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x01,
+    0x0a, 0x06, 0x01, 0x04, 0x00, 0x10, 0x00, 0x0b,
+];
+
+/// Guest hitting a trap:
+/// `(module (func (export "call") unreachable))`
+const GUEST_TRAP: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x04, 0x01, 0x60, 0x00, 0x00,
+    0x03, 0x02, 0x01, 0x00,
+    0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x00,
+    0x0a, 0x05, 0x01, 0x03, 0x00, 0x00, 0x0b,
+];
+
+/// Guest growing the imported memory by one page:
+/// `(module (import "env" "memory" (memory 1)) (func (export "call") (result i32) i32.const 1 memory.grow))`
+const GUEST_GROW: &[u8] = &[
+    0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+    0x01, 0x05, 0x01, 0x60, 0x00, 0x01, 0x7f,
+    0x02, 0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f,
+          0x72, 0x79, 0x02, 0x00, 0x01,
+    0x03, 0x02, 0x01, 0x00,
+    0x07, 0x08, 0x01, 0x04, 0x63, 0x61, 0x6c, 0x6c, 0x00, 0x00,
+    0x0a, 0x08, 0x01, 0x06, 0x00, 0x41, 0x01, 0x40, 0x00, 0x0b,
+];
+
+/// Instantiate guest code and invoke its `call` export
+fn invoke(rtm: &mut Runtime, code: &[u8], maximum: u32, state: &[u8]) -> (u32, Vec<u8>) {
+    rtm.call_and_decode::<(u32, Vec<u8>)>(
+        "rtm_ext_sandbox_invoke_version_1",
+        &(code, &b"call"[..], maximum, state).encode(),
+    )
+}
+
+/// The Wasm function tests creating, writing, reading and freeing sandbox memory
+pub fn ext_sandbox_memory_new_version_1(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let value = input.get(0);
+
+    // Write and read value at start of memory
+    let (set, get, res) = rtm.call_and_decode::<(u32, u32, Vec<u8>)>(
+        "rtm_ext_sandbox_memory_new_version_1",
+        &(1u32, 1u32, 0u32, value).encode(),
+    );
+    assert_eq!(set, 0);
+    assert_eq!(get, 0);
+    assert_eq!(res, value);
+
+    println!("{}", str(&res));
+}
+
+pub fn ext_sandbox_memory_get_version_1(rtm: Runtime, input: ParsedInput) {
+    ext_sandbox_memory_new_version_1(rtm, input)
+}
+
+pub fn ext_sandbox_memory_set_version_1(rtm: Runtime, input: ParsedInput) {
+    ext_sandbox_memory_new_version_1(rtm, input)
+}
+
+pub fn ext_sandbox_memory_teardown_version_1(rtm: Runtime, input: ParsedInput) {
+    ext_sandbox_memory_new_version_1(rtm, input)
+}
+
+/// Access sandbox memory across its limit and grow memory beyond its maximum
+pub fn test_sandbox_memory_limit(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let value = input.get(0);
+
+    // Write and read value across end of single page memory
+    let offset = PAGE_SIZE - value.len() as u32 / 2;
+    let (set, get, _) = rtm.call_and_decode::<(u32, u32, Vec<u8>)>(
+        "rtm_ext_sandbox_memory_new_version_1",
+        &(1u32, 1u32, offset, value).encode(),
+    );
+    assert_ne!(set, 0);
+    assert_ne!(get, 0);
+
+    // Grow memories with one and two pages maximum
+    let (res_1, grow_1) = invoke(&mut rtm, GUEST_GROW, 1, &[]);
+    let (res_2, grow_2) = invoke(&mut rtm, GUEST_GROW, 2, &[]);
+
+    println!("set: {}", set);
+    println!("get: {}", get);
+    println!("grow 1: {} {}", res_1, hex::encode(grow_1));
+    println!("grow 2: {} {}", res_2, hex::encode(grow_2));
+}
+
+/// The Wasm function tests instantiating, invoking and freeing sandboxed code
+pub fn ext_sandbox_instantiate_version_1(mut rtm: Runtime, _input: ParsedInput) {
+    let (res, value) = invoke(&mut rtm, GUEST_CONSTANT, 1, &[]);
+    assert_eq!(res, 0);
+    println!("constant: {}", hex::encode(value));
+
+    let (res, value) = invoke(&mut rtm, GUEST_TRAP, 1, &[]);
+    assert_ne!(res, 0);
+    println!("trap: {} {}", res, hex::encode(value));
+
+    let (res, value) = invoke(&mut rtm, b"invalid", 1, &[]);
+    assert_ne!(res, 0);
+    println!("invalid: {} {}", res, hex::encode(value));
+}
+
+pub fn ext_sandbox_invoke_version_1(rtm: Runtime, input: ParsedInput) {
+    ext_sandbox_instantiate_version_1(rtm, input)
+}
+
+pub fn ext_sandbox_instance_teardown_version_1(rtm: Runtime, input: ParsedInput) {
+    ext_sandbox_instantiate_version_1(rtm, input)
+}
+
+/// Sandboxed code calling back into the runtime, which writes to storage
+pub fn test_sandbox_nested(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let key = input.get(0);
+    let value = input.get(1);
+
+    // Invoke guest calling the supervisor with key and value as state
+    let (res, _) = invoke(&mut rtm, GUEST_NESTED, 1, &(key, value).encode());
+    assert_eq!(res, 0);
+
+    // Check that value was stored by the supervisor
+    let res = rtm
+        .call_and_decode::<Option<Vec<u8>>>("rtm_ext_storage_get_version_1", &key.encode())
+        .expect("Supervisor did not store value");
+    assert_eq!(res, value);
+
+    println!("{}", str(&res));
+}
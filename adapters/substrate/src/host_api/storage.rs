@@ -1,5 +1,6 @@
-use crate::host_api::utils::{str, ParsedInput, Runtime};
-use parity_scale_codec::{Encode, Decode};
+use crate::host_api::utils::{str, ParsedInput, Runtime, LARGE_DATA_HEAP_PAGES};
+use parity_scale_codec::{Encode, Decode, DecodeAll};
+use sp_io::KillStorageResult;
 
 pub fn test_storage_init(mut rtm: Runtime) {
     // Compute and print storage root on init
@@ -225,6 +226,49 @@ pub fn ext_storage_append_version_1(mut rtm: Runtime, input: ParsedInput) {
     );
 }
 
+/// Append to existing values of a key: an empty value, a SCALE encoded vector
+/// and values without a valid length prefix, which are replaced by a vector
+/// of only the appended item
+pub fn test_storage_append_existing(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let key = input.get(0);
+    let value = input.get(1);
+    let new_value = input.get(2);
+
+    let cases: [(&str, Vec<u8>, &[u8]); 4] = [
+        ("empty", vec![], value),
+        ("existing", vec![value].encode(), new_value),
+        // Two byte mode of compact length, but only one byte
+        ("truncated", vec![0x01], new_value),
+        // Length of zero in two byte mode
+        ("non-canonical", vec![0x01, 0x00], new_value),
+    ];
+    for (name, existing, item) in cases.iter() {
+        let _ = rtm.call(
+            "rtm_ext_storage_set_version_1",
+            &(key, existing).encode(),
+        );
+        let _ = rtm.call(
+            "rtm_ext_storage_append_version_1",
+            &(key, item.encode()).encode(),
+        );
+
+        let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+            "rtm_ext_storage_get_version_1", &key.encode()
+        ).unwrap();
+
+        let items = match Vec::<Vec<u8>>::decode_all(&res) {
+            Ok(items) => items
+                .iter()
+                .map(|v| String::from_utf8(v.to_vec()).unwrap())
+                .collect::<Vec::<String>>()
+                .join(";"),
+            Err(_) => format!("invalid ({})", hex::encode(res)),
+        };
+        println!("{}: {}", name, items);
+    }
+}
+
 pub fn ext_storage_root_version_1(mut rtm: Runtime, input: ParsedInput) {
     // Parse inputs
     let key1 = input.get(0);
@@ -294,3 +338,360 @@ pub fn ext_storage_next_key_version_1(mut rtm: Runtime, input: ParsedInput) {
         assert!(res.is_none());
     }
 }
+
+pub fn test_storage_clear_prefix_limit_root(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let prefix = input.get(0);
+    let key1 = input.get(1);
+    let value1 = input.get(2);
+    let key2 = input.get(3);
+    let value2 = input.get(4);
+
+    // Make sure at least two keys are affected by the prefix
+    let key3 = [prefix, key1].concat();
+    let key4 = [prefix, key2].concat();
+
+    let data: Vec<(&[u8], &[u8])> = vec![
+        (key1, value1),
+        (key2, value2),
+        (&key3[..], value1),
+        (&key4[..], value2),
+    ];
+
+    let count = data.iter().filter(|(k, _)| k.starts_with(prefix)).count() as u32;
+
+    // Set key/value and commit them to the backend, as the scenario is the
+    // removal of stored keys over several blocks: the limit only applies to
+    // keys of the backend, while the overlay is cleared regardless of it
+    for (key, value) in &data {
+        let _ = rtm.call("rtm_ext_storage_set_version_1", &(key, value).encode());
+    }
+    rtm.commit();
+
+    // Clear prefix one key at a time and print root after every step
+    let mut removed = 0;
+    loop {
+        let res = rtm.call_and_decode::<Vec<u8>>(
+            "rtm_ext_storage_clear_prefix_version_2", &(prefix, Some(1u32)).encode()
+        );
+        let res = KillStorageResult::decode(&mut res.as_slice()).unwrap();
+
+        let root = rtm.call_and_decode::<Vec<u8>>("rtm_ext_storage_root_version_1", &[]);
+        println!("{}", hex::encode(root));
+
+        // Commit the removal like the end of a block, so the next call
+        // continues with the remaining keys of the backend
+        rtm.commit();
+
+        match res {
+            KillStorageResult::SomeRemaining(n) => {
+                assert_eq!(n, 1);
+                removed += n;
+                assert!(removed < count);
+            }
+            KillStorageResult::AllRemoved(n) => {
+                assert!(n <= 1);
+                removed += n;
+                break;
+            }
+        }
+    }
+    assert_eq!(removed, count);
+
+    // Check that only keys outside the prefix remain
+    for (key, value) in &data {
+        let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+            "rtm_ext_storage_get_version_1", &key.encode()
+        );
+        if key.starts_with(prefix) {
+            assert!(res.is_none());
+        } else {
+            assert_eq!(res.unwrap(), *value);
+        }
+    }
+}
+
+/// Nest transactions to the given depth and set a value in the innermost one,
+/// which must only persist if all of them are committed
+pub fn test_storage_transaction_depth(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let depth = input.get_u32(0);
+
+    let key = b"transaction";
+    let value = format!("nested {}", depth);
+
+    for commit in &[false, true] {
+        let _ = rtm.call(
+            "rtm_ext_storage_nested_transactions_version_1",
+            &(depth, &key[..], value.as_bytes(), commit).encode(),
+        );
+
+        let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+            "rtm_ext_storage_get_version_1",
+            &key.encode(),
+        );
+
+        let outcome = if *commit { "committed" } else { "rolled back" };
+        match res {
+            Some(value) => println!("{}: {}", outcome, str(&value)),
+            None => println!("{}: none", outcome),
+        }
+    }
+}
+
+/// Print storage root and changes root, of which there is none as no changes
+/// trie is configured
+pub fn test_storage_root_changes_root(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let key1 = input.get(0);
+    let value1 = input.get(1);
+    let key2 = input.get(2);
+    let value2 = input.get(3);
+
+    let _ = rtm.call("rtm_ext_storage_set_version_1", &(key1, value1).encode());
+    let _ = rtm.call("rtm_ext_storage_set_version_1", &(key2, value2).encode());
+
+    let root = rtm.call_and_decode::<Vec<u8>>("rtm_ext_storage_root_version_1", &[]);
+    println!("root: {}", hex::encode(&root));
+
+    // Parent hash has to be a valid block hash
+    let parent_hash = [0u8; 32].to_vec();
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_changes_root_version_1", &parent_hash.encode()
+    );
+    println!("changes root: {}", res.map_or(String::from("none"), hex::encode));
+}
+
+/// Clear prefix of the committed initial state with a sequence of limits
+/// (semicolon separated, `none` for no limit), printing the result and the
+/// keys left in storage after every call
+pub fn test_storage_clear_prefix_limit_count(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let prefix = input.get(0);
+    let limits = str(input.get(1));
+
+    // The limit only applies to the backend
+    rtm.commit();
+
+    for limit in limits.split(';') {
+        let limit = match limit {
+            "none" => None,
+            limit => Some(limit.parse::<u32>().expect("Invalid limit")),
+        };
+
+        let res = rtm.call_and_decode::<Vec<u8>>(
+            "rtm_ext_storage_clear_prefix_version_2", &(prefix, limit).encode()
+        );
+        let res = match KillStorageResult::decode(&mut res.as_slice()).unwrap() {
+            KillStorageResult::AllRemoved(n) => format!("removed {}, all removed", n),
+            KillStorageResult::SomeRemaining(n) => format!("removed {}, some remaining", n),
+        };
+        // Continue deletion on top of the updated backend
+        rtm.commit();
+
+        let mut left = vec![];
+        let mut key = vec![];
+        while let Some(next) = rtm.call_and_decode::<Option<Vec<u8>>>(
+            "rtm_ext_storage_next_key_version_1", &key.encode()
+        ) {
+            left.push(hex::encode(&next));
+            key = next;
+        }
+
+        let limit = limit.map_or(String::from("none"), |l| l.to_string());
+        println!("limit {}: {}, left: {}", limit, res, left.join(" "));
+    }
+}
+
+/// Check if key of the initial state exists and read it at offsets into
+/// buffers (semicolon separated `offset:size`), printing the remaining length
+/// reported for every read and the whole buffer, untouched bytes being 0xff
+pub fn test_storage_exists_read_partial(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let key = input.get(0);
+    let reads = str(input.get(1));
+
+    let res = rtm.call_and_decode::<u32>(
+        "rtm_ext_storage_exists_version_1", &key.encode()
+    );
+    println!("exists: {}", res);
+
+    for read in reads.split(';') {
+        let mut parts = read.splitn(2, ':');
+        let offset = parts.next().unwrap().parse::<u32>().expect("Invalid offset");
+        let size = parts.next().expect("buffer size is missing").parse::<u32>().expect("Invalid buffer size");
+
+        let (remaining, buffer) = rtm.call_and_decode::<(Option<u32>, Vec<u8>)>(
+            "rtm_ext_storage_read_partial_version_1", &(key, offset, size).encode()
+        );
+        assert_eq!(buffer.len(), size as usize);
+
+        let remaining = remaining.map_or(String::from("none"), |n| n.to_string());
+        let buffer = if buffer.is_empty() { String::from("empty") } else { hex::encode(&buffer) };
+        println!("read {} at {}: remaining {}, buffer {}", size, offset, remaining, buffer);
+    }
+}
+
+/// Iterate over all keys of the initial state with next key, first of the top
+/// storage and then of the child storage, printing every key in order
+pub fn test_storage_next_key_order(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let child_key = input.get(0);
+
+    // Every key of the top storage is printed, including roots of child storages
+    let mut key = vec![];
+    while let Some(next) = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_next_key_version_1", &key.encode()
+    ) {
+        assert!(next > key);
+        println!("top: {}", hex::encode(&next));
+        key = next;
+    }
+
+    let mut key = vec![];
+    while let Some(next) = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_default_child_storage_next_key_version_1",
+        &(child_key, &key).encode(),
+    ) {
+        assert!(next > key);
+        println!("child: {}", hex::encode(&next));
+        key = next;
+    }
+}
+
+/// Read key written within the same call, in a prior call and only present
+/// in the backend, as well as after clearing it in a prior call
+pub fn test_storage_overlay_reads(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let key = input.get(0);
+    let value = input.get(1);
+    let new_value = input.get(2);
+
+    let print = |v: &Option<Vec<u8>>| v.as_ref().map_or(String::from("none"), |v| str(v).to_string());
+
+    // Key unset, then set in a prior call and finally committed to the backend
+    for state in ["unset", "prior call", "backend"].iter() {
+        if *state == "backend" {
+            rtm.commit();
+        }
+        let reads = rtm.call_and_decode::<Vec<Option<Vec<u8>>>>(
+            "rtm_ext_storage_overlay_reads_version_1",
+            &(key, value, new_value).encode(),
+        );
+        let reads: Vec<String> = reads.iter().map(print).collect();
+        println!("{}: {}", state, reads.join(";"));
+    }
+
+    // Value set last within the call is visible in the next one
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_get_version_1", &key.encode()
+    );
+    println!("next call: {}", print(&res));
+
+    // Clear value present in the backend
+    rtm.commit();
+    let _ = rtm.call("rtm_ext_storage_clear_version_1", &key.encode());
+
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_get_version_1", &key.encode()
+    );
+    println!("cleared in prior call: {}", print(&res));
+
+    rtm.commit();
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>(
+        "rtm_ext_storage_get_version_1", &key.encode()
+    );
+    println!("committed: {}", print(&res));
+}
+
+/// Overwrite and clear an existing value in transactions, rolling back has to
+/// restore the prior value while committing keeps the new one
+pub fn test_storage_transaction_restore(mut rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let key = input.get(0);
+    let value = input.get(1);
+    let new_value = input.get(2);
+
+    let _ = rtm.call(
+        "rtm_ext_storage_set_version_1",
+        &(key, value).encode(),
+    );
+
+    let steps = [
+        ("overwritten", Some(new_value), false),
+        ("cleared", None, false),
+        ("overwritten", Some(new_value), true),
+    ];
+    for (action, value, commit) in steps.iter() {
+        let inner = rtm.call_and_decode::<Option<Vec<u8>>>(
+            "rtm_ext_storage_transaction_overwrite_version_1",
+            &(key, value, commit).encode(),
+        );
+        let outer = rtm.call_and_decode::<Option<Vec<u8>>>(
+            "rtm_ext_storage_get_version_1",
+            &key.encode(),
+        );
+
+        let print = |v: Option<Vec<u8>>| v.map_or(String::from("none"), |v| str(&v).to_string());
+        let outcome = if *commit { "committed" } else { "rolled back" };
+        println!("{}: {}, {}: {}", action, print(inner), outcome, print(outer));
+    }
+}
+
+/// Close one transaction more than were started, which has to trap
+fn test_storage_transaction_unbalanced(mut rtm: Runtime, input: ParsedInput, commit: bool) {
+    // Parse inputs
+    let depth = input.get_u32(0);
+
+    let res = rtm.try_call(
+        "rtm_ext_storage_unbalanced_transactions_version_1",
+        &(depth, commit).encode(),
+    );
+
+    println!("{}", if res.is_ok() { "ok" } else { "trap" });
+}
+
+pub fn test_storage_transaction_commit_unbalanced(rtm: Runtime, input: ParsedInput) {
+    test_storage_transaction_unbalanced(rtm, input, true)
+}
+
+pub fn test_storage_transaction_rollback_unbalanced(rtm: Runtime, input: ParsedInput) {
+    test_storage_transaction_unbalanced(rtm, input, false)
+}
+
+/// Store value under a long key (count of `a`), then print whether storing
+/// trapped, the value read back, the length of the key as iterated and the
+/// storage root.
+pub fn test_storage_long_key(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_heap_pages(LARGE_DATA_HEAP_PAGES);
+
+    // Parse inputs
+    let key = vec![b'a'; input.get_u32(0) as usize];
+    let value = input.get(1);
+
+    // Set value, a host may reject long keys
+    if rtm.try_call("rtm_ext_storage_set_version_1", &(&key, value).encode()).is_err() {
+        println!("set: trap");
+        return;
+    }
+    println!("set: ok");
+
+    // Read value back under the full key
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>("rtm_ext_storage_get_version_1", &key.encode());
+    match res {
+        Some(res) if res == value => println!("get: {}", str(&res)),
+        Some(_) => println!("get: altered"),
+        None => println!("get: none"),
+    }
+
+    // Iterate to the stored key, which is shorter if truncated: "`" directly
+    // precedes "a" and follows the well known keys (e.g. ":code")
+    let res = rtm.call_and_decode::<Option<Vec<u8>>>("rtm_ext_storage_next_key_version_1", &b"`".to_vec().encode());
+    match res {
+        Some(stored) => println!("stored key: {} bytes", stored.len()),
+        None => println!("stored key: none"),
+    }
+
+    let res = rtm.call_and_decode::<Vec<u8>>("rtm_ext_storage_root_version_1", &[]);
+    println!("root: {}", hex::encode(res));
+}
@@ -1,5 +1,7 @@
-use crate::host_api::utils::{ParsedInput, Runtime};
-use parity_scale_codec::Encode;
+use crate::host_api::utils::{ParsedInput, Runtime, LARGE_DATA_HEAP_PAGES};
+use parity_scale_codec::{Decode, Encode};
+use sp_core::{Blake2Hasher, Hasher, KeccakHasher};
+use sp_trie::{generate_trie_proof, Layout, MemoryDB, TrieDBMut, TrieMut};
 
 pub fn ext_trie_blake2_256_root_version_1(mut rtm: Runtime, input: ParsedInput) {
     // Parse input
@@ -35,3 +37,154 @@ pub fn ext_trie_blake2_256_ordered_root_version_1(mut rtm: Runtime, input: Parse
 
     println!("{}", hex::encode(res));
 }
+
+/// Generate key value pairs: big endian index as key, "value <index>" as value
+fn generate_pairs(count: u32) -> Vec<(Vec<u8>, Vec<u8>)> {
+    (0..count)
+        .map(|i| (i.to_be_bytes().to_vec(), format!("value {}", i).into_bytes()))
+        .collect()
+}
+
+/// Compute root of generated trie (e.g. empty, single leaf or over 256 items)
+pub fn test_trie_blake2_256_root_generated(mut rtm: Runtime, input: ParsedInput) {
+    // Parse input
+    let count = input.get_u32(0);
+
+    let trie = generate_pairs(count);
+
+    let res = rtm.call_and_decode::<Vec<u8>>(
+        "rtm_ext_trie_blake2_256_root_version_1", &(trie).encode()
+    );
+
+    println!("{}", hex::encode(res));
+}
+
+/// Compute ordered root of generated values
+pub fn test_trie_blake2_256_ordered_root_generated(mut rtm: Runtime, input: ParsedInput) {
+    // Parse input
+    let count = input.get_u32(0);
+
+    let trie: Vec<Vec<u8>> = generate_pairs(count).into_iter().map(|(_, v)| v).collect();
+
+    let res = rtm.call_and_decode::<Vec<u8>>(
+        "rtm_ext_trie_blake2_256_ordered_root_version_1", &(trie).encode()
+    );
+
+    println!("{}", hex::encode(res));
+}
+
+/// Build trie of key value pairs and generate compact proof of single key,
+/// none if the nodes of the trie can not be decoded again
+fn generate_proof<H: Hasher>(pairs: &[(&[u8], &[u8])], key: &[u8]) -> Option<([u8; 32], Vec<Vec<u8>>)> {
+    let mut db = MemoryDB::<H>::default();
+    let mut root = Default::default();
+    {
+        let mut trie = TrieDBMut::<Layout<H>>::new(&mut db, &mut root);
+        for (k, v) in pairs {
+            trie.insert(k, v).expect("Failed to insert into trie");
+        }
+    }
+
+    let proof = generate_trie_proof::<Layout<H>, _, _, _>(&db, root, &[key]).ok()?;
+
+    let mut res = [0u8; 32];
+    res.copy_from_slice(root.as_ref());
+    Some((res, proof))
+}
+
+fn verify_proof(rtm: &mut Runtime, hasher: &str, root: [u8; 32], proof: &[Vec<u8>], key: &[u8], value: &[u8]) -> u32 {
+    rtm.call_and_decode::<u32>(
+        &format!("rtm_ext_trie_{}_verify_proof_version_1", hasher),
+        &(root, proof, key, value).encode(),
+    )
+}
+
+/// Verify valid proof of first key against altered proofs, values and roots
+fn test_verify_proof<H: Hasher>(mut rtm: Runtime, hasher: &str, input: ParsedInput) {
+    // Parse input
+    let key1 = input.get(0);
+    let value1 = input.get(1);
+    let key2 = input.get(2);
+    let value2 = input.get(3);
+
+    let (root, proof) = generate_proof::<H>(&[(key1, value1), (key2, value2)], key1)
+        .expect("Failed to generate proof");
+
+    // Valid proof of first key value pair
+    let valid = verify_proof(&mut rtm, hasher, root, &proof, key1, value1);
+    assert_eq!(valid, 1);
+
+    // Proof of wrong value
+    let wrong_value = verify_proof(&mut rtm, hasher, root, &proof, key1, value2);
+    assert_eq!(wrong_value, 0);
+
+    // Proof against wrong root
+    let mut wrong_root = root;
+    wrong_root[0] ^= 0xff;
+    let wrong_root = verify_proof(&mut rtm, hasher, wrong_root, &proof, key1, value1);
+    assert_eq!(wrong_root, 0);
+
+    // Proof missing the root node
+    let missing_node = verify_proof(&mut rtm, hasher, root, &proof[1..], key1, value1);
+    assert_eq!(missing_node, 0);
+
+    // Proof containing node of unrelated trie
+    let (_, extra) = generate_proof::<H>(&[(value1, key1)], value1).expect("Failed to generate proof");
+    let extra_node = verify_proof(&mut rtm, hasher, root, &[&proof[..], &extra[..]].concat(), key1, value1);
+    assert_eq!(extra_node, 0);
+
+    println!("valid: {}", valid);
+    println!("wrong value: {}", wrong_value);
+    println!("wrong root: {}", wrong_root);
+    println!("missing node: {}", missing_node);
+    println!("extra node: {}", extra_node);
+}
+
+pub fn ext_trie_blake2_256_verify_proof_version_1(rtm: Runtime, input: ParsedInput) {
+    test_verify_proof::<Blake2Hasher>(rtm, "blake2_256", input)
+}
+
+pub fn ext_trie_keccak_256_verify_proof_version_1(rtm: Runtime, input: ParsedInput) {
+    test_verify_proof::<KeccakHasher>(rtm, "keccak_256", input)
+}
+
+/// Compute root of the trie of a single pair with a long key (count of `a`)
+/// and verify a proof of it, printing the root and the result of the
+/// verification. Substrate can not generate proofs of keys longer than the
+/// partial key length bound of node headers, as it fails to decode such nodes.
+pub fn test_trie_long_key(rtm: Runtime, input: ParsedInput) {
+    let mut rtm = rtm.with_heap_pages(LARGE_DATA_HEAP_PAGES);
+
+    // Parse input
+    let key = vec![b'a'; input.get_u32(0) as usize];
+    let value = input.get(1);
+
+    // Root of the trie, a host may reject long keys
+    let trie = vec![(&key[..], value)];
+    match rtm.try_call("rtm_ext_trie_blake2_256_root_version_1", &trie.encode()) {
+        Ok(res) => {
+            let res = Vec::<u8>::decode(&mut res.as_slice()).expect("Failed to decode result");
+            println!("root: {}", hex::encode(res));
+        }
+        Err(_) => {
+            println!("root: trap");
+            return;
+        }
+    }
+
+    // Proof of the pair generated by the reference trie
+    let (root, proof) = match generate_proof::<Blake2Hasher>(&[(&key[..], value)], &key) {
+        Some(res) => res,
+        None => {
+            println!("proof: not generated");
+            return;
+        }
+    };
+    match rtm.try_call(
+        "rtm_ext_trie_blake2_256_verify_proof_version_1",
+        &(root, &proof, &key, value).encode(),
+    ) {
+        Ok(res) => println!("proof: {}", u32::decode(&mut res.as_slice()).expect("Failed to decode result")),
+        Err(_) => println!("proof: trap"),
+    }
+}
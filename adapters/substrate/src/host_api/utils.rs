@@ -1,30 +1,61 @@
 use clap::Values;
-use std::sync::Arc;
+use std::fmt;
+use std::fs::File;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::sync::{Arc, Mutex, Once};
 use parity_scale_codec::Decode;
 use sc_executor::{
     WasmExecutor,
     CallInWasm,
     WasmExecutionMethod,
+    error::Error,
     sp_wasm_interface::HostFunctions,
 };
 use sp_io::SubstrateHostFunctions;
 use sp_core::{
     offchain::testing::TestOffchainExt,
-    offchain::OffchainWorkerExt,
-    traits::MissingHostFunctions,
+    offchain::{
+        Capabilities, Externalities, HttpError, HttpRequestId, HttpRequestStatus, LimitedExternalities,
+        OffchainDbExt, OffchainWorkerExt, OpaqueNetworkState, OpaquePeerId, Timestamp,
+    },
+    storage::{well_known_keys::DEFAULT_CHILD_STORAGE_KEY_PREFIX, ChildInfo, Storage},
+    testing::TaskExecutor,
+    traits::{CallInWasmExt, MissingHostFunctions, TaskExecutorExt},
+    hashing::blake2_256,
     Blake2Hasher,
 };
 use sp_keystore::{KeystoreExt, testing::KeyStore};
-use sp_state_machine::TestExternalities as CoreTestExternalities;
+use sp_state_machine::{Backend, TestExternalities as CoreTestExternalities};
+use sp_trie::{Layout, TrieConfiguration};
 
 use runtime::WASM_BINARY;
 
+use crate::host_api::{disasm, http::HttpOffchainExt, native};
+
 type TestExternalities<H> = CoreTestExternalities<H, u64>;
 
+/// Heap pages of the runtime for calls with large data (e.g. long prints or
+/// keys), enough for copies of 1 MiB
+pub const LARGE_DATA_HEAP_PAGES: u64 = 128;
+
 pub fn str<'a>(input: &'a [u8]) -> &'a str {
     std::str::from_utf8(input).unwrap()
 }
 
+/// Decode initial state entry of hex encoded, colon separated parts (e.g. `KEY:VALUE`)
+pub fn parse_state_entry(entry: &str, parts: usize) -> Vec<Vec<u8>> {
+    let res: Vec<Vec<u8>> = entry
+        .split(':')
+        .map(|p| hex::decode(p.trim_start_matches("0x")).expect("Invalid hex in state entry"))
+        .collect();
+    if res.len() != parts {
+        panic!("State entry '{}' does not have {} parts", entry, parts);
+    }
+    res
+}
+
 pub struct ParsedInput<'a>(Vec<&'a str>);
 
 impl<'a> ParsedInput<'a> {
@@ -50,6 +81,12 @@ impl<'a> From<Values<'a>> for ParsedInput<'a> {
     }
 }
 
+impl<'a> From<Vec<&'a str>> for ParsedInput<'a> {
+    fn from(input: Vec<&'a str>) -> Self {
+        ParsedInput(input)
+    }
+}
+
 impl<'a> From<Option<Values<'a>>> for ParsedInput<'a> {
     fn from(input: Option<Values<'a>>) -> Self {
         match input {
@@ -59,10 +96,185 @@ impl<'a> From<Option<Values<'a>>> for ParsedInput<'a> {
     }
 }
 
+/// Offchain externalities of a node which is not a validator, all functions
+/// are provided by `TestOffchainExt`, which always reports to be one
+struct NonValidatorExt(TestOffchainExt);
+
+impl Externalities for NonValidatorExt {
+    fn is_validator(&self) -> bool {
+        false
+    }
+    fn network_state(&self) -> Result<OpaqueNetworkState, ()> {
+        self.0.network_state()
+    }
+    fn timestamp(&mut self) -> Timestamp {
+        self.0.timestamp()
+    }
+    fn sleep_until(&mut self, deadline: Timestamp) {
+        self.0.sleep_until(deadline)
+    }
+    fn random_seed(&mut self) -> [u8; 32] {
+        self.0.random_seed()
+    }
+    fn http_request_start(&mut self, method: &str, uri: &str, meta: &[u8]) -> Result<HttpRequestId, ()> {
+        self.0.http_request_start(method, uri, meta)
+    }
+    fn http_request_add_header(&mut self, request_id: HttpRequestId, name: &str, value: &str) -> Result<(), ()> {
+        self.0.http_request_add_header(request_id, name, value)
+    }
+    fn http_request_write_body(
+        &mut self,
+        request_id: HttpRequestId,
+        chunk: &[u8],
+        deadline: Option<Timestamp>,
+    ) -> Result<(), HttpError> {
+        self.0.http_request_write_body(request_id, chunk, deadline)
+    }
+    fn http_response_wait(&mut self, ids: &[HttpRequestId], deadline: Option<Timestamp>) -> Vec<HttpRequestStatus> {
+        self.0.http_response_wait(ids, deadline)
+    }
+    fn http_response_headers(&mut self, request_id: HttpRequestId) -> Vec<(Vec<u8>, Vec<u8>)> {
+        self.0.http_response_headers(request_id)
+    }
+    fn http_response_read_body(
+        &mut self,
+        request_id: HttpRequestId,
+        buffer: &mut [u8],
+        deadline: Option<Timestamp>,
+    ) -> Result<usize, HttpError> {
+        self.0.http_response_read_body(request_id, buffer, deadline)
+    }
+    fn set_authorized_nodes(&mut self, nodes: Vec<OpaquePeerId>, authorized_only: bool) {
+        self.0.set_authorized_nodes(nodes, authorized_only)
+    }
+}
+
+/// Sink collecting the output of the print host functions, which substrate
+/// forwards to the `runtime` log target.
+#[derive(Clone, Default)]
+pub struct PrintSink(Arc<Mutex<Vec<String>>>);
+
+impl PrintSink {
+    pub fn take(&self) -> Vec<String> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl log::Log for PrintSink {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.target() == "runtime"
+    }
+    fn log(&self, record: &log::Record) {
+        if self.enabled(record.metadata()) {
+            self.0.lock().unwrap().push(record.args().to_string());
+        }
+    }
+    fn flush(&self) {}
+}
+
+/// Sink collecting the records of the logging host functions, i.e. all records
+/// logged by sp-io itself instead of by the executor.
+#[derive(Clone, Default)]
+pub struct LogSink(Arc<Mutex<Vec<(log::Level, String, String)>>>);
+
+impl LogSink {
+    pub fn take(&self) -> Vec<(log::Level, String, String)> {
+        std::mem::take(&mut *self.0.lock().unwrap())
+    }
+}
+
+impl log::Log for LogSink {
+    fn enabled(&self, _metadata: &log::Metadata) -> bool {
+        true
+    }
+    fn log(&self, record: &log::Record) {
+        if record.module_path().map_or(false, |m| m.starts_with("sp_io")) {
+            let entry = (record.level(), record.target().to_string(), record.args().to_string());
+            self.0.lock().unwrap().push(entry);
+        }
+    }
+    fn flush(&self) {}
+}
+
+/// Logger forwarding all records to the sink of the current runtime. The log
+/// crate only allows to install a logger once per process, so repeated test
+/// runs swap the sink behind it instead.
+struct ForwardLogger;
+
+static CURRENT_SINK: AtomicPtr<Box<dyn log::Log>> = AtomicPtr::new(std::ptr::null_mut());
+static INSTALL_LOGGER: Once = Once::new();
+
+fn current_sink() -> Option<&'static dyn log::Log> {
+    // Replaced sinks are leaked, so a loaded sink stays valid
+    unsafe { CURRENT_SINK.load(Ordering::Acquire).as_ref().map(|sink| &**sink) }
+}
+
+fn install_sink(sink: Box<dyn log::Log>, level: log::LevelFilter) {
+    INSTALL_LOGGER.call_once(|| {
+        log::set_logger(&ForwardLogger).expect("Failed to install logger");
+    });
+    CURRENT_SINK.store(Box::into_raw(Box::new(sink)), Ordering::Release);
+    log::set_max_level(level);
+}
+
+impl log::Log for ForwardLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        current_sink().map_or(false, |sink| sink.enabled(metadata))
+    }
+    fn log(&self, record: &log::Record) {
+        if let Some(sink) = current_sink() {
+            sink.log(record);
+        }
+    }
+    fn flush(&self) {}
+}
+
+/// Class of trap of a failed call into the wasm blob
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trap {
+    /// Access outside of the linear memory, by the wasm code or a host
+    /// function reading or writing its arguments
+    OutOfBounds,
+    /// Unreachable instruction, e.g. a panic of the runtime
+    Unreachable,
+    /// Host function rejecting its arguments (e.g. an invalid encoding)
+    Host,
+}
+
+impl Trap {
+    /// Classify error of a failed call by its message, as executors only
+    /// report traps and host function errors as text
+    pub fn classify(error: &Error) -> Self {
+        let message = error.to_string().to_lowercase();
+        if ["out of bounds", "access region", "memory access"].iter().any(|m| message.contains(m)) {
+            Trap::OutOfBounds
+        } else if message.contains("unreachable") {
+            Trap::Unreachable
+        } else {
+            Trap::Host
+        }
+    }
+}
+
+impl fmt::Display for Trap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Trap::OutOfBounds => write!(f, "out of bounds"),
+            Trap::Unreachable => write!(f, "unreachable"),
+            Trap::Host => write!(f, "host error"),
+        }
+    }
+}
+
 pub struct Runtime {
     blob: Vec<u8>,
     ext: TestExternalities<Blake2Hasher>,
     method: WasmExecutionMethod,
+    heap_pages: u64,
+    disassemble: bool,
+    calls: Option<(File, PathBuf)>,
+    verify_root: bool,
+    native: bool,
 }
 
 impl Runtime {
@@ -71,6 +283,11 @@ impl Runtime {
             blob: WASM_BINARY.unwrap().to_vec(),
             ext: TestExternalities::default(),
             method: WasmExecutionMethod::Interpreted,
+            heap_pages: 8,
+            disassemble: false,
+            calls: None,
+            verify_root: false,
+            native: false,
         }
     }
     pub fn using_wasmi(mut self) -> Self {
@@ -86,32 +303,254 @@ impl Runtime {
         self.ext.register_extension(key_store);
         self
     }
-    #[allow(dead_code)]
+    pub fn with_shared_keystore(mut self, keystore: Arc<KeyStore>) -> Self {
+        self.ext.register_extension(KeystoreExt(keystore));
+        self
+    }
+    /// Factory of runtimes with the same blob and execution method, but fresh externalities
+    pub fn factory(&self) -> impl Fn() -> Runtime + Send {
+        let (blob, method, heap_pages, native) = (self.blob.clone(), self.method, self.heap_pages, self.native);
+        move || {
+            let mut rtm = Runtime::new().with_blob(blob.clone()).with_heap_pages(heap_pages);
+            rtm.method = method;
+            rtm.native = native;
+            rtm
+        }
+    }
     pub fn with_offchain(mut self) -> Self {
         let (offchain, _) = TestOffchainExt::new();
+        self.ext.register_extension(OffchainDbExt::new(offchain.clone()));
         self.ext.register_extension(OffchainWorkerExt::new(offchain));
         self
     }
-    pub fn call(&mut self, func: &str, args: &[u8]) -> Vec<u8> {
-        let mut extext = self.ext.ext();
-
-        WasmExecutor::new(
+    pub fn with_offchain_capabilities(mut self, capabilities: Capabilities) -> Self {
+        let (offchain, _) = TestOffchainExt::new();
+        let db = LimitedExternalities::new(capabilities, offchain.clone());
+        let limited = LimitedExternalities::new(capabilities, offchain);
+        self.ext.register_extension(OffchainDbExt::new(db));
+        self.ext.register_extension(OffchainWorkerExt::new(limited));
+        self
+    }
+    /// Offchain context of a node which is not a validator, with restricted capabilities
+    pub fn with_offchain_non_validator(mut self, capabilities: Capabilities) -> Self {
+        let (offchain, _) = TestOffchainExt::new();
+        let db = LimitedExternalities::new(capabilities, offchain.clone());
+        let limited = LimitedExternalities::new(capabilities, NonValidatorExt(offchain));
+        self.ext.register_extension(OffchainDbExt::new(db));
+        self.ext.register_extension(OffchainWorkerExt::new(limited));
+        self
+    }
+    /// Capture printed output in sink instead of discarding it, replaces the sink of previous runtimes
+    pub fn with_print_sink(self, sink: PrintSink) -> Self {
+        install_sink(Box::new(sink), log::LevelFilter::Debug);
+        self
+    }
+    /// Capture logged records in sink, replaces the sink of previous runtimes like the print sink
+    pub fn with_log_sink(self, sink: LogSink) -> Self {
+        install_sink(Box::new(sink), log::LevelFilter::Trace);
+        self
+    }
+    /// Executor for wasm blobs passed to the host (e.g. to read their runtime version)
+    pub fn with_wasm_executor(mut self) -> Self {
+        let executor = WasmExecutor::new(
             self.method,
-            Some(8), // heap_pages
+            Some(self.heap_pages),
             SubstrateHostFunctions::host_functions(),
             8, // max_runtime_instances
             None // cache_path
-        ).call_in_wasm(
-            &self.blob,
-            None, // Optional<Hash>
-            func,
-            args,
-            &mut extext,
-            MissingHostFunctions::Disallow,
-        ).unwrap()
+        );
+        self.ext.register_extension(CallInWasmExt::new(executor));
+        self
+    }
+    /// Executor of background tasks, needed by batch verification
+    pub fn with_task_executor(mut self) -> Self {
+        self.ext.register_extension(TaskExecutorExt::new(TaskExecutor::new()));
+        self
+    }
+    /// Offchain context performing real HTTP requests, e.g. to the mock server of the testsuite
+    pub fn with_offchain_http(mut self) -> Self {
+        self.ext.register_extension(OffchainWorkerExt::new(HttpOffchainExt::new()));
+        self
+    }
+    pub fn with_storage(mut self, key: &[u8], value: &[u8]) -> Self {
+        self.ext.execute_with(|| sp_io::storage::set(key, value));
+        self
+    }
+    pub fn with_child_storage(mut self, child: &[u8], key: &[u8], value: &[u8]) -> Self {
+        self.ext.execute_with(|| sp_io::default_child_storage::set(child, key, value));
+        self
+    }
+    pub fn with_blob(mut self, blob: Vec<u8>) -> Self {
+        self.blob = blob;
+        self
+    }
+    pub fn with_heap_pages(mut self, pages: u64) -> Self {
+        self.heap_pages = pages;
+        self
+    }
+    /// Replace state with genesis storage, drops all previously registered extensions
+    pub fn with_genesis(mut self, storage: Storage) -> Self {
+        // Calls are made on top of the genesis, exported next to them
+        if let Some((_, dir)) = &self.calls {
+            let mut file = File::create(dir.join("genesis")).expect("Failed to create genesis export file");
+            for (key, value) in &storage.top {
+                writeln!(file, "top {} {}", hex::encode(key), hex::encode(value))
+                    .expect("Failed to write genesis export file");
+            }
+            let mut children: Vec<_> = storage.children_default.values().collect();
+            children.sort_by_key(|child| child.child_info.storage_key().to_vec());
+            for child in children {
+                let name = hex::encode(child.child_info.storage_key());
+                for (key, value) in &child.data {
+                    writeln!(file, "child {} {} {}", name, hex::encode(key), hex::encode(value))
+                        .expect("Failed to write genesis export file");
+                }
+            }
+        }
+
+        self.ext = TestExternalities::new(storage);
+        self
+    }
+    pub fn with_disassembly(mut self) -> Self {
+        self.disassemble = true;
+        self
+    }
+    /// Record calls into the wasm blob to file, with the blobs and genesis used written next to it
+    pub fn with_call_export(mut self, path: &str) -> Self {
+        let file = File::create(path).expect("Failed to create call export file");
+        let dir = PathBuf::from(path).parent().map(PathBuf::from).unwrap_or_default();
+        self.calls = Some((file, dir));
+        self
+    }
+    pub fn with_root_verification(mut self) -> Self {
+        self.verify_root = true;
+        self
+    }
+    /// Execute test functions natively against sp-io instead of the wasm blob
+    pub fn with_native(mut self) -> Self {
+        self.native = true;
+        self
+    }
+    /// Run native code (e.g. to read storage) against the state of the runtime
+    pub fn execute_with<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.ext.execute_with(f)
+    }
+    /// Commit all changes of the overlay to the backend, like at the end of a block
+    pub fn commit(&mut self) {
+        self.ext.commit_all().expect("Failed to commit changes to backend");
+    }
+    pub fn call(&mut self, func: &str, args: &[u8]) -> Vec<u8> {
+        match self.try_call(func, args) {
+            Ok(res) => res,
+            Err(e) => {
+                if self.disassemble && !self.native {
+                    disasm::print_trap_context(&self.blob, func, &e.to_string());
+                }
+                panic!("Failed to call {}: {}", func, e)
+            }
+        }
+    }
+    pub fn try_call(&mut self, func: &str, args: &[u8]) -> Result<Vec<u8>, Error> {
+        let res = if self.native {
+            // Missing equivalents are reported as unsupported by the caller
+            // of the test, once it returned
+            self.ext
+                .execute_with(|| native::call(func, args))
+                .unwrap_or_else(|| Err(format!("No native equivalent of {}", func)))
+                .map_err(Error::Other)
+        } else {
+            let mut extext = self.ext.ext();
+
+            WasmExecutor::new(
+                self.method,
+                Some(self.heap_pages),
+                SubstrateHostFunctions::host_functions(),
+                8, // max_runtime_instances
+                None // cache_path
+            ).call_in_wasm(
+                &self.blob,
+                None, // Optional<Hash>
+                func,
+                args,
+                &mut extext,
+                MissingHostFunctions::Disallow,
+            )
+        };
+
+        // Record entry point with hex encoded input and output, and the blob called
+        if let Some((file, dir)) = &mut self.calls {
+            let output = match &res {
+                Ok(data) => hex::encode(data),
+                Err(_) => String::from("trap"),
+            };
+            let blob = format!("{}.wasm", hex::encode(blake2_256(&self.blob)));
+            if !dir.join(&blob).is_file() {
+                std::fs::write(dir.join(&blob), &self.blob).expect("Failed to write blob export file");
+            }
+            writeln!(file, "{} {} {} {}", func, hex::encode(args), output, blob)
+                .expect("Failed to write call export file");
+        }
+
+        res
+    }
+    /// Call without reporting panics of the executor (e.g. on traps), for
+    /// tests only interested in whether the call failed
+    pub fn try_call_silent(&mut self, func: &str, args: &[u8]) -> Result<Vec<u8>, Error> {
+        let hook = std::panic::take_hook();
+        std::panic::set_hook(Box::new(|_| {}));
+        let res = self.try_call(func, args);
+        std::panic::set_hook(hook);
+        res
     }
     pub fn call_and_decode<T: Decode>(&mut self, func: &str, args: &[u8]) -> T {
         Decode::decode(&mut self.call(func, args).as_slice())
             .expect("Failed to decode returned SCALE data")
     }
 }
+
+impl Drop for Runtime {
+    /// Cross-check root of the test externalities with an independent
+    /// recomputation from the raw key value pairs: The root of every child
+    /// trie is computed separately and placed under its prefixed key, instead
+    /// of relying on the roots tracked by the trie backend.
+    fn drop(&mut self) {
+        if !self.verify_root || std::thread::panicking() {
+            return;
+        }
+
+        let root = self.ext.execute_with(|| sp_io::storage::root());
+
+        let backend = self.ext.as_backend();
+        let (children, mut pairs): (Vec<_>, Vec<_>) = backend
+            .pairs()
+            .into_iter()
+            .partition(|(key, _)| key.starts_with(DEFAULT_CHILD_STORAGE_KEY_PREFIX));
+        for (prefixed, _) in children {
+            let child_info = ChildInfo::new_default(&prefixed[DEFAULT_CHILD_STORAGE_KEY_PREFIX.len()..]);
+            let child_pairs: Vec<_> = backend
+                .child_keys(&child_info, &[])
+                .into_iter()
+                .map(|key| {
+                    let value = backend
+                        .child_storage(&child_info, &key)
+                        .expect("Failed to read child storage")
+                        .expect("Child key without value");
+                    (key, value)
+                })
+                .collect();
+            if !child_pairs.is_empty() {
+                let child_root = Layout::<Blake2Hasher>::trie_root(child_pairs);
+                pairs.push((prefixed, child_root.as_ref().to_vec()));
+            }
+        }
+        let expected = Layout::<Blake2Hasher>::trie_root(pairs);
+
+        if root != expected.as_ref() {
+            panic!(
+                "Storage root of test externalities {} differs from full recomputation {}",
+                hex::encode(&root),
+                hex::encode(expected)
+            );
+        }
+    }
+}
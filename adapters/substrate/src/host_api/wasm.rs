@@ -0,0 +1,18 @@
+use crate::host_api::utils::{ParsedInput, Runtime};
+
+/// Instantiate hex encoded probe module in place of the test runtime and call
+/// its `probe` export, which returns nothing. Probes only differ in the shape
+/// of the module (e.g. how their memory is declared), so the call fails if and
+/// only if the host rejects the module.
+pub fn test_wasm_probe(rtm: Runtime, input: ParsedInput) {
+    // Parse inputs
+    let module = hex::decode(input.get(0)).expect("Invalid wasm module");
+
+    let mut rtm = rtm.with_blob(module);
+
+    // Reasons for rejection differ between hosts, only the outcome is printed
+    match rtm.try_call("probe", &[]) {
+        Ok(_) => println!("accepted"),
+        Err(_) => println!("rejected"),
+    }
+}
@@ -30,6 +30,7 @@ extern crate sp_state_machine;
 
 use clap::App;
 
+pub mod grandpa;
 pub mod hash;
 pub mod hasher;
 mod host_api;
@@ -47,7 +48,11 @@ fn main() {
     } else if let Some(matches) = matches.subcommand_matches("state-trie") {
         let mut trie_tryer: TrieTester = TrieTester::new(matches);
         trie_tryer.process_state_trie_command(matches);
+    } else if let Some(matches) = matches.subcommand_matches("grandpa") {
+        grandpa::process_grandpa_command(matches);
     } else if let Some(matches) = matches.subcommand_matches("host-api") {
         host_api::process_host_api_tests(matches);
+    } else if matches.subcommand_matches("healthcheck").is_some() {
+        println!("ok");
     }
 }
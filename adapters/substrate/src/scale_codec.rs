@@ -21,7 +21,32 @@ extern crate clap;
 extern crate data_encoding;
 
 use clap::ArgMatches;
-use parity_scale_codec::Encode;
+use parity_scale_codec::{Compact, Decode, DecodeAll, Encode};
+
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display};
+use std::str::FromStr;
+
+use sp_core::{blake2_256, ecdsa, ed25519, sr25519, Pair, H256};
+use sp_runtime::generic::{ChangesTrieSignal, Digest, DigestItem, Era, Header};
+use sp_runtime::traits::{
+    BlakeTwo256, Hash as HashT, Header as HeaderT, IdentifyAccount, UniqueSaturatedInto, Verify,
+};
+use sp_runtime::{
+    AccountId32, ConsensusEngineId, FixedPointNumber, FixedU128, Justifications, MultiAddress,
+    MultiSignature, MultiSigner, OpaqueExtrinsic, PerThing, Perbill, Percent,
+};
+
+/// Enum with explicit, non-contiguous variant indices.
+#[derive(Encode, Decode)]
+enum SparseEnum {
+    #[codec(index = 0)]
+    First,
+    #[codec(index = 5)]
+    Second(u8),
+    #[codec(index = 254)]
+    Third(u32),
+}
 
 fn encode(matches: &ArgMatches) {
     let scale_encoded_value = matches.value_of("input").unwrap().encode();
@@ -32,8 +57,585 @@ fn encode(matches: &ArgMatches) {
     );
 }
 
+/// Encode decimal value as compact integer.
+fn encode_compact(matches: &ArgMatches) {
+    let value: u128 = matches
+        .value_of("input")
+        .unwrap()
+        .parse()
+        .expect("Failed to parse compact value");
+
+    println!("encoded: {}", hex::encode(Compact(value).encode()));
+}
+
+/// Decode a hex encoded compact integer, rejecting non-canonical encodings.
+fn decode_compact(matches: &ArgMatches) {
+    let input = hex::decode(matches.value_of("input").unwrap())
+        .expect("Failed to decode hex input");
+
+    match Compact::<u128>::decode_all(&input) {
+        Ok(Compact(value)) => println!("decoded: {}", value),
+        Err(_) => println!("error"),
+    }
+}
+
+/// Decode a hex encoded `Option<bool>`, which uses a single byte.
+fn option_bool(matches: &ArgMatches) {
+    let input = hex::decode(matches.value_of("input").unwrap())
+        .expect("Failed to decode hex input");
+
+    let value = match Option::<bool>::decode_all(&input) {
+        Ok(value) => value,
+        Err(_) => {
+            println!("error");
+            return;
+        }
+    };
+
+    match value {
+        None => println!("decoded: none"),
+        Some(b) => println!("decoded: {}", b),
+    }
+
+    assert_eq!(value.encode(), input);
+}
+
+/// Decode a hex encoded `SparseEnum`, out of range indices have to fail.
+fn sparse_enum(matches: &ArgMatches) {
+    let input = hex::decode(matches.value_of("input").unwrap())
+        .expect("Failed to decode hex input");
+
+    let value = match SparseEnum::decode_all(&input) {
+        Ok(value) => value,
+        Err(_) => {
+            println!("error");
+            return;
+        }
+    };
+
+    match value {
+        SparseEnum::First => println!("decoded: First"),
+        SparseEnum::Second(v) => println!("decoded: Second({})", v),
+        SparseEnum::Third(v) => println!("decoded: Third({})", v),
+    }
+
+    assert_eq!(value.encode(), input);
+}
+
+/// Build a per-thing from comma separated parts and integer value, then print
+/// its encoding and the rounded products with the value.
+fn per_thing<P>(matches: &ArgMatches)
+where
+    P: PerThing + Encode,
+    P::Inner: FromStr + Display + Into<u128>,
+    <P::Inner as FromStr>::Err: Debug,
+    u128: UniqueSaturatedInto<P::Inner>,
+{
+    let input: Vec<&str> = matches.value_of("input").unwrap().split(',').collect();
+    assert_eq!(input.len(), 2, "per-thing requires parts and value");
+
+    // Out of range parts get clamped to one
+    let parts: P::Inner = input[0].parse().expect("Failed to parse parts");
+    let value: u128 = input[1].parse().expect("Failed to parse value");
+    let per_thing = P::from_parts(parts);
+
+    println!("encoded: {}", hex::encode(per_thing.encode()));
+    println!("parts: {}", per_thing.deconstruct());
+    println!("mul_floor: {}", per_thing.mul_floor(value));
+    println!("mul_ceil: {}", per_thing.mul_ceil(value));
+}
+
+/// Build fixed point number from comma separated inner value and integer
+/// value, then print its encoding and the saturating product with the value.
+fn fixed_u128(matches: &ArgMatches) {
+    let input: Vec<&str> = matches.value_of("input").unwrap().split(',').collect();
+    assert_eq!(input.len(), 2, "fixed point requires inner and integer value");
+
+    let inner: u128 = input[0].parse().expect("Failed to parse inner value");
+    let value: u128 = input[1].parse().expect("Failed to parse value");
+    let fixed = FixedU128::from_inner(inner);
+
+    println!("encoded: {}", hex::encode(fixed.encode()));
+    println!("saturating_mul_int: {}", fixed.saturating_mul_int(value));
+}
+
+/// Print era together with its validity window relative to current block.
+fn print_era(era: Era, current: u64) {
+    match era {
+        Era::Immortal => println!("era: immortal"),
+        Era::Mortal(period, phase) => println!("era: mortal {} {}", period, phase),
+    }
+
+    println!("birth: {}", era.birth(current));
+    println!("death: {}", era.death(current));
+}
+
+/// Build mortal era from comma separated period and current block.
+fn mortal_era(matches: &ArgMatches) {
+    let input: Vec<&str> = matches.value_of("input").unwrap().split(',').collect();
+    assert_eq!(input.len(), 2, "era requires period and current block");
+
+    let period: u64 = input[0].parse().expect("Failed to parse period");
+    let current: u64 = input[1].parse().expect("Failed to parse current block");
+    let era = Era::mortal(period, current);
+
+    println!("encoded: {}", hex::encode(era.encode()));
+    print_era(era, current);
+}
+
+/// Decode era from comma separated hex encoding and current block.
+fn decode_era(matches: &ArgMatches) {
+    let input: Vec<&str> = matches.value_of("input").unwrap().split(',').collect();
+    assert_eq!(input.len(), 2, "era requires encoding and current block");
+
+    let encoded = hex::decode(input[0]).expect("Failed to decode hex input");
+    let current: u64 = input[1].parse().expect("Failed to parse current block");
+
+    match Era::decode_all(&encoded) {
+        Ok(era) => print_era(era, current),
+        Err(_) => println!("error"),
+    }
+}
+
+/// Decode a hex encoded multi address, print its content and re-encode it.
+fn multi_address(matches: &ArgMatches) {
+    let input = hex::decode(matches.value_of("input").unwrap())
+        .expect("Failed to decode hex input");
+
+    let address = match MultiAddress::<AccountId32, u32>::decode_all(&input) {
+        Ok(address) => address,
+        Err(_) => {
+            println!("error");
+            return;
+        }
+    };
+
+    match &address {
+        MultiAddress::Id(id) => println!("id: {}", hex::encode(id)),
+        MultiAddress::Index(index) => println!("index: {}", index),
+        MultiAddress::Raw(data) => println!("raw: {}", hex::encode(data)),
+        MultiAddress::Address32(data) => println!("address32: {}", hex::encode(data)),
+        MultiAddress::Address20(data) => println!("address20: {}", hex::encode(data)),
+    }
+
+    assert_eq!(address.encode(), input);
+}
+
+/// Decode a hex encoded multi signer and print the account derived from it.
+fn multi_signer(matches: &ArgMatches) {
+    let input = hex::decode(matches.value_of("input").unwrap())
+        .expect("Failed to decode hex input");
+
+    let signer = match MultiSigner::decode_all(&input) {
+        Ok(signer) => signer,
+        Err(_) => {
+            println!("error");
+            return;
+        }
+    };
+
+    match &signer {
+        MultiSigner::Ed25519(public) => println!("ed25519: {}", hex::encode(public)),
+        MultiSigner::Sr25519(public) => println!("sr25519: {}", hex::encode(public)),
+        MultiSigner::Ecdsa(public) => println!("ecdsa: {}", hex::encode(public)),
+    }
+
+    assert_eq!(signer.encode(), input);
+
+    println!("account: {}", hex::encode(signer.into_account()));
+}
+
+/// Sign message with key of given scheme derived from secret uri.
+fn multi_sign(scheme: &str, suri: &str, message: &[u8]) -> (MultiSigner, MultiSignature) {
+    match scheme {
+        "ed25519" => {
+            let pair = ed25519::Pair::from_string(suri, None).expect("Failed to derive key");
+            (pair.public().into(), pair.sign(message).into())
+        }
+        "sr25519" => {
+            let pair = sr25519::Pair::from_string(suri, None).expect("Failed to derive key");
+            (pair.public().into(), pair.sign(message).into())
+        }
+        "ecdsa" => {
+            let pair = ecdsa::Pair::from_string(suri, None).expect("Failed to derive key");
+            (pair.public().into(), pair.sign(message).into())
+        }
+        _ => panic!("unknown signature scheme"),
+    }
+}
+
+/// Sign message given as comma separated scheme, secret uri and message, then
+/// verify the multi signature against the account of the multi signer.
+fn multi_signature(matches: &ArgMatches) {
+    let input: Vec<&str> = matches.value_of("input").unwrap().split(',').collect();
+    assert_eq!(input.len(), 3, "signature requires scheme, secret uri and message");
+
+    let (scheme, suri, message) = (input[0], input[1], input[2].as_bytes());
+    let (signer, signature) = multi_sign(scheme, suri, message);
+    let (other, _) = multi_sign(scheme, &format!("{}//other", suri), message);
+
+    // Signatures are randomized for some schemes, so only the signer is printed
+    println!("signer: {}", hex::encode(signer.encode()));
+
+    let account = signer.into_account();
+    println!("account: {}", hex::encode(&account));
+
+    println!("verified: {}", signature.verify(message, &account));
+    println!("verified_other_message: {}", signature.verify(&b"other"[..], &account));
+    println!("verified_other_signer: {}", signature.verify(message, &other.into_account()));
+}
+
+/// Build the payload to sign from comma separated hex encoded call and signed
+/// extensions (as `name=value`), then print the extra and additional signed
+/// data as well as the payload to sign (hashed if longer than 256 bytes).
+fn signed_payload(matches: &ArgMatches) {
+    let input: Vec<&str> = matches.value_of("input").unwrap().split(',').collect();
+
+    let call = hex::decode(input[0]).expect("Failed to decode hex call");
+    let mut extra = Vec::new();
+    let mut additional = Vec::new();
+
+    for extension in &input[1..] {
+        let mut parts = extension.splitn(2, '=');
+        let name = parts.next().unwrap();
+        let value = parts.next().unwrap_or("");
+
+        match name {
+            "CheckSpecVersion" | "CheckTxVersion" => {
+                let version: u32 = value.parse().expect("Failed to parse version");
+                version.encode_to(&mut additional);
+            }
+            "CheckGenesis" => {
+                decode_hash(value).encode_to(&mut additional);
+            }
+            "CheckEra" | "CheckMortality" => {
+                // Era and hash of the block the era starts at
+                let mut parts = value.splitn(2, ':');
+                let era = hex::decode(parts.next().unwrap()).expect("Failed to decode hex era");
+                let era = Era::decode_all(&era).expect("Failed to decode era");
+                let birth = decode_hash(parts.next().expect("era birth hash is missing"));
+                era.encode_to(&mut extra);
+                birth.encode_to(&mut additional);
+            }
+            "CheckNonce" => {
+                let nonce: u32 = value.parse().expect("Failed to parse nonce");
+                Compact(nonce).encode_to(&mut extra);
+            }
+            "ChargeTransactionPayment" => {
+                let tip: u128 = value.parse().expect("Failed to parse tip");
+                Compact(tip).encode_to(&mut extra);
+            }
+            "CheckMetadataHash" => {
+                // Enabled mode if a metadata hash is provided
+                let hash = if value.is_empty() { None } else { Some(decode_hash(value)) };
+                (hash.is_some() as u8).encode_to(&mut extra);
+                hash.encode_to(&mut additional);
+            }
+            "CheckWeight" => (),
+            _ => panic!("unknown signed extension: {}", name),
+        }
+    }
+
+    let payload = [&call[..], &extra[..], &additional[..]].concat();
+
+    println!("extra: {}", hex::encode(&extra));
+    println!("additional: {}", hex::encode(&additional));
+    println!("payload: {}", hex::encode(&payload));
+
+    if payload.len() > 256 {
+        println!("to_sign: {}", hex::encode(blake2_256(&payload)));
+    } else {
+        println!("to_sign: {}", hex::encode(&payload));
+    }
+}
+
+/// Decode a hex encoded digest item, print its content and re-encode it.
+fn digest_item(matches: &ArgMatches) {
+    let input = hex::decode(matches.value_of("input").unwrap())
+        .expect("Failed to decode hex input");
+
+    let item = match DigestItem::<H256>::decode_all(&input) {
+        Ok(item) => item,
+        Err(_) => {
+            println!("error");
+            return;
+        }
+    };
+
+    match &item {
+        DigestItem::ChangesTrieRoot(root) => {
+            println!("type: changes_trie_root");
+            println!("root: {}", hex::encode(root));
+        }
+        DigestItem::PreRuntime(engine, data) => {
+            println!("type: pre_runtime");
+            println!("engine: {}", hex::encode(engine));
+            println!("data: {}", hex::encode(data));
+        }
+        DigestItem::Consensus(engine, data) => {
+            println!("type: consensus");
+            println!("engine: {}", hex::encode(engine));
+            println!("data: {}", hex::encode(data));
+        }
+        DigestItem::Seal(engine, data) => {
+            println!("type: seal");
+            println!("engine: {}", hex::encode(engine));
+            println!("data: {}", hex::encode(data));
+        }
+        DigestItem::ChangesTrieSignal(ChangesTrieSignal::NewConfiguration(config)) => {
+            println!("type: changes_trie_signal");
+            match config {
+                Some(c) => println!("config: {},{}", c.digest_interval, c.digest_levels),
+                None => println!("config: none"),
+            }
+        }
+        DigestItem::Other(data) => {
+            println!("type: other");
+            println!("data: {}", hex::encode(data));
+        }
+    }
+
+    // Unknown engines and payloads have to be preserved as is
+    let encoded = item.encode();
+    assert_eq!(encoded, input);
+
+    println!("encoded: {}", hex::encode(encoded));
+}
+
+fn decode_hash(input: &str) -> H256 {
+    let bytes = hex::decode(input).expect("Failed to decode hex hash");
+    assert_eq!(bytes.len(), 32, "hash has to be 32 bytes long");
+    H256::from_slice(&bytes)
+}
+
+/// Build a header from comma separated fields (parent hash, number, state root,
+/// extrinsics root, followed by any number of hex encoded digest items).
+fn parse_header(input: &str) -> Header<u32, BlakeTwo256> {
+    let input: Vec<&str> = input.split(',').collect();
+    assert!(input.len() >= 4, "header requires at least four fields");
+
+    let parent_hash = decode_hash(input[0]);
+    let number: u32 = input[1].parse().expect("Failed to parse block number");
+    let state_root = decode_hash(input[2]);
+    let extrinsics_root = decode_hash(input[3]);
+
+    let logs = input[4..]
+        .iter()
+        .map(|item| {
+            let item = hex::decode(item).expect("Failed to decode hex digest item");
+            DigestItem::<H256>::decode_all(&item).expect("Failed to decode digest item")
+        })
+        .collect();
+
+    Header::<u32, BlakeTwo256>::new(
+        number,
+        extrinsics_root,
+        state_root,
+        parent_hash,
+        Digest { logs },
+    )
+}
+
+/// Build a header from comma separated fields, then print its encoding and the
+/// resulting block hash.
+fn header(matches: &ArgMatches) {
+    let header = parse_header(matches.value_of("input").unwrap());
+
+    println!("encoded: {}", hex::encode(header.encode()));
+    println!("hash: {}", hex::encode(header.hash()));
+}
+
+/// Engine id of BABE, authoring all blocks after genesis
+const BABE_ENGINE_ID: ConsensusEngineId = *b"BABE";
+
+/// Slot of a BABE pre-runtime digest: primary and secondary VRF pre-digests
+/// carry authority index, slot, VRF output and proof, secondary plain ones
+/// only authority index and slot.
+fn babe_slot(data: &[u8]) -> Option<u64> {
+    let len = match data.first()? {
+        1 | 3 => 1 + 4 + 8 + 32 + 64,
+        2 => 1 + 4 + 8,
+        _ => return None,
+    };
+    if data.len() != len {
+        return None;
+    }
+    u64::decode(&mut &data[5..13]).ok()
+}
+
+/// Slot a header (after genesis) was authored in, if it carries exactly one
+/// BABE pre-runtime digest, or why its digests are invalid. The seal has to be
+/// the last item, its signature is not verified as that needs the authorities
+/// from the state.
+fn verify_digests(header: &Header<u32, BlakeTwo256>) -> Result<u64, &'static str> {
+    let logs = header.digest().logs();
+
+    let slots: Vec<Option<u64>> = logs
+        .iter()
+        .filter_map(|item| match item {
+            DigestItem::PreRuntime(engine, data) if *engine == BABE_ENGINE_ID => Some(babe_slot(data)),
+            _ => None,
+        })
+        .collect();
+    let slot = match slots[..] {
+        [Some(slot)] => slot,
+        [None] => return Err("invalid pre-runtime digest"),
+        [] => return Err("missing pre-runtime digest"),
+        _ => return Err("multiple pre-runtime digests"),
+    };
+
+    match logs.last() {
+        Some(DigestItem::Seal(engine, data)) if *engine == BABE_ENGINE_ID && data.len() == 64 => Ok(slot),
+        _ => Err("missing seal"),
+    }
+}
+
+/// Verify the ancestry of a chain of headers (separated by semicolons, fields
+/// as for `header`) from the headers and their digests only, without any state
+/// access: every header has to reference the hash of its predecessor and
+/// increment its block number, and all headers after genesis have to carry a
+/// BABE pre-runtime digest with a slot above the one of their parent and end
+/// with a BABE seal. Prints the hash of the verified head.
+fn header_ancestry(matches: &ArgMatches) {
+    let headers: Vec<Header<u32, BlakeTwo256>> = matches
+        .value_of("input")
+        .unwrap()
+        .split(';')
+        .map(parse_header)
+        .collect();
+
+    let mut parent_slot = None;
+    for (i, header) in headers.iter().enumerate() {
+        if let Some(parent) = i.checked_sub(1).map(|i| &headers[i]) {
+            if header.parent_hash() != &parent.hash() {
+                println!("ancestry: invalid parent hash at block {}", header.number());
+                return;
+            }
+            if parent.number().checked_add(1) != Some(*header.number()) {
+                println!("ancestry: invalid number at block {}", header.number());
+                return;
+            }
+        }
+
+        // Genesis is not authored, so it carries no digests to verify
+        if *header.number() == 0 {
+            continue;
+        }
+
+        let slot = match verify_digests(header) {
+            Ok(slot) => slot,
+            Err(e) => {
+                println!("ancestry: {} at block {}", e, header.number());
+                return;
+            }
+        };
+        if parent_slot.map_or(false, |parent_slot| slot <= parent_slot) {
+            println!("ancestry: slot not increasing at block {}", header.number());
+            return;
+        }
+        parent_slot = Some(slot);
+    }
+
+    println!("head: {}", hex::encode(headers.last().unwrap().hash()));
+    println!("ancestry: ok");
+}
+
+/// Compute the extrinsics root of a block body from the encoded extrinsics
+/// (separated by semicolons, hex encoded including their length prefix): the
+/// ordered trie root of the encodings, keyed by their compact encoded index.
+fn extrinsics_root(matches: &ArgMatches) {
+    let input = matches.value_of("input").unwrap();
+    let extrinsics: Vec<Vec<u8>> = input
+        .split(';')
+        .filter(|e| !e.is_empty())
+        .map(|e| {
+            let encoded = hex::decode(e).expect("Failed to decode extrinsic hex");
+            OpaqueExtrinsic::decode_all(&encoded).expect("Invalid length prefix of extrinsic");
+            encoded
+        })
+        .collect();
+
+    println!("extrinsics: {}", extrinsics.len());
+    println!("root: {}", hex::encode(BlakeTwo256::ordered_trie_root(extrinsics)));
+}
+
+/// Build a justifications container from comma separated `engine:data` hex
+/// pairs, print its encoding and every entry retrieved by its engine id, as
+/// canonical JSON (see `CanonicalOutput` of the testsuite).
+fn justifications(matches: &ArgMatches) {
+    let input: Vec<(ConsensusEngineId, Vec<u8>)> = matches
+        .value_of("input")
+        .unwrap()
+        .split(',')
+        .map(|entry| {
+            let mut parts = entry.splitn(2, ':');
+            let engine = hex::decode(parts.next().unwrap()).expect("Failed to decode engine id");
+            assert_eq!(engine.len(), 4, "engine id has to be 4 bytes long");
+            let data = hex::decode(parts.next().expect("justification data is missing"))
+                .expect("Failed to decode justification data");
+            let mut id = ConsensusEngineId::default();
+            id.copy_from_slice(&engine);
+            (id, data)
+        })
+        .collect();
+
+    let mut entries = input.iter().cloned();
+    let mut justifications = Justifications::from(entries.next().unwrap());
+    let mut duplicates = Vec::new();
+    for entry in entries {
+        if !justifications.append(entry.clone()) {
+            duplicates.push(format!("\"0x{}\"", hex::encode(entry.0)));
+        }
+    }
+
+    let encoded = justifications.encode();
+    assert_eq!(
+        Justifications::decode_all(&encoded).expect("Failed to decode justifications"),
+        justifications
+    );
+
+    // Keys of objects have to be sorted, which lowercase hex already is
+    let retrieved: BTreeMap<String, String> = input
+        .iter()
+        .map(|(engine, _)| {
+            let data = justifications.get(*engine).expect("Failed to retrieve justification");
+            (hex::encode(engine), hex::encode(data))
+        })
+        .collect();
+    let retrieved: Vec<String> = retrieved
+        .iter()
+        .map(|(engine, data)| format!("\"0x{}\":\"0x{}\"", engine, data))
+        .collect();
+
+    println!(
+        "{{\"duplicates\":[{}],\"encoded\":\"0x{}\",\"entries\":{{{}}}}}",
+        duplicates.join(","),
+        hex::encode(encoded),
+        retrieved.join(",")
+    );
+}
+
 pub fn process_scale_codec_command(subcmd_matches: &ArgMatches) {
-    if subcmd_matches.is_present("encode") {
-        encode(subcmd_matches);
+    match subcmd_matches.value_of("codec-subcommand") {
+        Some("encode") => encode(subcmd_matches),
+        Some("encode-compact") => encode_compact(subcmd_matches),
+        Some("decode-compact") => decode_compact(subcmd_matches),
+        Some("option-bool") => option_bool(subcmd_matches),
+        Some("sparse-enum") => sparse_enum(subcmd_matches),
+        Some("perbill") => per_thing::<Perbill>(subcmd_matches),
+        Some("percent") => per_thing::<Percent>(subcmd_matches),
+        Some("fixed-u128") => fixed_u128(subcmd_matches),
+        Some("mortal-era") => mortal_era(subcmd_matches),
+        Some("decode-era") => decode_era(subcmd_matches),
+        Some("multi-address") => multi_address(subcmd_matches),
+        Some("multi-signer") => multi_signer(subcmd_matches),
+        Some("multi-signature") => multi_signature(subcmd_matches),
+        Some("signed-payload") => signed_payload(subcmd_matches),
+        Some("digest-item") => digest_item(subcmd_matches),
+        Some("header") => header(subcmd_matches),
+        Some("header-ancestry") => header_ancestry(subcmd_matches),
+        Some("justifications") => justifications(subcmd_matches),
+        Some("extrinsics-root") => extrinsics_root(subcmd_matches),
+        _ => panic!("scale codec subcommand is required"),
     }
 }
@@ -51,6 +51,9 @@ use clap::ArgMatches;
 
 use crate::hasher::blake2::Blake2Hasher;
 
+pub type RefPolkadotTrieDBMutNoExt<'a> =
+    trie_db::TrieDBMut<'a, GenericNoExtensionLayout<Blake2Hasher>>;
+
 ///An object to perform various tests on a trie
 pub struct TrieTester {
     keys: Vec<Vec<u8>>,
@@ -115,8 +118,6 @@ impl TrieTester {
         let mut root = Default::default();
 
         //let mut memtrie = RefTrieDBMutNoExt::new(&mut memdb, &mut root);
-        pub type RefPolkadotTrieDBMutNoExt<'a> =
-            trie_db::TrieDBMut<'a, GenericNoExtensionLayout<Blake2Hasher>>;
         let mut memtrie = RefPolkadotTrieDBMutNoExt::new(&mut memdb, &mut root);
 
         for i in 0..self.keys.len() {
@@ -138,12 +139,41 @@ impl TrieTester {
         }
     }
 
+    /// Create a trie from the key value yaml file and print the encodings of all its
+    /// stored (i.e. not inlined) nodes ordered by their hash, followed by its root.
+    fn print_trie_nodes(&self, _matches: &ArgMatches) {
+        let mut memdb = MemoryDB::<_, HashKey<_>, _>::default();
+        let mut root = Default::default();
+
+        {
+            let mut memtrie = RefPolkadotTrieDBMutNoExt::new(&mut memdb, &mut root);
+            for (key, val) in self.keys.iter().zip(self.values.iter()) {
+                memtrie.insert(key, val).unwrap();
+            }
+            // nodes are committed on drop
+        }
+
+        memdb.purge();
+        let nodes: BTreeMap<_, _> = memdb
+            .drain()
+            .into_iter()
+            .map(|(hash, (node, _))| (hash, node))
+            .collect();
+
+        for (hash, node) in nodes {
+            println!("node {:x}: {}", hash, hex::encode(node));
+        }
+        println!("state root: {:x}", root);
+    }
+
     pub fn process_state_trie_command(&mut self, subcmd_matches: &ArgMatches) {
         if let Some(trie_subcommand) = subcmd_matches.value_of("trie-subcommand") {
             if trie_subcommand == "trie-root" {
                 self.compute_state_root(subcmd_matches);
             } else if trie_subcommand == "insert-and-delete" {
                 self.insert_and_delete_test(subcmd_matches);
+            } else if trie_subcommand == "trie-nodes" {
+                self.print_trie_nodes(subcmd_matches);
             }
         } else {
             panic!("trie-root subcommand is required");
@@ -0,0 +1,40 @@
+using .HostFixture
+using Test
+
+
+"Properties declared in the chain spec of the tester (see runtimes/tester)"
+const PROPERTIES = [
+    "ss58Format"    => "5",
+    "tokenDecimals" => "9",
+    "tokenSymbol"   => "\"SPEC\"",
+]
+
+"Address of Alice (sr25519 //Alice, also her BABE key) rendered in the address format of the chain"
+const ALICE = "ajYMsCKsEAhEvHpeA4XqsfiA9v1CdzZPrCfS6pEfeGHW9j8"
+
+
+tester = HostFixture.Tester("Chain Properties", "tester")
+
+HostFixture.execute(tester, 10, rpc=[
+    "system_properties"       => "[]",
+    "babe_epochAuthorship"    => "[]",
+]) do (root, result, responses)
+    # Check that properties are reported unchanged
+    properties = get(responses, "system_properties", "")
+    for (name, value) in PROPERTIES
+        reported = occursin(Regex("\"$name\"\\s*:\\s*\\Q$value\\E"), properties)
+        if !reported
+            @warn "Property $name not reported as $value: $properties"
+        end
+        @test reported
+    end
+
+    # Check that keys are rendered by the host in the format of the chain, the
+    # slots claimed in the current epoch are keyed by the authority of Alice
+    authorship = get(responses, "babe_epochAuthorship", "")
+    rendered = occursin("\"$ALICE\"", authorship)
+    if !rendered
+        @warn "Authority not rendered as $ALICE: $authorship"
+    end
+    @test rendered
+end
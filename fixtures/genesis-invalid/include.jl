@@ -0,0 +1,59 @@
+using .HostFixture
+using Test
+
+
+"Valid tester genesis from which all invalid variants are derived"
+const GENESIS = "$(@__DIR__)/../../runtimes/tester/genesis.json"
+
+"Storage entry of the runtime code (i.e. `:code`) in the raw genesis"
+const CODE_ENTRY = r"\"0x3a636f6465\": \"0x([0-9a-f]*)\",\s*"
+
+"Structurally invalid variants of the genesis and how they are derived"
+const INVALID_GENESIS = [
+    "Missing code"       => g -> replace(g, CODE_ENTRY => ""),
+    "Invalid hex"        => g -> replace(g, CODE_ENTRY => s"\"0x3a636f6465\": \"0xzz\1\", "),
+    "Odd length hex"     => g -> replace(g, CODE_ENTRY => s"\"0x3a636f6465\": \"0x0\1\", "),
+    "Non-string value"   => g -> replace(g, CODE_ENTRY => s"\"0x3a636f6465\": 42, "),
+]
+
+"Output fragments indicating a crash instead of a proper error"
+const PANIC_MARKERS = [
+    "panicked at",       # rust
+    "panic:",            # go
+    "terminate called",  # c++
+    "Segmentation fault",
+]
+
+
+# Listing the fixture does not require a built genesis
+if !isfile(GENESIS) && !Config.listing
+    @error "Failed to locate genesis: $GENESIS"
+end
+
+for (name, derive) in INVALID_GENESIS
+    path = joinpath(mktempdir(), "genesis.json")
+
+    if !Config.listing
+        genesis = read(GENESIS, String)
+        invalid = derive(genesis)
+
+        if invalid == genesis
+            error("Failed to derive invalid genesis: $name")
+        end
+
+        write(path, invalid)
+    end
+
+    tester = HostFixture.Tester("Invalid Genesis: $name", "tester", path)
+
+    # Hosts have to reject the genesis and exit with an error
+    HostFixture.execute(tester, 5, true) do (root, result)
+        # Check that genesis is not loaded
+        @test !occursin(r"##([^#\n]+)##", result)
+
+        # Check that host did not crash
+        for marker in PANIC_MARKERS
+            @test !occursin(marker, result)
+        end
+    end
+end
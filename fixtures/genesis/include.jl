@@ -2,6 +2,29 @@ using .HostFixture
 using Test
 
 
+"Runtime API identifiers (blake2_64 of the API name) of all logged calls"
+const RUNTIME_API_CALLS = Dict(
+    "version()"               => "df6acb689907609b", # Core
+    "execute_block()"         => "df6acb689907609b",
+    "initialize_block()"      => "df6acb689907609b",
+    "metadata()"              => "37e397fc7c91f5e4", # Metadata
+    "apply_extrinsics()"      => "40fe3ad401f8959a", # BlockBuilder
+    "finalize_block()"        => "40fe3ad401f8959a",
+    "inherent_extrinsics()"   => "40fe3ad401f8959a",
+    "check_inherents()"       => "40fe3ad401f8959a",
+    "random_seed()"           => "40fe3ad401f8959a",
+    "validate_transaction()"  => "d2bc9897eed08f15", # TaggedTransactionQueue
+    "generate_session_keys()" => "ab3c0572291feb8b", # SessionKeys
+    "decode_session_keys()"   => "ab3c0572291feb8b",
+    "configuration()"         => "cbca25e39f142387", # BabeApi
+    "current_epoch_start()"   => "cbca25e39f142387",
+    "current_epoch()"         => "cbca25e39f142387",
+    "next_epoch()"            => "cbca25e39f142387",
+    "grandpa_authorities()"   => "ed99c5acb25eedf5", # GrandpaApi
+    "nested_transactions()"   => "3ce9b051fc30018d", # TesterApi
+)
+
+
 tester = HostFixture.Tester("Genesis", "tester")
 
 HostFixture.execute(tester, 5) do (root, result)
@@ -15,9 +38,27 @@ HostFixture.execute(tester, 5) do (root, result)
     # Extract all calls made from log
     calls = map(m -> m[1], eachmatch(r"@@([^@\n]+)@@", result))
 
+    # Check that version is requested (runtime has no version section)
+    @test "version()" in calls
+
     # Check that grandpa config is requested
     @test "grandpa_authorities()" in calls
 
     # Check that babe configuration is requested
     @test "configuration()" in calls
+
+
+    # Extract all declared runtime apis and versions from log
+    apis = Dict(map(m -> m[1] => parse(Int, m[2]), eachmatch(r"%%([0-9a-f]{16}):([0-9]+)%%", result)))
+
+    # Check that core api is declared
+    @test haskey(apis, RUNTIME_API_CALLS["version()"])
+
+    # Check that the tester api is declared in its current version
+    @test get(apis, TESTER_API.first, nothing) == TESTER_API.second
+
+    # Check that only declared runtime apis are called
+    for call in unique(calls)
+        @test haskey(RUNTIME_API_CALLS, call) && haskey(apis, RUNTIME_API_CALLS[call])
+    end
 end
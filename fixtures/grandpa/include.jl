@@ -0,0 +1,86 @@
+using .AdapterFixture
+
+
+# Chain with a fork at B: GENESIS - A - B - C - D and B - E
+const CHAIN = "GENESIS:A,A:B,B:C,C:D,B:E"
+
+# Four voters of equal weight, i.e. precommits and prevotes need a weight of
+# at least 3 for a supermajority (one faulty voter tolerated)
+const VOTERS = "alice:1,bob:1,charlie:1,dave:1"
+
+# Scripted votes of a round on top of GENESIS, with the expected state of the
+# round after each vote as defined by the spec: prevote GHOST, estimate,
+# finalized block and completability.
+const ROUNDS = [
+  # All voters agree, the prevoted block gets finalized and the round is completable
+  "prevote:alice:C,prevote:bob:C,prevote:charlie:C,precommit:alice:C,precommit:bob:C,precommit:charlie:C"
+    => """
+       prevote alice C: ghost=none, estimate=none, finalized=none, completable=0
+       prevote bob C: ghost=none, estimate=none, finalized=none, completable=0
+       prevote charlie C: ghost=C, estimate=C, finalized=none, completable=0
+       precommit alice C: ghost=C, estimate=C, finalized=none, completable=0
+       precommit bob C: ghost=C, estimate=C, finalized=none, completable=0
+       precommit charlie C: ghost=C, estimate=C, finalized=C, completable=1
+       """,
+  # Prevotes split on the fork, the GHOST is their common ancestor
+  "prevote:alice:D,prevote:bob:D,prevote:charlie:E,prevote:dave:E,precommit:alice:B,precommit:bob:B,precommit:charlie:B"
+    => """
+       prevote alice D: ghost=none, estimate=none, finalized=none, completable=0
+       prevote bob D: ghost=none, estimate=none, finalized=none, completable=0
+       prevote charlie E: ghost=B, estimate=B, finalized=none, completable=0
+       prevote dave E: ghost=B, estimate=B, finalized=none, completable=0
+       precommit alice B: ghost=B, estimate=B, finalized=none, completable=0
+       precommit bob B: ghost=B, estimate=B, finalized=none, completable=0
+       precommit charlie B: ghost=B, estimate=B, finalized=B, completable=1
+       """,
+  # Precommits below the GHOST, which can no longer be finalized in this round
+  "prevote:alice:C,prevote:bob:C,prevote:charlie:C,prevote:dave:C,precommit:alice:B,precommit:bob:B,precommit:charlie:B"
+    => """
+       prevote alice C: ghost=none, estimate=none, finalized=none, completable=0
+       prevote bob C: ghost=none, estimate=none, finalized=none, completable=0
+       prevote charlie C: ghost=C, estimate=C, finalized=none, completable=0
+       prevote dave C: ghost=C, estimate=C, finalized=none, completable=0
+       precommit alice B: ghost=C, estimate=C, finalized=none, completable=0
+       precommit bob B: ghost=C, estimate=C, finalized=none, completable=0
+       precommit charlie B: ghost=C, estimate=B, finalized=B, completable=1
+       """,
+  # Precommits above the GHOST keep the round incompletable, until the
+  # last prevote moves the GHOST up to them
+  "prevote:alice:D,prevote:bob:D,prevote:charlie:C,precommit:alice:D,precommit:bob:D,precommit:charlie:C,precommit:dave:D,prevote:dave:D"
+    => """
+       prevote alice D: ghost=none, estimate=none, finalized=none, completable=0
+       prevote bob D: ghost=none, estimate=none, finalized=none, completable=0
+       prevote charlie C: ghost=C, estimate=C, finalized=none, completable=0
+       precommit alice D: ghost=C, estimate=C, finalized=none, completable=0
+       precommit bob D: ghost=C, estimate=C, finalized=none, completable=0
+       precommit charlie C: ghost=C, estimate=C, finalized=C, completable=0
+       precommit dave D: ghost=C, estimate=C, finalized=C, completable=0
+       prevote dave D: ghost=D, estimate=D, finalized=D, completable=1
+       """,
+  # Duplicates, equivocations and votes of unknown voters
+  "prevote:alice:C,prevote:alice:C,prevote:alice:E,prevote:eve:C,prevote:bob:C,prevote:charlie:C"
+    => """
+       prevote alice C: ghost=none, estimate=none, finalized=none, completable=0
+       prevote alice C (duplicate): ghost=none, estimate=none, finalized=none, completable=0
+       prevote alice E (equivocation): ghost=none, estimate=none, finalized=none, completable=0
+       prevote eve C (unknown voter): ghost=none, estimate=none, finalized=none, completable=0
+       prevote bob C: ghost=none, estimate=none, finalized=none, completable=0
+       prevote charlie C: ghost=C, estimate=C, finalized=none, completable=0
+       """,
+]
+
+
+tests = AdapterFixture.Builder("GRANDPA Voter Rounds", `grandpa`)
+
+describe!(tests, "GRANDPA voter round state: prevote GHOST, estimate, finalization and completability",
+          "Polkadot Host Specification, Finality")
+
+require!(tests, "grandpa-voter")
+
+sub!(tests) do t
+  arg!(t, `round --chain $CHAIN --voters $VOTERS --base GENESIS --votes`)
+  foreach!(t, first.(ROUNDS))
+  commit!(t, last.(ROUNDS))
+end
+
+AdapterFixture.execute(tests)
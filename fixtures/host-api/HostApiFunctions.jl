@@ -6,6 +6,28 @@ module HostApiFunctions
 		"test_storage_init"
 	]
 	
+	# Hashing API, called with a repeated byte
+	const byte_count = [
+		"test_hashing_keccak_256_repeated",
+		"test_hashing_sha2_256_repeated",
+		"test_hashing_blake2_128_repeated",
+		"test_hashing_blake2_256_repeated",
+		"test_hashing_twox_256_repeated",
+		"test_hashing_twox_128_repeated",
+		"test_hashing_twox_64_repeated"
+	]
+
+	# Hashing API, called with data too large to pass as argument
+	const large_value = [
+		"ext_hashing_keccak_256_version_1",
+		"ext_hashing_sha2_256_version_1",
+		"ext_hashing_blake2_128_version_1",
+		"ext_hashing_blake2_256_version_1",
+		"ext_hashing_twox_256_version_1",
+		"ext_hashing_twox_128_version_1",
+		"ext_hashing_twox_64_version_1"
+	]
+
 	const value = [
 		"ext_hashing_keccak_256_version_1",
 		"ext_hashing_sha2_256_version_1",
@@ -42,6 +64,14 @@ module HostApiFunctions
 		"ext_default_child_storage_next_key_version_1"
 	]
 
+	const child_key_value_root = [
+		"test_child_storage_prefix_root"
+	]
+
+	const child_key_value_key_value_root = [
+		"test_child_storage_kill_root"
+	]
+
 	const child_key_value_offset_buffer_size = [
 		"ext_default_child_storage_read_version_1"
 	]
@@ -54,6 +84,36 @@ module HostApiFunctions
 		"ext_storage_clear_prefix_version_1"
 	]
 
+	const key_value_key_value_roots = [
+		"test_storage_root_changes_root"
+	]
+
+	const prefix_key_value_key_value_root = [
+		"test_storage_clear_prefix_limit_root"
+	]
+
+	# Storage API, called with a depth of nested transactions
+	const transaction_depth = [
+		"test_storage_transaction_depth"
+	]
+
+	const transaction_unbalanced = [
+		"test_storage_transaction_commit_unbalanced",
+		"test_storage_transaction_rollback_unbalanced"
+	]
+
+	const append_key_value_value = [
+		"test_storage_append_existing"
+	]
+
+	const overlay_key_value_value = [
+		"test_storage_overlay_reads"
+	]
+
+	const transaction_key_value_value = [
+		"test_storage_transaction_restore"
+	]
+
 	const key_value_key_value = [
 		"ext_storage_append_version_1",
 		"ext_storage_root_version_1",
@@ -71,6 +131,16 @@ module HostApiFunctions
 		"ext_crypto_sr25519_public_keys_version_1"
 	]
 
+	const key_count = [
+		"test_crypto_ed25519_generate_randomness",
+		"test_crypto_sr25519_generate_randomness"
+	]
+
+	const seed_list = [
+		"test_crypto_ed25519_generate_seeds",
+		"test_crypto_sr25519_generate_seeds"
+	]
+
 	# TODO: Expand seed data
 	const seed_msg = [
 		"ext_crypto_ed25519_sign_version_1",
@@ -85,5 +155,172 @@ module HostApiFunctions
 
 	const value_value_value = [
 		"ext_trie_blake2_256_ordered_root_version_1"
-	]	
+	]
+
+	const proof_key_value_key_value = [
+		"ext_trie_blake2_256_verify_proof_version_1",
+		"ext_trie_keccak_256_verify_proof_version_1"
+	]
+
+	const ed25519_encodings = [
+		"test_crypto_ed25519_verify_encodings"
+	]
+
+	const secp256k1_encodings = [
+		"test_crypto_secp256k1_ecdsa_recover",
+		"test_crypto_secp256k1_ecdsa_recover_compressed"
+	]
+
+	const seed_count = [
+		"test_crypto_batch_verify"
+	]
+
+	# Allocator API stress scenarios
+	const allocator_none = [
+		"test_allocator_zero_size"
+	]
+
+	const count_size = [
+		"test_allocator_roundtrip"
+	]
+
+	const size = [
+		"test_allocator_exhaust",
+		"test_allocator_double_free"
+	]
+
+	const allocator_sizes = [
+		"test_allocator_fragmentation"
+	]
+
+	# Misc and Logging API, called with (possibly invalid) UTF-8 encodings
+	const utf8_encodings = [
+		"test_misc_print_utf8_encodings"
+	]
+
+	const level_target_utf8_encodings = [
+		"test_logging_log_encodings"
+	]
+
+	# Logging API, logged records are captured and checked
+	const level_target_message = [
+		"test_logging_log"
+	]
+
+	# Misc API, printed output is captured and checked
+	const print_utf8 = [
+		"test_misc_print_utf8"
+	]
+
+	const print_hex = [
+		"test_misc_print_hex"
+	]
+
+	const print_num = [
+		"test_misc_print_num"
+	]
+
+	const runtime_version = [
+		"test_misc_runtime_version"
+	]
+
+	# Runtime module shape, called with probe modules
+	const wasm_probe = [
+		"test_wasm_probe"
+	]
+
+	# Offchain API, called in different offchain contexts
+	const offchain_validator = [
+		"ext_offchain_is_validator_version_1"
+	]
+
+	const offchain_context = [
+		"test_offchain_capabilities"
+	]
+
+	# Offchain API, called with all offchain capabilities
+	const offchain_random_seed = [
+		"ext_offchain_random_seed_version_1"
+	]
+
+	const offchain_timestamp = [
+		"ext_offchain_timestamp_version_1"
+	]
+
+	const offchain_deadline = [
+		"ext_offchain_sleep_until_version_1"
+	]
+
+	const offchain_key_value = [
+		"ext_offchain_local_storage_set_version_1"
+	]
+
+	const offchain_key_value_value = [
+		"ext_offchain_local_storage_compare_and_set_version_1"
+	]
+
+	# Offchain API, requests to the local HTTP mock server
+	const offchain_http = [
+		"test_offchain_http"
+	]
+
+	const offchain_http_deadlines = [
+		"test_offchain_http_deadlines"
+	]
+
+	# Trie API, called with generated tries of given size
+	const trie_count = [
+		"test_trie_blake2_256_root_generated",
+		"test_trie_blake2_256_ordered_root_generated"
+	]
+
+	# Keystore used concurrently, only supported by some implementations
+	const thread_count = [
+		"test_crypto_ed25519_keystore_concurrency",
+		"test_crypto_sr25519_keystore_concurrency"
+	]
+
+	# Sandbox API, only supported by some implementations
+	const sandbox_none = [
+		"ext_sandbox_instantiate_version_1",
+		"ext_sandbox_invoke_version_1",
+		"ext_sandbox_instance_teardown_version_1"
+	]
+
+	const sandbox_value = [
+		"ext_sandbox_memory_new_version_1",
+		"ext_sandbox_memory_get_version_1",
+		"ext_sandbox_memory_set_version_1",
+		"ext_sandbox_memory_teardown_version_1",
+		"test_sandbox_memory_limit"
+	]
+
+	const sandbox_key_value = [
+		"test_sandbox_nested"
+	]
+
+	# Block builder of the tester runtime, filling a block until its weight limit
+	const weight = [
+		"test_block_weight_accounting"
+	]
+
+	# Block builder of the tester runtime, filling a block until its length limit
+	const block_length = [
+		"test_block_length_accounting"
+	]
+
+	# Block initialization of the tester runtime, next to its genesis
+	const block_header = [
+		"test_block_initialization"
+	]
+
+	# Storage transactions of FRAME nested in the tester runtime
+	const transactional = [
+		"test_transactional_depth"
+	]
+
+	# Runtime api version negotiation against the tester runtime
+	const tester_api_version = [
+		"test_tester_api_version"
+	]
 end
@@ -452,4 +452,633 @@ module HostApiInputs
 			"scorpion six useless front start crawl axis win auto bird genuine sort fee convince entire minute cost village miracle hotel country"
 		],
 	]
+
+	# Seeds of keys to generate (`;` separated): secret key of RFC 8032 test 1,
+	# well-known development accounts, a repeated seed and the development
+	# phrase with the derivation of Alice (implied by `//Alice` on its own)
+	const seed_list = [
+		[
+			"0x9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60"
+		],
+		[
+			"//Alice;//Bob"
+		],
+		[
+			"//Alice;//Alice"
+		],
+		[
+			"bottom drive obey lake curtain smoke basket hold race lonely fit walk//Alice;//Alice"
+		],
+	]
+
+	# Number and size of buffers, around the allocation size classes
+	const count_size = [
+		[
+			"16", "1"
+		],
+		[
+			"16", "8"
+		],
+		[
+			"16", "9"
+		],
+		[
+			"8", "4096"
+		],
+		[
+			"2", "65536"
+		],
+	]
+
+	# Size of buffer to allocate
+	const size = [
+		[
+			"4096"
+		],
+		[
+			"65536"
+		],
+	]
+
+	# Buffer sizes allocated in order (`;` separated), each freed after the next
+	# one: doubling sizes, sizes just above each size class and a reused size
+	# adding up to more than the heap
+	const allocator_sizes = [
+		[
+			"8;16;32;64;128;256;512;1024;2048;4096;8192;16384;32768;65536;131072;262144;524288;1048576;2097152;4194304;8388608;16777216"
+		],
+		[
+			"9;17;33;65;129;257;513;1025;2049;4097;8193;16385;32769;65537;131073;262145;524289;1048577;2097153;4194305;8388609"
+		],
+		[
+			"65536;65536;65536;65536;65536;65536;65536;65536;65536;65536;65536;65536;65536;65536;65536;65536"
+		],
+	]
+
+	# Byte to repeat (hex) and count: empty, single byte and more than a page
+	const byte_count = [
+		[
+			"00", "0"
+		],
+		[
+			"00", "1"
+		],
+		[
+			"61", "65537"
+		],
+	]
+
+	# Value above the argument size limit of 64 KiB, passed as --input-file
+	const large_value = [
+		[
+			repeat("a", 65537)
+		],
+	]
+
+	# Number of generated trie entries: empty, single leaf, branches and over 256 items
+	const trie_count = [
+		[
+			"0"
+		],
+		[
+			"1"
+		],
+		[
+			"2"
+		],
+		[
+			"16"
+		],
+		[
+			"257"
+		],
+		[
+			"1000"
+		],
+	]
+
+	# Number of threads and keys generated by each
+	const thread_count = [
+		[
+			"4", "16"
+		],
+		[
+			"16", "4"
+		],
+	]
+
+	const key_count = [
+		[
+			"64"
+		],
+		[
+			"256"
+		],
+	]
+
+	# Number of signatures per scheme in a batch
+	const batch_count = [
+		[
+			"1"
+		],
+		[
+			"8"
+		],
+		[
+			"128"
+		],
+	]
+
+	# Ed25519 edge cases: public key, signature, message (all hex). The accepted
+	# set is pinned to cofactorless verification requiring canonical S and R,
+	# while non-canonical and small order public keys are accepted.
+	const ed25519_encodings = [
+		[ # valid signature
+			"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+			"6e2723455f0d2fffc9b2eed3278bdf1794c86f9930341fee99c23f71f4f7174df13fd4635d182d890a16e31d1ab2d22e08fce2be3eb947a1bb439e1d2868b406",
+			"506f6c6b61646f74"
+		],
+		[ # altered message
+			"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+			"6e2723455f0d2fffc9b2eed3278bdf1794c86f9930341fee99c23f71f4f7174df13fd4635d182d890a16e31d1ab2d22e08fce2be3eb947a1bb439e1d2868b406",
+			"706f6c6b61646f74"
+		],
+		[ # non-canonical S (S + L)
+			"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+			"8891a7f673357ecf0d0fa62e612d4b9214199d5100e1f125b15485aed9d4392791e2bd76779490edeac783289b4f2eee7631759eb898cc028b3502a1ae73a713",
+			"506f6c6b61646f7400"
+		],
+		[ # S with high bit set
+			"d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a",
+			"6e2723455f0d2fffc9b2eed3278bdf1794c86f9930341fee99c23f71f4f7174df13fd4635d182d890a16e31d1ab2d22e08fce2be3eb947a1bb439e1d2868b486",
+			"506f6c6b61646f74"
+		],
+		[ # small order A (identity), R identity, S zero
+			"0100000000000000000000000000000000000000000000000000000000000000",
+			"01000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+			"506f6c6b61646f74"
+		],
+		[ # non-canonical A (identity, y = p + 1)
+			"eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f",
+			"01000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+			"506f6c6b61646f74"
+		],
+		[ # A identity with sign bit set
+			"0100000000000000000000000000000000000000000000000000000000000080",
+			"01000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+			"506f6c6b61646f74"
+		],
+		[ # non-canonical R (identity, y = p + 1)
+			"0100000000000000000000000000000000000000000000000000000000000000",
+			"eeffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff7f0000000000000000000000000000000000000000000000000000000000000000",
+			"506f6c6b61646f74"
+		],
+		[ # order 8 A, k = 0 mod 8
+			"c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+			"01000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+			"506f6c6b61646f7400"
+		],
+		[ # order 8 A, k != 0 mod 8
+			"c7176a703d4dd84fba3c0b760d10670f2a2053fa2c39ccc64ec7fd7792ac037a",
+			"01000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+			"506f6c6b61646f7401"
+		],
+		[ # mixed order A, k != 0 mod 8
+			"9158312a9a8d6e3b34c891d6d61444f8b8211c5117ebad15bdb0bd68b07e0245",
+			"8891a7f673357ecf0d0fa62e612d4b9214199d5100e1f125b15485aed9d43927aeeafceda8d4a868ec5b4abcc3d2820120505aa699fa2a544a4de0b9b65ed607",
+			"506f6c6b61646f7400"
+		],
+		[ # mixed order A, k = 0 mod 8
+			"9158312a9a8d6e3b34c891d6d61444f8b8211c5117ebad15bdb0bd68b07e0245",
+			"45954e8e8137fd727d4742e8dc50c9a49de11e3840392a5e29369909e8eb6d476456243ec2b0082163a9bae707fa0c378e0927a71d85891c1ffda4c53cd9f909",
+			"506f6c6b61646f740c"
+		],
+		[ # A not on curve
+			"0200000000000000000000000000000000000000000000000000000000000000",
+			"6e2723455f0d2fffc9b2eed3278bdf1794c86f9930341fee99c23f71f4f7174df13fd4635d182d890a16e31d1ab2d22e08fce2be3eb947a1bb439e1d2868b406",
+			"506f6c6b61646f74"
+		],
+	]
+
+	# Secp256k1 ECDSA edge cases: signature with recovery id, message hash (both
+	# hex). Valid signatures are made with secret key sha2_256("polkadot") over
+	# the sha2_256 hashes of "Polkadot" and "Kusama".
+	const secp256k1_encodings = [
+		[ # valid signature, recovery id 1
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c401",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # valid signature, recovery id 0
+			"88070264b9a8d3f2ad7a0eb2a9d209950f16036542e9b5dc87426833e30443186cd846d34206e14371453b0ef67c9df1cc4955fc24dc549a893f562d03ed685800",
+			"16e3d38953e2baeb090a760d8ac123f4f61558a6ab12f351eb49d03776d89382"
+		],
+		[ # legacy recovery id (v + 27)
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c41c",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # flipped recovery id, other public key
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c400",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # altered message, other public key
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c401",
+			"c5bf12114ededb87c1f32d5e2bfd40f142e39aa4148c1c1c058241ad36844658"
+		],
+		[ # recovery id 2, R overflowing field
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c403",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # invalid recovery id 4
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c404",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # invalid legacy recovery id 31
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca83362011e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c41f",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # R zero
+			"00000000000000000000000000000000000000000000000000000000000000001e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c401",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # S zero
+			"99c126da20397558f23658764c3a7c583db7ff706e93981cc170e27ca8336201000000000000000000000000000000000000000000000000000000000000000001",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+		[ # R equal to curve order
+			"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd03641411e68090cf4948c16ce7b530cee12f2187ffbfdb656ac0229c5396b467b51f2c401",
+			"225a6ac58ea76ebcfc8c995c63ffcdbc2d254adc8ff9f32bbbf4a123abed01ed"
+		],
+	]
+
+	# Depths of nested transactions, around the limit of 255 enforced by FRAME
+	# (via `:transaction_level:`), which the host itself must not impose
+	const transaction_depth = [
+		[
+			"1",
+		],
+		[
+			"255",
+		],
+		[
+			"256",
+		],
+		[
+			"1024",
+		],
+	]
+
+	const transaction_unbalanced_depth = [
+		[
+			"0",
+		],
+		[
+			"1",
+		],
+		[
+			"256",
+		],
+	]
+
+	# Data passed as hex, since invalid UTF-8 can not be passed as argument
+	const utf8_encodings = [
+		[
+			"48656c6c6f", # valid
+		],
+		[
+			"80", # lone continuation byte
+		],
+		[
+			"c0af", # overlong encoding of '/'
+		],
+		[
+			"e282", # truncated 3-byte sequence
+		],
+		[
+			"eda080", # utf-16 surrogate
+		],
+		[
+			"f4908080", # beyond U+10FFFF
+		],
+		[
+			"ff", # invalid byte
+		],
+		[
+			"48656cff6c6f", # invalid byte within valid text
+		],
+	]
+
+	# Log level (1 = error ... 5 = trace), target and hex encoded message
+	const level_target_utf8_encodings = [
+		[
+			"1", "runtime", "48656c6c6f", # valid
+		],
+		[
+			"3", "runtime", "80", # lone continuation byte
+		],
+		[
+			"4", "runtime", "c0af", # overlong encoding of '/'
+		],
+		[
+			"5", "runtime", "e282", # truncated 3-byte sequence
+		],
+		[
+			"1", "runtime", "eda080", # utf-16 surrogate
+		],
+		[
+			"2", "runtime", "f4908080", # beyond U+10FFFF
+		],
+		[
+			"3", "runtime", "ff", # invalid byte
+		],
+		[
+			"4", "runtime", "48656cff6c6f", # invalid byte within valid text
+		],
+	]
+
+	# Log level, target and hex encoded message of every level, of targets as
+	# used by pallets and of messages with multi-byte characters
+	const level_target_message = [
+		[
+			"1", "runtime", "48656c6c6f",
+		],
+		[
+			"2", "runtime", "48656c6c6f",
+		],
+		[
+			"3", "runtime", "48656c6c6f",
+		],
+		[
+			"4", "runtime", "48656c6c6f",
+		],
+		[
+			"5", "runtime", "48656c6c6f",
+		],
+		[
+			"3", "pallet_balances", "48656c6c6f20576f726c64",
+		],
+		[
+			"3", "frame::executive", "48656c6c6f20576f726c64",
+		],
+		[
+			"3", "pället", "48656c6c6f20576f726c64", # non-ASCII target
+		],
+		[
+			"3", "runtime", "68c3a96c6c6f", # two-byte character
+		],
+		[
+			"3", "runtime", "f09f9a80", # four-byte character
+		],
+		[
+			"3", "runtime", "48656cff6c6f", # invalid byte within valid text
+		],
+	]
+
+	# Data printed as lowercase hex
+	const hex_data = [
+		[
+			"00",
+		],
+		[
+			"deadbeef",
+		],
+		[
+			"DEADBEEF", # uppercase input
+		],
+		[
+			"48656c6c6f",
+		],
+	]
+
+	# Numbers printed in decimal, including u64 limits
+	const num_u64 = [
+		[
+			"0",
+		],
+		[
+			"42",
+		],
+		[
+			"4294967296",
+		],
+		[
+			"18446744073709551615",
+		],
+	]
+
+	# Hex encoded wasm blobs without a runtime version
+	const wasm_blobs = [
+		[
+			"00", # not a wasm module
+		],
+		[
+			"0061736d01000000", # empty wasm module
+		],
+	]
+
+	# Hex encoded probe modules declaring their memory in different ways, each
+	# exporting `__heap_base` and a `probe` function returning nothing. Hosts
+	# add 8 heap pages to the initial size of the memory.
+	const wasm_memory_probes = [
+		[
+			"0061736d01000000010a0260027f7f017e600000020f0103656e76066d656d6f7279020001030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503000a0601040042000b", # imports memory of 1 page
+		],
+		[
+			"0061736d01000000010a0260027f7f017e60000002100103656e76066d656d6f727902010109030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503000a0601040042000b", # imports memory of 1 page, at most 9 (fits heap pages)
+		],
+		[
+			"0061736d01000000010a0260027f7f017e60000002100103656e76066d656d6f727902010108030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503000a0601040042000b", # imports memory of 1 page, at most 8 (no room for heap pages)
+		],
+		[
+			"0061736d01000000010a0260027f7f017e60000002100103656e76066d656d6f727902010201030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503000a0601040042000b", # imports memory of 2 pages, at most 1 (invalid)
+		],
+		[
+			"0061736d01000000010a0260027f7f017e6000000302010005030100010607017f004180080b0720030570726f626500000b5f5f686561705f626173650300066d656d6f727902000a0601040042000b", # exports memory of 1 page
+		],
+		[
+			"0061736d01000000010a0260027f7f017e600000030201000504010101090607017f004180080b0720030570726f626500000b5f5f686561705f626173650300066d656d6f727902000a0601040042000b", # exports memory of 1 page, at most 9 (fits heap pages)
+		],
+		[
+			"0061736d01000000010a0260027f7f017e600000030201000504010101080607017f004180080b0720030570726f626500000b5f5f686561705f626173650300066d656d6f727902000a0601040042000b", # exports memory of 1 page, at most 8 (no room for heap pages)
+		],
+		[
+			"0061736d01000000010a0260027f7f017e6000000302010005030100010607017f004180080b071d030570726f626500000b5f5f686561705f626173650300036d656d02000a0601040042000b", # exports memory of 1 page as `mem`
+		],
+		[
+			"0061736d01000000010a0260027f7f017e600000030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503000a0601040042000b", # neither imports nor exports memory
+		],
+	]
+
+	# Hex encoded probe modules importing memory of 1 page, with a start
+	# function or globals
+	const wasm_shape_probes = [
+		[
+			"0061736d01000000010a0260027f7f017e600000020f0103656e76066d656d6f727902000103030200010607017f004180080b0717020570726f626500000b5f5f686561705f6261736503000801010a0902040042000b02000b", # start function, which does nothing
+		],
+		[
+			"0061736d01000000010a0260027f7f017e600000020f0103656e76066d656d6f727902000103020100060c027f004180080b7f0141000b0717020570726f626500000b5f5f686561705f6261736503000a0601040042000b", # defines mutable global
+		],
+		[
+			"0061736d01000000010a0260027f7f017e60000002230203656e76066d656d6f727902000103656e760c70726f62655f676c6f62616c037f00030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503010a0601040042000b", # imports immutable global
+		],
+		[
+			"0061736d01000000010a0260027f7f017e60000002230203656e76066d656d6f727902000103656e760c70726f62655f676c6f62616c037f01030201000607017f004180080b0717020570726f626500000b5f5f686561705f6261736503010a0601040042000b", # imports mutable global
+		],
+	]
+
+	# Offchain contexts: with all capabilities, without any (of a validator or
+	# not) or no offchain context
+	const offchain_validator = [
+		[
+			"validator"
+		],
+		[
+			"restricted"
+		],
+		[
+			"restricted-non-validator"
+		],
+	]
+
+	const offchain_context = [
+		[
+			"validator"
+		],
+		[
+			"restricted"
+		],
+		[
+			"unavailable"
+		],
+	]
+
+	# HTTP requests to the mock server: method, path, body and timeout in milliseconds
+	const offchain_http = [
+		[
+			"GET", "/hello", "", "5000"
+		],
+		[
+			"POST", "/echo", "ping", "5000"
+		],
+		[
+			"POST", "/echo", "Polkadot Host Test Suite", "5000"
+		],
+		[
+			"GET", "/missing", "", "5000"
+		],
+		[
+			"GET", "/slow", "", "500"
+		],
+	]
+
+	# HTTP requests to the mock server waited for together: paths (or invalid
+	# request ids) and deadline in milliseconds
+	const offchain_http_deadlines = [
+		[
+			"/hello;/slow;invalid;/missing", "500"
+		],
+		[
+			"/slow;/hello", "500"
+		],
+	]
+
+	# Deadlines as milliseconds since the unix epoch (all in the past) or in
+	# the future relative to the start of the test, if prefixed with `+`
+	const offchain_deadline = [
+		[
+			"0"
+		],
+		[
+			"1000"
+		],
+		[
+			"1577836800000" # 2020-01-01
+		],
+		[
+			"+250"
+		],
+	]
+
+	# Weights of extrinsics filling a block (`;` separated, repeated in order),
+	# the normal class is limited to 75% of 2 seconds of compute by the tester
+	# runtime, including a base weight per extrinsic.
+	const weight = [
+		[
+			"100000000000" # 0.1s
+		],
+		[
+			"10000000000" # 0.01s
+		],
+		[
+			"500000000000;1"
+		],
+		[
+			"1500000000000" # exceeds maximum per extrinsic
+		],
+	]
+
+	# Sizes of signed remarks filling the first block, whose length is limited
+	# to 75% of 5 MiB (3932160 bytes) for the normal class by the tester runtime.
+	# Remarks of 3932050 bytes exactly fill a block on their own.
+	const block_length = [
+		[
+			"1048576" # 1 MiB
+		],
+		[
+			"3932160" # exceeds maximum by the encoding of the extrinsic
+		],
+		[
+			"3932050;0" # exactly fills block, nothing fits afterwards
+		],
+		[
+			"3932051" # exceeds maximum by one byte
+		],
+		[
+			"3931945;0" # exactly filled by an empty remark (105 bytes)
+		],
+	]
+
+	# Headers initialized on top of the tester genesis: Block number and parent
+	# hash, the genesis hash stored by the system pallet (0x45..45) or zero
+	const block_header = [
+		[
+			"1", "4545454545454545454545454545454545454545454545454545454545454545"
+		],
+		[
+			"1", "0000000000000000000000000000000000000000000000000000000000000000"
+		],
+		[
+			"0", "4545454545454545454545454545454545454545454545454545454545454545"
+		],
+		[
+			"0", "0000000000000000000000000000000000000000000000000000000000000000"
+		],
+	]
+
+	# Depths of nested FRAME transactions, up to and beyond the limit of 255
+	# enforced by later releases of FRAME
+	const transactional_depth = [
+		[
+			"1"
+		],
+		[
+			"255"
+		],
+		[
+			"256"
+		],
+	]
+
+	# Versions of the tester api required by the caller, below, equal to and
+	# above the version 2 declared by the tester runtime
+	const tester_api_versions = [
+		[
+			"1"
+		],
+		[
+			"2"
+		],
+		[
+			"3"
+		],
+	]
 end
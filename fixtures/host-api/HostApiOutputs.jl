@@ -4,6 +4,40 @@ module HostApiOutputs
         "2747ab7c0dc38b7f2afba82bd5e2d6acef8c31e09800f660b75ec84a7005099f\n"
     ]
 
+    const byte_count = [
+        # test_hashing_keccak_256_repeated
+        "c5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470",
+        "bc36789e7a1e281436464229828f817d6612f7b477d66591ff96a9e064bcc98a",
+        "de9754ea8bd851fb25153843eec74fc49f175247db360563fb1cdc264b05fcf1",
+        # test_hashing_sha2_256_repeated
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855",
+        "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d",
+        "008ffc88d3c96a9f307524eb361e47c5222a887fc45fa0c1fb8d429c5c23b430",
+        # test_hashing_blake2_128_repeated
+        "cae66941d9efbd404e4d88758ea67670",
+        "7025e075d5e2f6cde3cc051a31f07660",
+        "c319977c9c76b82bdfe8ec70d0536776",
+        # test_hashing_blake2_256_repeated
+        "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8",
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314",
+        "9a08ac2ce73629060fea1f323ca6f6d33f6a61fb11f2c11681b9ca710c5252af",
+        # test_hashing_twox_256_repeated
+        "99e9d85137db46ef4bbea33613baafd56f963c64b1f3685a4eb4abd67ff6203a",
+        "682705db4aa834e95124eef6c71719772dde12a78da5a8d714ac0f1ccc690fab",
+        "b56934254d05103c385c8754e56c06c768c5b00ccb2c854fa47422410bb91e2d",
+        # test_hashing_twox_128_repeated
+        "99e9d85137db46ef4bbea33613baafd5",
+        "682705db4aa834e95124eef6c7171977",
+        "b56934254d05103c385c8754e56c06c7",
+        # test_hashing_twox_64_repeated
+        "99e9d85137db46ef",
+        "682705db4aa834e9",
+        "b56934254d05103c",
+    ]
+
+    # Same data as the repeated byte 61 of the last byte count, for every hash
+    const large_value = byte_count[3:3:end]
+
     const value = [
         # ext_hashing_keccak_256_version_1
         "d517392f8119f79c1623774b9346e00104a1d193f1fa641e6e659bf323c37967",
@@ -262,6 +296,37 @@ module HostApiOutputs
         "object-oriented\n",
     ]
 
+    const prefix_key_value_key_value_root = [
+        # test_storage_clear_prefix_limit_root, root after removing each key
+        # with the prefix in turn (sorted bytewise), with :code and :heappages
+        # of the initial state remaining besides the keys outside the prefix
+        "bb58b7c087a192133a5e089df85c44d3793bec8862390f26f0ae971444041149\n43f67a5eb48e3166bcd5ce660dfbe024430509dd7a7322ad82e88cd961a2d743\n71db8a5c922eedcdf21b99c120f99688a25ca03a892ab16331c8067175329249\n",
+        "518b4fbd5f6bfbc15ad5bf49478f107478515923663badd69cd342f6557a0779\ndf19fc6a197f3283216d11409c3a41ed1ee72ef8c400902d7ee51afaa85a1c25\na9c78b540c4c3c5c25e098137f8aceace99c2e5c5a5b956a0145b470a69ddfd7\n",
+        "d86da3d85fa09cf45ecc0d6f71d8b0c223a49eea60ce06b688fb24aaf3975294\n6d4ce804b3a62cc3613de9892de777d1bf9feabcba9ee5f910f3be0d0f2114bf\nabe228b977e438a38eb29127ef67f7d163308d6e2209ebbd7a4843d5f270e692\n",
+        "ac41a5d59a35505657457e5b8e853a9f8891125e834e8d04dc185a62f396bc07\na36eedebb6a6d5cba998d71214d9b6e1394546df51e54e50124a008c329085a3\n089b3cbdfe75573a34ebd571332bbf16e2740ca876ce96507d7cf30feb02f893\n2747ab7c0dc38b7f2afba82bd5e2d6acef8c31e09800f660b75ec84a7005099f\n",
+        "e94d4dc3fd112e834525927903ddd412dd5c9223af97d3fffb928dd78295cc9c\nef892cfb7c591bcd1d93ff7d7453e10353d82b30ec71c3f87b73bd8936d419c3\n",
+        "a54653ed01b44516ad5f80da2918b3def4109c8dcd5f8f0aa63d95d40f448e31\ndfeb34bd1bf7caced1c12bb8c04a9d614982a587db2ad084b30319f82d7b6868\n5a0b06f5b9b7bdcbcdff9e55891eac4c4a951e4e41833e808cda57c0cd3aca6a\n",
+        "d621b62a566eea33635ae0786fadb97fa7f22e78de9c0729023998e5624379f0\ncc1431d76ada06cc9beb5a41dead5b2ded9035d14a5a69b3e7a00284e52ce5ce\n",
+        "2bfb60f2fdbc01f21a5003fbbda82f7a4dc82af47b1c492c8dbdf6f9e81a8c3a\nf5ab2ad6934f2e3b2408aced5bfd774d75f84f3b981b6654298592d12c4775a0\n8a8dff1cdcec8e6de2f1bd7a72fe64cbbb7f2ef634bcc026ce8b8573086578e2\n2747ab7c0dc38b7f2afba82bd5e2d6acef8c31e09800f660b75ec84a7005099f\n",
+        "ec0582482dcf3a42ca7a634eb2f65c77da8f8b0569a40e39f5ac74a369fff20b\n6693bcbbccc2dccc664ab5b12bdc933d52f0b20f247c64a43c5ef8f6b40f0150\n",
+        "eaa717bf7c836016c64d32f93fc4e84da8f18a0ee19819e10fbefc1300660f03\nc8d46590a11e29e65b2ea147421c8aaf49997101469dd78190fcbe59712639c2\n"
+    ]
+
+    const key_value_key_value_roots = [
+        # test_storage_root_changes_root, same roots as of ext_storage_root_version_1
+        # and no changes root as no changes trie is configured
+        "root: 88642528f17282b87fabd1f04e75228e2a167b847e6656c9b95beda1ffd24d87\nchanges root: none\n",
+        "root: aba4e533d278005c1814c64ccb1f524d5eb425bdaf74870895ce4d0148ede0e9\nchanges root: none\n",
+        "root: 38e58296e627090ba24d4fb4a2aa321550709f9474cda22a86650f5383916101\nchanges root: none\n",
+        "root: 2ea5608acee55be4551b39f44e70135289600930bebd7c5e33d2363bf832695b\nchanges root: none\n",
+        "root: ef892cfb7c591bcd1d93ff7d7453e10353d82b30ec71c3f87b73bd8936d419c3\nchanges root: none\n",
+        "root: dfeb34bd1bf7caced1c12bb8c04a9d614982a587db2ad084b30319f82d7b6868\nchanges root: none\n",
+        "root: cc1431d76ada06cc9beb5a41dead5b2ded9035d14a5a69b3e7a00284e52ce5ce\nchanges root: none\n",
+        "root: f5ab2ad6934f2e3b2408aced5bfd774d75f84f3b981b6654298592d12c4775a0\nchanges root: none\n",
+        "root: 6693bcbbccc2dccc664ab5b12bdc933d52f0b20f247c64a43c5ef8f6b40f0150\nchanges root: none\n",
+        "root: c8d46590a11e29e65b2ea147421c8aaf49997101469dd78190fcbe59712639c2\nchanges root: none\n"
+    ]
+
     const key_value_key_value = [
         # ext_storage_append_version_1
         "Inverse;Future-proofed\nFuture-proofed;Inverse;Future-proofed;Inverse",
@@ -313,6 +378,301 @@ module HostApiOutputs
         "84f22cc1152616a2fe2b6b13ce18b183278397ba250f06ecfd293ad650c60e3b"
     ]
 
+    const key_count = [
+        # test_crypto_ed25519_generate_randomness
+        "64 unique keys",
+        "256 unique keys",
+        # test_crypto_sr25519_generate_randomness
+        "64 unique keys",
+        "256 unique keys"
+    ]
+
+    # Published keys of the seeds, the keystore lists each key once
+    const seed_list = [
+        # test_crypto_ed25519_generate_seeds
+        "1. Public key: d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a\nListed keys: 1",
+        "1. Public key: 88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee\n" *
+        "2. Public key: d17c2d7823ebf260fd138f2d7e27d114c0145d968b5ff5006125f2414fadae69\nListed keys: 2",
+        "1. Public key: 88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee\n" *
+        "2. Public key: 88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee\nListed keys: 1",
+        "1. Public key: 88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee\n" *
+        "2. Public key: 88dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee\nListed keys: 1",
+        # test_crypto_sr25519_generate_seeds
+        "1. Public key: 44a996beb1eef7bdcab976ab6d2ca26104834164ecf28fb375600576fcc6eb0f\nListed keys: 1",
+        "1. Public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\n" *
+        "2. Public key: 8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48\nListed keys: 2",
+        "1. Public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\n" *
+        "2. Public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\nListed keys: 1",
+        "1. Public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\n" *
+        "2. Public key: d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d\nListed keys: 1"
+    ]
+
+    const ed25519_encodings = [
+        # test_crypto_ed25519_verify_encodings
+        "GOOD SIGNATURE", # valid signature
+        "BAD SIGNATURE", # altered message
+        "BAD SIGNATURE", # non-canonical S (S + L)
+        "BAD SIGNATURE", # S with high bit set
+        "GOOD SIGNATURE", # small order A (identity), R identity, S zero
+        "GOOD SIGNATURE", # non-canonical A (identity, y = p + 1)
+        "GOOD SIGNATURE", # A identity with sign bit set
+        "BAD SIGNATURE", # non-canonical R (identity, y = p + 1)
+        "GOOD SIGNATURE", # order 8 A, k = 0 mod 8
+        "BAD SIGNATURE", # order 8 A, k != 0 mod 8
+        "BAD SIGNATURE", # mixed order A, k != 0 mod 8
+        "GOOD SIGNATURE", # mixed order A, k = 0 mod 8
+        "BAD SIGNATURE" # A not on curve
+    ]
+
+    # Same public key in uncompressed (64 bytes, without 0x04 prefix) and
+    # compressed (33 bytes) form, invalid R or S may be reported either way
+    # (outputs are patterns)
+    const secp256k1_encodings = [
+        # test_crypto_secp256k1_ecdsa_recover
+        "Public key: 7da07b2851981be3c5b21117e9e15986f3639646b10feacbd3e0269246b240da2c12322445c67d012f0ad5dbcbaa213643f6bdb227b91852045bc5efa893fc80", # valid signature, recovery id 1
+        "Public key: 7da07b2851981be3c5b21117e9e15986f3639646b10feacbd3e0269246b240da2c12322445c67d012f0ad5dbcbaa213643f6bdb227b91852045bc5efa893fc80", # valid signature, recovery id 0
+        "Public key: 7da07b2851981be3c5b21117e9e15986f3639646b10feacbd3e0269246b240da2c12322445c67d012f0ad5dbcbaa213643f6bdb227b91852045bc5efa893fc80", # legacy recovery id (v + 27)
+        "Public key: a01ce6d86594c022fa7595c8d618a8847b5b9f46ff4be239d470872db08bffff7da348e8cb5c1de87098a328198b4a59e2408bce30323a299561e69fe66ab38c", # flipped recovery id, other public key
+        "Public key: 30846e6bc4a4773671da3debf849037ab87d30f77ccc623fe2282cc3d4aad2d6f388ab02a1c9df55f584025db96db87cc75493a33d73a8cb25c522df92dd8923", # altered message, other public key
+        "Error: bad signature", # recovery id 2, R overflowing field
+        "Error: bad V", # invalid recovery id 4
+        "Error: bad V", # invalid legacy recovery id 31
+        "Error: {any-of=bad RS|bad signature}", # R zero
+        "Error: {any-of=bad RS|bad signature}", # S zero
+        "Error: {any-of=bad RS|bad signature}", # R equal to curve order
+        # test_crypto_secp256k1_ecdsa_recover_compressed
+        "Public key: 027da07b2851981be3c5b21117e9e15986f3639646b10feacbd3e0269246b240da", # valid signature, recovery id 1
+        "Public key: 027da07b2851981be3c5b21117e9e15986f3639646b10feacbd3e0269246b240da", # valid signature, recovery id 0
+        "Public key: 027da07b2851981be3c5b21117e9e15986f3639646b10feacbd3e0269246b240da", # legacy recovery id (v + 27)
+        "Public key: 02a01ce6d86594c022fa7595c8d618a8847b5b9f46ff4be239d470872db08bffff", # flipped recovery id, other public key
+        "Public key: 0330846e6bc4a4773671da3debf849037ab87d30f77ccc623fe2282cc3d4aad2d6", # altered message, other public key
+        "Error: bad signature", # recovery id 2, R overflowing field
+        "Error: bad V", # invalid recovery id 4
+        "Error: bad V", # invalid legacy recovery id 31
+        "Error: {any-of=bad RS|bad signature}", # R zero
+        "Error: {any-of=bad RS|bad signature}", # S zero
+        "Error: {any-of=bad RS|bad signature}" # R equal to curve order
+    ]
+
+    # Any invalid signature fails the whole batch
+    const seed_count = "empty batch: GOOD SIGNATURES\n" *
+                       "ed25519 batch: GOOD SIGNATURES\n" *
+                       "sr25519 batch: GOOD SIGNATURES\n" *
+                       "ecdsa batch: GOOD SIGNATURES\n" *
+                       "mixed batch: GOOD SIGNATURES\n" *
+                       "mixed batch, invalid ed25519 signature: BAD SIGNATURES\n" *
+                       "mixed batch, invalid sr25519 signature: BAD SIGNATURES\n" *
+                       "mixed batch, invalid ecdsa signature: BAD SIGNATURES\n"
+
+    # Only valid proof verifies, independent of proven key value pair
+    const proof_key_value_key_value = "valid: 1\nwrong value: 0\nwrong root: 0\nmissing node: 0\nextra node: 0\n"
+
+    const child_key_value_key_value_root = [
+        # test_child_storage_kill_root: roots of both children and the main trie, then after
+        # killing the first child its (empty) root and the main trie without it
+        "e04eb753bc044436c6624b2062f7ad2be3bf19c62ed6f10aa2d7ee2586828cd5\n" *
+        "43afa69c8053a054ec1fc383e984d6273a72c09d7277235c980afb71141f2255\n" *
+        "ab1061752bd520d77b0cc1641dbca63e4f432afe4542803f415095c14935b66a\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "d88b8f02dc66d3bb17e9783a8bce968747f59ba2bb661bdd622a3596a1b74e2e\n",
+        "e563e5520daa936c3629783df1390428bf1a57bf2ea2e30d26efe54bd225e706\n" *
+        "aece99d7a362ebf68a2fde27d2d5ef2aad19b6d37f23999f7df3d4790ae4dade\n" *
+        "562710c4b9156d15ff4330b56d7713a9d3ea7f25eeaf353ade7a1bc4f541c2c4\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "77bf907e9559d21088bb174a314487384a170e28e8133974e55e23d306822ca6\n",
+        "10577651a2a8b02fa35d6aaed6e9cdceb26db2bf76746b4135401dd9fa4661d5\n" *
+        "cbe50f347a190ab5ea4ecddac336081b7b50c6f0f0f9790f35f5c9392dab1512\n" *
+        "11e867f361092a861511126014df02dc0b674457e060aec9768db41bfdb99628\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "4c3b332f405a2bb0b2b0b90d76daf0f7a9f51cc6405562cb6a8d944f1fe1145a\n",
+        "532931bf9fab64b045404c3ef1f6098c239a57120dc6a868c387aff2460d0353\n" *
+        "dce79c716768428aebedd0b7c54957fa1475818b5c9fd69f7f4909da9f59ff0e\n" *
+        "e00e3d29e4950c977ec3416ba648c6d102297fcbf3a73a7cc4166aa5c80006f0\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "bd55313826e4c21db224029054b0d4a80ec83837e43d0e1623cb3cd82e23d000\n",
+        "0150b3380992a8ac69f31a7d78de314e021b5d2d5db8e2128deda8b37fedcfde\n" *
+        "84a7367be62b77791c9eb16e08af1f30657831d85de2e1bed3bcdc607a6e4d5a\n" *
+        "7f91f8f21e85f8b20d6483974f8d71d190ab3c0266ebbb9a3f2329ebfc9d27ad\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "7ea6577e2649aef55e4f0f2004c449975a1d12c99b2d8d42b8e949eed1be7dbb\n",
+        "f4dd4421a4830b4d5f4bfb3894b747d07a25c5436c9db4147dda7f7b1cc4ae20\n" *
+        "7a3b49cec8da0af1b878e93e565e01b91f5eb1c128b32aaf4c5969c941c47cae\n" *
+        "95839babda634accf94065d296d829d7ff76b9f8d2f8525e2bff817e49905a60\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "b98d61d433b0fcaaecb2240692b8afd51d7432a842658e40db1bccc8156fd5d0\n",
+        "9c4268aac75479a264b4b293c828bf3f23823326c173ed87ca63130e406ae5ec\n" *
+        "9d75aa95ddb63425b498f0b15694c577b92b1970764472fa75f6e1c8ad59a70b\n" *
+        "224002ef9b1c6f0ceeab347799a83bbc95851a5a2e9d413867e78e902578f27c\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "712cc962085c05b1db1eeac06aaef89cacf57cef2767ae9384c696cc95d137a8\n",
+        "803205f7b32b7aadcf4955a2f934a065060da645a45c817f26c90025f8e4a978\n" *
+        "b397803296869f4d6c5c96dc32004292a8397c1fb0f89462469a75c823f1eaee\n" *
+        "c66dd0eef54751aa241772151ab5697a655a64ec13fa353ae0a2b0947cba1630\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "e99a92110e9f08ac0a6616103b22bbf86e1fc446336e2999a8f5c9c3056ad61a\n",
+        "41d7e4e8d2198d42c00a753db0f11dde0d2288ce0d148a83e9e774eafbf17584\n" *
+        "2775d5486da5a2ad4cd87583fb280a1e5729f635d726c5c9481e22c978e9710f\n" *
+        "ae590e1336e59edf432f8a8ab898400fa89be2b5458a8c43ae9d6788e9412777\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "51cf2434e0c42aca598a021f5afc411e408fe09f20a96baa244514442aa6a49c\n",
+        "3b71375a64a94627d03b257d2d2538474ac0263c0a5e6872d848ce6889092e15\n" *
+        "4030a764ec4b7cd138ca28b5991bdd9af5abf091fed5290df8e6b05353969a0b\n" *
+        "181573f3ce7c47199a61fa4017d4fdd3519d3d315959ce2275e9af98da15b88f\n" *
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n" *
+        "dd42cbfa98fce014afc2521287273eff9f600fc646af6a34a41127010e287630\n"
+    ]
+
+    const trie_count = [
+        # test_trie_blake2_256_root_generated, the empty trie has the hash of its empty node as root
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314",
+        "aa9c2e4e83e995321bf2104021fdc09d74bce21cd09244f8650f5de6bed9b4b9",
+        "d390f8a92bc7e21a367add562fb1839b156c2ffe964f51edad171220f591faef",
+        "469586b0acb784929201c6f59825922de500ea12ae51d7569340bbb08bed5d52",
+        "2f3daefbbe6b34dfc0ad601eaa58bedf0fe7f9bb47dbe6dddcda3d9cc12e0dde",
+        "fce3235d8e8ef8e77d5926028e897b520754973d12a8903a846d34aa3979c6e2",
+        # test_trie_blake2_256_ordered_root_generated
+        "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314",
+        "229b22d3e78cd5dfc5cc3ed87eec43f69c969e1df121c6fc5c7065665378a130",
+        "17cfddc40b484c23cfe66800cf70754b1fb73ec7b3041eb4656d4bef1d1becdb",
+        "8753dc4664dd7c244c2e05d6ffc5299e7126d7f8f36f22819b79142078da396d",
+        "8433ea5fe1864a0ece6408682c07e089dce6ec2ce737decb1150bcb373610f52",
+        "b64b1ea2558e6188cbf1c45fe2bc46c4e2bf3aa8a415b52c3610303fdab823f7"
+    ]
+
+    const print_utf8 = [
+        # test_misc_print_utf8, invalid UTF-8 is not printed
+        "print: Hello",
+        "(nothing printed)",
+        "(nothing printed)",
+        "(nothing printed)",
+        "(nothing printed)",
+        "(nothing printed)",
+        "(nothing printed)",
+        "(nothing printed)"
+    ]
+
+    const level_target_message = [
+        # test_logging_log, invalid UTF-8 is not logged
+        "log: ERROR runtime: Hello",
+        "log: WARN runtime: Hello",
+        "log: INFO runtime: Hello",
+        "log: DEBUG runtime: Hello",
+        "log: TRACE runtime: Hello",
+        "log: INFO pallet_balances: Hello World",
+        "log: INFO frame::executive: Hello World",
+        "log: INFO pället: Hello World",
+        "log: INFO runtime: héllo",
+        "log: INFO runtime: 🚀",
+        "(nothing logged)"
+    ]
+
+    const print_hex = [
+        # test_misc_print_hex
+        "print: 00",
+        "print: deadbeef",
+        "print: deadbeef",
+        "print: 48656c6c6f"
+    ]
+
+    const print_num = [
+        # test_misc_print_num
+        "print: 0",
+        "print: 42",
+        "print: 4294967296",
+        "print: 18446744073709551615"
+    ]
+
+    const offchain_http = [
+        # test_offchain_http
+        "status: 200\nheader: x-mock: hello\nbody: Hello World",
+        "status: 200\nheader: x-mock: echo\nheader: x-mock-test: polkadot\nbody: ping",
+        "status: 200\nheader: x-mock: echo\nheader: x-mock-test: polkadot\nbody: Polkadot Host Test Suite",
+        "status: 404\nheader: x-mock: missing\nbody: Not found",
+        "status: deadline reached"
+    ]
+
+    const offchain_http_deadlines = [
+        # test_offchain_http_deadlines
+        "wait /hello: finished 200\n" *
+        "wait /slow: deadline reached\n" *
+        "wait invalid: invalid\n" *
+        "wait /missing: finished 404\n" *
+        "/hello: write invalid, body Hello World, then invalid\n" *
+        "/slow: write invalid, body deadline reached\n" *
+        "invalid: write invalid, body invalid\n" *
+        "/missing: write invalid, body Not found, then invalid",
+        "wait /slow: deadline reached\n" *
+        "wait /hello: finished 200\n" *
+        "/slow: write invalid, body deadline reached\n" *
+        "/hello: write invalid, body Hello World, then invalid"
+    ]
+
+    const wasm_memory_probes = [
+        # test_wasm_probe, memory can be imported or exported (as `memory`), but
+        # has to be able to hold the heap pages
+        "accepted",
+        "accepted",
+        "rejected",
+        "rejected",
+        "accepted",
+        "accepted",
+        "rejected",
+        "rejected",
+        "rejected"
+    ]
+
+    const wasm_shape_probes = [
+        # test_wasm_probe, hosts only provide memory and functions as imports
+        "accepted",
+        "accepted",
+        "rejected",
+        "rejected"
+    ]
+
+    const block_length = [
+        # test_block_length_accounting, extrinsics exceeding the block length
+        # are rejected without being included, even as first extrinsic
+        "initialized: extrinsics=0, length=0\n" *
+        "1. applied: extrinsics=1, length=1048686\n" *
+        "2. applied: extrinsics=2, length=2097372\n" *
+        "3. applied: extrinsics=3, length=3146058\n" *
+        "4. exhausts resources: extrinsics=3, length=3146058",
+        "initialized: extrinsics=0, length=0\n" *
+        "1. exhausts resources: extrinsics=0, length=0",
+        "initialized: extrinsics=0, length=0\n" *
+        "1. applied: extrinsics=1, length=3932160\n" *
+        "2. exhausts resources: extrinsics=1, length=3932160",
+        "initialized: extrinsics=0, length=0\n" *
+        "1. exhausts resources: extrinsics=0, length=0",
+        "initialized: extrinsics=0, length=0\n" *
+        "1. applied: extrinsics=1, length=3932055\n" *
+        "2. applied: extrinsics=2, length=3932160\n" *
+        "3. exhausts resources: extrinsics=2, length=3932160",
+    ]
+
+    const tester_api_versions = [
+        # test_tester_api_version, runtimes declaring an older version than
+        # required must not be called
+        "declared: 2\ncall: 1",
+        "declared: 2\ncall: 1",
+        "declared: 2\ncall: unsupported"
+    ]
+
+    const offchain_validator = [
+        # ext_offchain_is_validator_version_1, the validator role is independent
+        # of the offchain capabilities
+        "1",
+        "1",
+        "0"
+    ]
+
+    const offchain_context = [
+        # test_offchain_capabilities
+        "is_validator: 1\nrandom_seed: ok\ntimestamp: ok",
+        "is_validator: 1\nrandom_seed: error\ntimestamp: error",
+        "is_validator: error\nrandom_seed: error\ntimestamp: error"
+    ]
+
     const key_value_key_value_key_value = [
         "1192e3ed48d28fba2eeae885fa367c535eca6a149eafad982265586902783d4f",
         "92aafcf31cc2012d7467fa96b47caa389762ef02e97c27c30484a7ffd4b3780e",
@@ -7,6 +7,9 @@ using .AdapterFixture
 
 tests = AdapterFixture.Builder("Host API", `host-api`)
 
+describe!(tests, "Host functions called from the runtime through the wasm blob",
+          "Polkadot Host Specification, Appendix: Host API")
+
 HOSTAPI_FIXTURE_DATASETS = [
     [
         HostApiFunctions.none,
@@ -18,6 +21,18 @@ HOSTAPI_FIXTURE_DATASETS = [
             HostApiInputs.value_1,
         ],
         HostApiOutputs.value .* "\n",
+    ],[
+        HostApiFunctions.byte_count,
+        [
+            HostApiInputs.byte_count,
+        ],
+        HostApiOutputs.byte_count .* "\n",
+    ],[
+        HostApiFunctions.large_value,
+        [
+            HostApiInputs.large_value,
+        ],
+        HostApiOutputs.large_value .* "\n",
     ],[
         HostApiFunctions.child_key_value,
         [
@@ -88,14 +103,248 @@ HOSTAPI_FIXTURE_DATASETS = [
             HostApiInputs.seed_1,
             HostApiInputs.seed_2,
         ],
-        nothing,
+        # Order of returned keys is not specified
+        pattern("1. Public key: {len=64}\n2. Public key: {len=64}\n"),
     ],[
         HostApiFunctions.seed_msg,
         [
             HostApiInputs.seed_1,
             HostApiInputs.value_1[1:6],
         ],
+        # Signatures of sr25519 are randomized
+        [
+            pattern("Message: $msg\nPublic key: {len=64}\nSignature: {len=128}\n" *
+                    (occursin("verify", func) ? "GOOD SIGNATURE\n" : ""))
+            for func in HostApiFunctions.seed_msg
+            for (_, msg) in flatzip(HostApiInputs.seed_1, HostApiInputs.value_1[1:6])
+        ],
+    ],[
+        HostApiFunctions.key_count,
+        [
+            HostApiInputs.key_count,
+        ],
+        HostApiOutputs.key_count .* "\n",
+    ],[
+        HostApiFunctions.seed_list,
+        [
+            HostApiInputs.seed_list,
+        ],
+        HostApiOutputs.seed_list .* "\n",
+    ],[
+        HostApiFunctions.ed25519_encodings,
+        [
+            HostApiInputs.ed25519_encodings,
+        ],
+        HostApiOutputs.ed25519_encodings .* "\n",
+    ],[
+        HostApiFunctions.secp256k1_encodings,
+        [
+            HostApiInputs.secp256k1_encodings,
+        ],
+        pattern.(HostApiOutputs.secp256k1_encodings .* "\n"),
+    ],[
+        HostApiFunctions.seed_count,
+        [
+            HostApiInputs.seed_1,
+            HostApiInputs.batch_count,
+        ],
+        HostApiOutputs.seed_count,
+    ],[
+        HostApiFunctions.proof_key_value_key_value,
+        [
+            HostApiInputs.key_value_1,
+            HostApiInputs.key_value_2,
+        ],
+        HostApiOutputs.proof_key_value_key_value,
+    ],[
+        HostApiFunctions.trie_count,
+        [
+            HostApiInputs.trie_count,
+        ],
+        HostApiOutputs.trie_count .* "\n",
+    ],[
+        HostApiFunctions.child_key_value_key_value_root,
+        [
+            HostApiInputs.child_child,
+            HostApiInputs.key_value_1,
+            HostApiInputs.key_value_2,
+        ],
+        HostApiOutputs.child_key_value_key_value_root,
+    ],[
+        HostApiFunctions.key_value_key_value_roots,
+        [
+            HostApiInputs.key_value_1,
+            HostApiInputs.key_value_2,
+        ],
+        HostApiOutputs.key_value_key_value_roots,
+    ],[
+        HostApiFunctions.allocator_none,
         nothing,
+        "ok\n",
+    ],[
+        HostApiFunctions.count_size,
+        [
+            HostApiInputs.count_size,
+        ],
+        "ok\n",
+    ],[
+        HostApiFunctions.size,
+        [
+            HostApiInputs.size,
+        ],
+        # Running out of memory and freeing twice both have to trap
+        "trap\n",
+    ],[
+        HostApiFunctions.transaction_depth,
+        [
+            HostApiInputs.transaction_depth,
+        ],
+        "rolled back: none\ncommitted: nested " .* first.(HostApiInputs.transaction_depth) .* "\n",
+    ],[
+        HostApiFunctions.transaction_unbalanced,
+        [
+            HostApiInputs.transaction_unbalanced_depth,
+        ],
+        # Closing a transaction which was never started has to trap
+        "trap\n",
+    ],[
+        HostApiFunctions.append_key_value_value,
+        [
+            HostApiInputs.key_value_1,
+            HostApiInputs.value_2,
+        ],
+        # Values without valid length prefix are replaced by the appended item
+        [
+            "empty: $value\n" *
+            "existing: $value;$new\n" *
+            "truncated: $new\n" *
+            "non-canonical: $new\n"
+            for (_, value, new) in flatzip(HostApiInputs.key_value_1, HostApiInputs.value_2)
+        ],
+    ],[
+        HostApiFunctions.overlay_key_value_value,
+        [
+            HostApiInputs.key_value_1,
+            HostApiInputs.value_2,
+        ],
+        # Writes are visible right away, the same for all previous states of the key
+        [
+            "unset: none;$new;none;$value\n" *
+            "prior call: $value;$new;none;$value\n" *
+            "backend: $value;$new;none;$value\n" *
+            "next call: $value\n" *
+            "cleared in prior call: none\n" *
+            "committed: none\n"
+            for (_, value, new) in flatzip(HostApiInputs.key_value_1, HostApiInputs.value_2)
+        ],
+    ],[
+        HostApiFunctions.transaction_key_value_value,
+        [
+            HostApiInputs.key_value_1,
+            HostApiInputs.value_2,
+        ],
+        # Rolling back restores the prior value, committing keeps the new one
+        [
+            "overwritten: $new, rolled back: $value\n" *
+            "cleared: none, rolled back: $value\n" *
+            "overwritten: $new, committed: $new\n"
+            for (_, value, new) in flatzip(HostApiInputs.key_value_1, HostApiInputs.value_2)
+        ],
+    ],[
+        HostApiFunctions.utf8_encodings,
+        [
+            HostApiInputs.utf8_encodings,
+        ],
+        # Invalid UTF-8 must not cause a trap
+        "ok\n",
+    ],[
+        HostApiFunctions.level_target_utf8_encodings,
+        [
+            HostApiInputs.level_target_utf8_encodings,
+        ],
+        "ok\n",
+    ],[
+        HostApiFunctions.level_target_message,
+        [
+            HostApiInputs.level_target_message,
+        ],
+        HostApiOutputs.level_target_message .* "\n",
+    ],[
+        HostApiFunctions.print_utf8,
+        [
+            HostApiInputs.utf8_encodings,
+        ],
+        HostApiOutputs.print_utf8 .* "\n",
+    ],[
+        HostApiFunctions.print_hex,
+        [
+            HostApiInputs.hex_data,
+        ],
+        HostApiOutputs.print_hex .* "\n",
+    ],[
+        HostApiFunctions.print_num,
+        [
+            HostApiInputs.num_u64,
+        ],
+        HostApiOutputs.print_num .* "\n",
+    ],[
+        HostApiFunctions.runtime_version,
+        [
+            HostApiInputs.wasm_blobs,
+        ],
+        # Neither blob provides a version, which must not trap
+        "none\n",
+    ],[
+        HostApiFunctions.wasm_probe,
+        [
+            HostApiInputs.wasm_memory_probes,
+        ],
+        HostApiOutputs.wasm_memory_probes .* "\n",
+    ],[
+        HostApiFunctions.wasm_probe,
+        [
+            HostApiInputs.wasm_shape_probes,
+        ],
+        HostApiOutputs.wasm_shape_probes .* "\n",
+    ],[
+        HostApiFunctions.tester_api_version,
+        [
+            HostApiInputs.tester_api_versions,
+        ],
+        HostApiOutputs.tester_api_versions .* "\n",
+    ],[
+        HostApiFunctions.block_length,
+        [
+            HostApiInputs.block_length,
+        ],
+        HostApiOutputs.block_length .* "\n",
+    ],[
+        HostApiFunctions.offchain_validator,
+        [
+            HostApiInputs.offchain_validator,
+        ],
+        HostApiOutputs.offchain_validator .* "\n",
+    ],[
+        HostApiFunctions.offchain_context,
+        [
+            HostApiInputs.offchain_context,
+        ],
+        HostApiOutputs.offchain_context .* "\n",
+    ],[
+        HostApiFunctions.offchain_random_seed,
+        nothing,
+        # Seed and timestamp differ between hosts and runs
+        pattern("random_seed: {len=64}\n"),
+    ],[
+        HostApiFunctions.offchain_timestamp,
+        nothing,
+        pattern("timestamp: {regex=[0-9]+}\n"),
+    ],[
+        HostApiFunctions.offchain_deadline,
+        [
+            HostApiInputs.offchain_deadline,
+        ],
+        "deadline reached\n",
     ],[
         HostApiFunctions.key_value_key_value_key_value,
         [
@@ -112,6 +361,12 @@ HOSTAPI_FIXTURE_DATASETS = [
             HostApiInputs.value_3,
         ],
         HostApiOutputs.value_value_value .* "\n",
+    ],[
+        HostApiFunctions.prefix_key_value_key_value_root,
+        [
+            HostApiInputs.prefix_key_value_key_value,
+        ],
+        HostApiOutputs.prefix_key_value_key_value_root,
     ]
 ]
 
@@ -129,4 +384,459 @@ for (func, input, output) in HOSTAPI_FIXTURE_DATASETS
     end
 end
 
+# Datasets without hardcoded outputs, expected results are provided by reference
+HOSTAPI_REFERENCE_DATASETS = [
+    [
+        HostApiFunctions.offchain_key_value,
+        [
+            HostApiInputs.key_value_1,
+        ],
+    ],[
+        HostApiFunctions.offchain_key_value_value,
+        [
+            HostApiInputs.key_value_1,
+            HostApiInputs.value_2,
+        ],
+    ],[
+        HostApiFunctions.child_key_value_root,
+        [
+            HostApiInputs.child_child,
+            HostApiInputs.key_value_1,
+        ],
+    ],[
+        HostApiFunctions.weight,
+        [
+            HostApiInputs.weight,
+        ],
+    ],[
+        HostApiFunctions.block_header,
+        [
+            HostApiInputs.block_header,
+        ],
+    ],[
+        HostApiFunctions.transactional,
+        [
+            HostApiInputs.transactional_depth,
+        ],
+    ],[
+        HostApiFunctions.allocator_sizes,
+        [
+            HostApiInputs.allocator_sizes,
+        ],
+    ]
+]
+
+for (func, input) in HOSTAPI_REFERENCE_DATASETS
+    sub!(tests) do t
+        arg!(t, `--function`)
+        foreach!(t, func)
+
+        arg!(t, `--input`)
+        foreach!(t, commajoin(flatzip(input...)))
+
+        commit!(t)
+        prepare!(t)
+    end
+end
+
+# Storage root of initial state declared inline by each test vector, top
+# storage and child storages as hex encoded key value maps.
+HOSTAPI_INLINE_STATES = [
+    [
+        # Boolean => Inverse
+        Dict("426f6f6c65616e" => "496e7665727365"),
+        Dict(),
+    ],[
+        # Boolean => Inverse, Horizontal => portal, static => (empty)
+        Dict("426f6f6c65616e" => "496e7665727365",
+             "486f72697a6f6e74616c" => "706f7274616c",
+             "737461746963" => ""),
+        Dict(),
+    ],[
+        # Boolean => Inverse, child1: { Horizontal => portal }
+        Dict("426f6f6c65616e" => "496e7665727365"),
+        Dict("6368696c6431" => Dict("486f72697a6f6e74616c" => "706f7274616c")),
+    ]
+]
+
+for (top, children) in HOSTAPI_INLINE_STATES
+    sub!(tests) do t
+        arg!(t, `--function test_storage_init`)
+        state!(t, top, children)
+
+        commit!(t)
+        prepare!(t)
+    end
+end
+
+# Iteration order of next key over inline state: keys sorted bytewise (unsigned),
+# keys being prefixes of each other and keys around the prefix of child storage
+# roots in the top storage, which does not contain the entries of child storages.
+# Neither does it contain the root of child1 under :child_storage:default:child1,
+# as child roots are only placed in the top trie when computing its root, while
+# the well known keys :code and :heappages of the initial state are iterated.
+HOSTAPI_NEXT_KEY_TOP = Dict(
+    "00" => "01", "0000" => "01", "0001" => "01", "00ff" => "01", "01" => "01",
+    "7f" => "01", "80" => "01", "ff" => "01", "ffff" => "01",
+    # :child_storage and :child_storage;
+    "3a6368696c645f73746f72616765" => "01", "3a6368696c645f73746f726167653b" => "01",
+)
+HOSTAPI_NEXT_KEY_CHILD = Dict(
+    # child1
+    "6368696c6431" => Dict("0000" => "01", "0002" => "01", "7f00" => "01", "ff" => "01"),
+)
+
+sub!(tests) do t
+    arg!(t, `--function test_storage_next_key_order --input child1`)
+    state!(t, HOSTAPI_NEXT_KEY_TOP, HOSTAPI_NEXT_KEY_CHILD)
+
+    commit!(t, """
+        top: 00
+        top: 0000
+        top: 0001
+        top: 00ff
+        top: 01
+        top: 3a6368696c645f73746f72616765
+        top: 3a6368696c645f73746f726167653b
+        top: 3a636f6465
+        top: 3a686561707061676573
+        top: 7f
+        top: 80
+        top: ff
+        top: ffff
+        child: 0000
+        child: 0002
+        child: 7f00
+        child: ff
+        """)
+end
+
+# Partial reads of key => 0102030405 from inline state, at offsets (before,
+# at and after the end of the value) into buffers shorter, as long as and longer
+# than the value: Hosts report the length of the value remaining at the offset
+# and must only write that much of it into the buffer, leaving the rest of it
+# untouched (0xff). Neither extensions nor prefixes of the key exist.
+HOSTAPI_READ_PARTIAL_STATE = Dict("6b6579" => "0102030405")
+
+HOSTAPI_READ_PARTIAL = [
+    # Buffers from the start of the value
+    "key,0:5;0:3;0:8;0:0" => """
+        exists: 1
+        read 5 at 0: remaining 5, buffer 0102030405
+        read 3 at 0: remaining 5, buffer 010203
+        read 8 at 0: remaining 5, buffer 0102030405ffffff
+        read 0 at 0: remaining 5, buffer empty
+        """,
+    # Buffers at non-zero offsets
+    "key,2:2;2:3;2:8;4:1" => """
+        exists: 1
+        read 2 at 2: remaining 3, buffer 0304
+        read 3 at 2: remaining 3, buffer 030405
+        read 8 at 2: remaining 3, buffer 030405ffffffffff
+        read 1 at 4: remaining 1, buffer 05
+        """,
+    # Offsets at and beyond the end of the value
+    "key,5:4;6:4;4294967295:2" => """
+        exists: 1
+        read 4 at 5: remaining 0, buffer ffffffff
+        read 4 at 6: remaining 0, buffer ffffffff
+        read 2 at 4294967295: remaining 0, buffer ffff
+        """,
+    # Missing keys, buffers are left untouched
+    "ke,0:2;1:2" => """
+        exists: 0
+        read 2 at 0: remaining none, buffer ffff
+        read 2 at 1: remaining none, buffer ffff
+        """,
+    "keys,0:2" => """
+        exists: 0
+        read 2 at 0: remaining none, buffer ffff
+        """,
+]
+
+sub!(tests) do t
+    arg!(t, `--function test_storage_exists_read_partial`)
+    state!(t, HOSTAPI_READ_PARTIAL_STATE)
+
+    arg!(t, `--input`)
+    foreach!(t, first.(HOSTAPI_READ_PARTIAL))
+
+    commit!(t, last.(HOSTAPI_READ_PARTIAL))
+end
+
+# Prints of long data (count of "a" and hex encoded tail) as UTF-8 or hex: Hosts
+# must not trap on any length and print valid UTF-8 completely, but silently
+# ignore data which is invalid UTF-8 anywhere, instead of sanitizing it. Hex of
+# long data may be shortened for display (start ... end).
+HOSTAPI_PRINT_LONG = [
+    "utf8,0,"             => "ok\nprint: 0 bytes, complete\n",
+    "utf8,1048576,"       => "ok\nprint: 1048576 bytes, complete\n",
+    "utf8,1048576,e282ac" => "ok\nprint: 1048579 bytes, complete\n",
+    "utf8,1048576,e282"   => "ok\n(nothing printed)\n",
+    "utf8,1048576,80"     => "ok\n(nothing printed)\n",
+    "utf8,1048576,ff61"   => "ok\n(nothing printed)\n",
+    "hex,0,"              => "ok\nprint: 0 bytes, complete\n",
+    "hex,512,"            => "ok\nprint: 1024 bytes, complete\n",
+    "hex,1048576,"        => pattern("ok\nprint: {any-of=2097152 bytes, complete|truncated}\n"),
+    "hex,1048576,ff"      => pattern("ok\nprint: {any-of=2097154 bytes, complete|truncated}\n"),
+]
+
+sub!(tests) do t
+    arg!(t, `--function test_misc_print_long --input`)
+    foreach!(t, first.(HOSTAPI_PRINT_LONG))
+
+    commit!(t, last.(HOSTAPI_PRINT_LONG))
+end
+
+# Storage keys and trie keys of increasing length (count of "a") with a short
+# value: The spec defines no maximum key length, so hosts have to store keys of
+# any length as is instead of rejecting or truncating them. Partial keys in
+# trie node headers are limited to 65535 nibbles though, so the spec defines no
+# encoding (and hence no root) of tries with longer partial keys. Their roots
+# are only checked to be reported, whether a proof can be generated for them is
+# left open. In the storage trie the first nibble of the key selects a child of
+# the root branch (next to the well known keys), so only the 1 MiB key exceeds
+# the bound, while the single pair trie has the whole key as partial key of its
+# leaf and already exceeds it at 32768 bytes.
+HOSTAPI_STORAGE_LONG_KEY = [
+    "32,value"      => "set: ok\nget: value\nstored key: 32 bytes\n" *
+                       "root: ef7bc511e4de5e66f27bf699d2a49db519e2a53bba82c76eb2022eadb5b996cc\n",
+    "32767,value"   => "set: ok\nget: value\nstored key: 32767 bytes\n" *
+                       "root: 0521baaf660070b8e97e23f800281ce3e525457d27bdec0280b56e2760e4e3ec\n",
+    "32768,value"   => "set: ok\nget: value\nstored key: 32768 bytes\n" *
+                       "root: e6b9db83713b1cead124b4e270f6f17de5974f0c09a432f0749a01c9ede47b33\n",
+    "1048576,value" => pattern("set: ok\nget: value\nstored key: 1048576 bytes\nroot: {len=64}\n"),
+]
+
+sub!(tests) do t
+    arg!(t, `--function test_storage_long_key --input`)
+    foreach!(t, first.(HOSTAPI_STORAGE_LONG_KEY))
+
+    commit!(t, last.(HOSTAPI_STORAGE_LONG_KEY))
+end
+
+HOSTAPI_TRIE_LONG_KEY = [
+    "32,value"      => "root: 66a89d0386bc0602114094b458e471c67d4457b941b3ddfd91e5febcfac43e4d\nproof: 1\n",
+    "32767,value"   => "root: df9eac9387758ddc21577b05f6b50fd4203107064d8409388cc277ba3b4fb006\nproof: 1\n",
+    "32768,value"   => pattern("root: {len=64}\nproof: {any-of=1|not generated}\n"),
+    "1048576,value" => pattern("root: {len=64}\nproof: {any-of=1|not generated}\n"),
+]
+
+sub!(tests) do t
+    arg!(t, `--function test_trie_long_key --input`)
+    foreach!(t, first.(HOSTAPI_TRIE_LONG_KEY))
+
+    commit!(t, last.(HOSTAPI_TRIE_LONG_KEY))
+end
+
+# Clearing prefix "ab" with limits (semicolon separated) from inline state of
+# a => 1, ab => 1, ab1 => 1, ab2 => 1, ab3 => 1, abc => 1, b => 1: Every call
+# returns the count of removed keys and if some are remaining, the limit splits
+# the prefix in lexicographic order of the keys.
+HOSTAPI_CLEAR_PREFIX_STATE = Dict(
+    "61" => "01", "6162" => "01", "616231" => "01", "616232" => "01",
+    "616233" => "01", "616263" => "01", "62" => "01",
+)
+
+HOSTAPI_CLEAR_PREFIX_LIMITS = [
+    # Prefix split mid-iteration
+    "2;2;2" => """
+        limit 2: removed 2, some remaining, left: 61 616232 616233 616263 62
+        limit 2: removed 2, some remaining, left: 61 616263 62
+        limit 2: removed 1, all removed, left: 61 62
+        """,
+    # Limit of exactly the count of keys
+    "5" => """
+        limit 5: removed 5, all removed, left: 61 62
+        """,
+    # Limit of zero removes nothing, no limit all
+    "0;none" => """
+        limit 0: removed 0, some remaining, left: 61 6162 616231 616232 616233 616263 62
+        limit none: removed 5, all removed, left: 61 62
+        """,
+    # Limit above the count of keys, nothing left to be removed afterwards
+    "10;1" => """
+        limit 10: removed 5, all removed, left: 61 62
+        limit 1: removed 0, all removed, left: 61 62
+        """,
+]
+
+sub!(tests) do t
+    arg!(t, `--function test_storage_clear_prefix_limit_count`)
+    state!(t, HOSTAPI_CLEAR_PREFIX_STATE)
+
+    arg!(t, `--input`)
+    foreach!(t, "ab," .* first.(HOSTAPI_CLEAR_PREFIX_LIMITS))
+
+    commit!(t, last.(HOSTAPI_CLEAR_PREFIX_LIMITS))
+end
+
+# Host functions called with raw arguments, with the class of any trap (invalid
+# arguments are covered by the negative fixture). Parameters are either input
+# pointer-sizes (ptr), output pointer-sizes (out), pointers to data of fixed
+# size (fixed) or integers (int), the output is the expected result for
+# well-formed empty arguments.
+HOSTAPI_RAW_FUNCTIONS = [
+    ["ext_storage_set_version_1",          [:ptr, :ptr],       "ok\n"],
+    ["ext_storage_get_version_1",          [:ptr],             "ok\n"],
+    ["ext_storage_read_version_1",         [:ptr, :out, :int], "ok\n"],
+    ["ext_storage_clear_version_1",        [:ptr],             "ok\n"],
+    ["ext_storage_exists_version_1",       [:ptr],             "ok\nresult: 0\n"],
+    ["ext_storage_clear_prefix_version_1", [:ptr],             "ok\n"],
+    ["ext_storage_append_version_1",       [:ptr, :ptr],       "ok\n"],
+    ["ext_storage_next_key_version_1",     [:ptr],             "ok\n"],
+
+    ["ext_default_child_storage_set_version_1",    [:ptr, :ptr, :ptr],       "ok\n"],
+    ["ext_default_child_storage_get_version_1",    [:ptr, :ptr],             "ok\n"],
+    ["ext_default_child_storage_read_version_1",   [:ptr, :ptr, :out, :int], "ok\n"],
+    ["ext_default_child_storage_clear_version_1",  [:ptr, :ptr],             "ok\n"],
+    ["ext_default_child_storage_exists_version_1", [:ptr, :ptr],             "ok\nresult: 0\n"],
+
+    ["ext_hashing_keccak_256_version_1",   [:ptr],             "ok\n"],
+    ["ext_hashing_sha2_256_version_1",     [:ptr],             "ok\n"],
+    ["ext_hashing_blake2_128_version_1",   [:ptr],             "ok\n"],
+    ["ext_hashing_blake2_256_version_1",   [:ptr],             "ok\n"],
+    ["ext_hashing_twox_256_version_1",     [:ptr],             "ok\n"],
+    ["ext_hashing_twox_128_version_1",     [:ptr],             "ok\n"],
+    ["ext_hashing_twox_64_version_1",      [:ptr],             "ok\n"],
+
+    # Zeroed signature and key do not verify
+    ["ext_crypto_ed25519_verify_version_1", [:fixed, :ptr, :fixed], "ok\nresult: 0\n"],
+    ["ext_crypto_sr25519_verify_version_1", [:fixed, :ptr, :fixed], "ok\nresult: 0\n"],
+
+    # Empty input is not a valid encoding of the key value pairs
+    ["ext_trie_blake2_256_root_version_1",         [:ptr],     "trap: host error\n"],
+    ["ext_trie_blake2_256_ordered_root_version_1", [:ptr],     "trap: host error\n"],
+]
+
+"Well-formed raw argument for parameter kind"
+raw_default(kind) = kind in (:int, :fixed) ? "0" : "0:0"
+
+for (func, params, output) in HOSTAPI_RAW_FUNCTIONS
+    input = join(map(raw_default, params), ",")
+    sub!(tests) do t
+        arg!(t, `--raw --classify-trap --function $func --input $input`)
+        commit!(t, output)
+    end
+end
+
+# Raw calls with explicit arguments
+HOSTAPI_RAW_DATASETS = [
+    # Result buffer overlapping the input key
+    ["ext_storage_read_version_1", "0:4,0:4,0", "ok\n"],
+]
+
+for (func, input, output) in HOSTAPI_RAW_DATASETS
+    sub!(tests) do t
+        arg!(t, `--raw --classify-trap --function $func --input $input`)
+        commit!(t, output)
+    end
+end
+
 AdapterFixture.execute(tests)
+
+# Sandboxing host functions, as used by smart contracts
+sandbox = AdapterFixture.Builder("Host API Sandbox", `host-api`)
+
+describe!(sandbox, "Sandboxed execution of wasm code and its memory, including nested calls into the runtime",
+          "Polkadot Host Specification, Appendix: Host API, Sandboxing")
+
+require!(sandbox, "sandbox")
+
+# Datasets without hardcoded outputs, expected results are provided by reference
+HOSTAPI_SANDBOX_DATASETS = [
+    [
+        HostApiFunctions.sandbox_none,
+        nothing,
+    ],[
+        HostApiFunctions.sandbox_value,
+        [
+            HostApiInputs.value_1,
+        ],
+    ],[
+        HostApiFunctions.sandbox_key_value,
+        [
+            HostApiInputs.key_value_1,
+        ],
+    ]
+]
+
+for (func, input) in HOSTAPI_SANDBOX_DATASETS
+    sub!(sandbox) do t
+        arg!(t, `--function`)
+        foreach!(t, func)
+
+        if input != nothing
+            arg!(t, `--input`)
+            foreach!(t, commajoin(flatzip(input...)))
+        end
+
+        commit!(t)
+        prepare!(t)
+    end
+end
+
+AdapterFixture.execute(sandbox)
+
+
+# Keystore shared by concurrent runtime calls, skipped by implementations not
+# claiming a thread-safe keystore.
+keystore = AdapterFixture.Builder("Host API Keystore Concurrency", `host-api`)
+
+describe!(keystore, "Keys generated and used concurrently from several runtime instances sharing one keystore",
+          "Polkadot Host Specification, Appendix: Host API, Crypto")
+
+require!(keystore, "concurrent-keystore")
+
+sub!(keystore) do t
+    arg!(t, `--function`)
+    foreach!(t, HostApiFunctions.thread_count)
+
+    arg!(t, `--input`)
+    foreach!(t, commajoin(HostApiInputs.thread_count))
+
+    commit!(t, "64 keys, 64 valid signatures\n")
+end
+
+AdapterFixture.execute(keystore)
+
+
+# Offchain http requests to a local mock server, started for this testsuite
+http = AdapterFixture.Builder("Host API Offchain HTTP", `host-api`)
+
+describe!(http, "HTTP requests of offchain workers, checked against fixed responses and deadlines of a local mock server",
+          "Polkadot Host Specification, Appendix: Host API, Offchain")
+
+require!(http, "offchain-http")
+
+# Substrate performs requests with the http worker of sc-offchain, which its
+# host api can not be backed with outside of a node
+nonreference!(http, "recorded with the minimal http client of the substrate adapter")
+
+sub!(http) do t
+    arg!(t, `--function`)
+    foreach!(t, HostApiFunctions.offchain_http)
+
+    arg!(t, `--input`)
+    foreach!(t, commajoin(HostApiInputs.offchain_http))
+
+    commit!(t, HostApiOutputs.offchain_http .* "\n")
+end
+
+# Deadlines of requests waited for together, with bodies read after them
+sub!(http) do t
+    arg!(t, `--function`)
+    foreach!(t, HostApiFunctions.offchain_http_deadlines)
+
+    arg!(t, `--input`)
+    foreach!(t, commajoin(HostApiInputs.offchain_http_deadlines))
+
+    commit!(t, HostApiOutputs.offchain_http_deadlines .* "\n")
+end
+
+mock = HttpMock.start()
+try
+    AdapterFixture.execute(http)
+finally
+    HttpMock.stop(mock)
+end
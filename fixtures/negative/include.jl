@@ -0,0 +1,95 @@
+using .AdapterFixture
+
+
+# Host functions called through a generated wasm shim with raw arguments. Parameters
+# are either input pointer-sizes (ptr), output pointer-sizes (out), pointers to data
+# of fixed size (fixed) or integers (int).
+const FUNCTIONS = [
+  "ext_storage_set_version_1"                   => [:ptr, :ptr],
+  "ext_storage_get_version_1"                   => [:ptr],
+  "ext_storage_read_version_1"                  => [:ptr, :out, :int],
+  "ext_storage_clear_version_1"                 => [:ptr],
+  "ext_storage_exists_version_1"                => [:ptr],
+  "ext_storage_clear_prefix_version_1"          => [:ptr],
+  "ext_storage_append_version_1"                => [:ptr, :ptr],
+  "ext_storage_next_key_version_1"              => [:ptr],
+  "ext_default_child_storage_set_version_1"     => [:ptr, :ptr, :ptr],
+  "ext_default_child_storage_get_version_1"     => [:ptr, :ptr],
+  "ext_default_child_storage_read_version_1"    => [:ptr, :ptr, :out, :int],
+  "ext_default_child_storage_clear_version_1"   => [:ptr, :ptr],
+  "ext_default_child_storage_exists_version_1"  => [:ptr, :ptr],
+  "ext_hashing_keccak_256_version_1"            => [:ptr],
+  "ext_hashing_sha2_256_version_1"              => [:ptr],
+  "ext_hashing_blake2_128_version_1"            => [:ptr],
+  "ext_hashing_blake2_256_version_1"            => [:ptr],
+  "ext_hashing_twox_256_version_1"              => [:ptr],
+  "ext_hashing_twox_128_version_1"              => [:ptr],
+  "ext_hashing_twox_64_version_1"               => [:ptr],
+  "ext_crypto_ed25519_verify_version_1"         => [:fixed, :ptr, :fixed],
+  "ext_crypto_sr25519_verify_version_1"         => [:fixed, :ptr, :fixed],
+  "ext_trie_blake2_256_root_version_1"          => [:ptr],
+  "ext_trie_blake2_256_ordered_root_version_1"  => [:ptr],
+]
+
+"Well-formed raw argument for parameter kind"
+valid(kind) = kind == :int || kind == :fixed ? "0" : "0:0"
+
+# Pointer-sizes outside of the linear memory: null pointer with length exceeding
+# memory, range overflowing memory, beyond the end of the memory of any executor
+# and wrapping around the address space
+const INVALID_RANGES = ["0:4294967295", "4294967280:16", "2147483648:1", "4294967295:2"]
+
+# Pointers to fixed size data (e.g. signatures) beyond the end of memory and
+# wrapping around
+const INVALID_POINTERS = ["2147483648", "4294967295"]
+
+
+tests = AdapterFixture.Builder("Negative Host API Calls", `host-api --raw --classify-trap`)
+
+describe!(tests, "Out of bounds pointers and absurd lengths passed to host functions, which have to trap instead of accessing memory outside of the instance",
+          "Polkadot Host Specification, Appendix: Host API")
+
+# Every invalid pointer has to be rejected as out of bounds of the memory
+for (func, params) in FUNCTIONS
+  defaults = map(valid, params)
+
+  for (i, kind) in enumerate(params)
+    invalid = kind == :fixed ? INVALID_POINTERS : kind == :int ? [] : INVALID_RANGES
+
+    for arg in invalid
+      args = copy(defaults)
+      args[i] = arg
+
+      sub!(tests) do t
+        arg!(t, `--function $func --input $(join(args, ","))`)
+        commit!(t, "trap: out of bounds\n")
+      end
+    end
+  end
+end
+
+# Misaligned buffers and data at the end of the first page are valid and must
+# neither trap nor be read from another location
+const MISALIGNED = [
+  ["ext_storage_set_version_1",           "1:3,5:1",     "ok\n"],
+  ["ext_storage_read_version_1",          "3:5,9:7,1",   "ok\n"],
+  ["ext_storage_exists_version_1",        "65533:3",     "ok\nresult: 0\n"],
+  ["ext_hashing_blake2_256_version_1",    "1:31",        "ok\n"],
+  ["ext_hashing_twox_128_version_1",      "65535:1",     "ok\n"],
+  ["ext_crypto_ed25519_verify_version_1", "3,0:0,65501", "ok\nresult: 0\n"],
+]
+
+for (func, input, output) in MISALIGNED
+  sub!(tests) do t
+    arg!(t, `--function $func --input $input`)
+    commit!(t, output)
+  end
+end
+
+# Absurd sizes are rejected by the host functions themselves
+sub!(tests) do t
+  arg!(t, `--function ext_allocator_malloc_version_1 --input 4294967295`)
+  commit!(t, "trap: host error\n")
+end
+
+AdapterFixture.execute(tests)
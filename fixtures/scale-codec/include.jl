@@ -24,15 +24,502 @@ const TEST_DATA = [
       enough."""
 ]
 
+# Header digest items, hex encoded (last entries are expected to fail decoding)
+const DIGEST_ITEMS = [
+  # Other
+  "000c010203",
+  "0000",
+  # ChangesTrieRoot
+  "02" * "0123456789abcdef"^4,
+  # Consensus (GRANDPA and an unknown engine id)
+  "0446524e4b080100",
+  "04ffffffff10deadbeef",
+  # Seal (BABE, 64 byte signature)
+  "05424142450101" * "aa"^64,
+  # PreRuntime (BABE and an unknown engine id)
+  "06424142450c010203",
+  "065445535400",
+  # ChangesTrieSignal
+  "0700010400000002000000",
+  "070000",
+  # Invalid variant indexes
+  "0100",
+  "0300",
+  "0800",
+  # Truncated payload and trailing bytes
+  "05424142450101aaaa",
+  "000000",
+]
+
+
+# Block headers as comma separated fields: parent hash, number, state root,
+# extrinsics root and hex encoded digest items
+const HEADER_PARENT = "00"^32
+const HEADER_STATE = "0123456789abcdef"^4
+const HEADER_EXTRINSICS = "03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314"
+
+const HEADERS = [
+  # Block number compact encoding boundaries, no digest
+  join([HEADER_PARENT, "0", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "1", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "63", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "64", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "16383", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "16384", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "1073741823", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "1073741824", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join([HEADER_PARENT, "4294967295", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  # Distinct field values, to catch field ordering mistakes
+  join([HEADER_STATE, "42", HEADER_EXTRINSICS, HEADER_PARENT], ","),
+  # Digests with one and multiple items
+  join([HEADER_PARENT, "1", HEADER_STATE, HEADER_EXTRINSICS, "06424142450c010203"], ","),
+  join([HEADER_PARENT, "100", HEADER_STATE, HEADER_EXTRINSICS,
+        "06424142450c010203", "0446524e4b080100", "05424142450101" * "aa"^64], ","),
+]
+
+# Chains of headers separated by semicolons, each referencing the blake2-256
+# hash of its predecessor. Blocks after genesis are authored by BABE, i.e. carry
+# a pre-runtime digest of their slot (secondary plain, in slots 1 and 2) and end
+# with a seal, whose signature can not be verified without the authorities.
+const ANCESTRY_PRE_DIGESTS = ["06424142453402000000000100000000000000", "06424142453402000000000200000000000000"]
+const ANCESTRY_SEAL = "05424142450101" * "aa"^64
+
+const ANCESTRY_BLOCKS = [
+  join([HEADER_PARENT, "0", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join(["5524f2d9e37bdba43c4796fb5c23fc0c0f6b7bb7d687cdedd0b93b625b2cff18", "1", HEADER_STATE, HEADER_EXTRINSICS], ","),
+  join(["8867d2eaaae1c68f5d264877314c0604581147d3bc7627e41e41afc78dbf7981", "2", HEADER_STATE, HEADER_EXTRINSICS], ","),
+]
+
+const ANCESTRY_DIGEST_BLOCKS = [
+  ANCESTRY_BLOCKS[1],
+  join(["5524f2d9e37bdba43c4796fb5c23fc0c0f6b7bb7d687cdedd0b93b625b2cff18", "1", HEADER_STATE, HEADER_EXTRINSICS,
+        ANCESTRY_PRE_DIGESTS[1], ANCESTRY_SEAL], ","),
+  join(["e2499a895b08490d8dcd7ec38dfdb0ee920a752077691d855418619922b200e0", "2", HEADER_STATE, HEADER_EXTRINSICS,
+        ANCESTRY_PRE_DIGESTS[2], ANCESTRY_SEAL], ","),
+]
+
+# Chains with their expected verification result, as defined by the spec
+const HEADER_CHAINS = [
+  # Genesis only
+  ANCESTRY_BLOCKS[1]
+    => "head: 5524f2d9e37bdba43c4796fb5c23fc0c0f6b7bb7d687cdedd0b93b625b2cff18\nancestry: ok\n",
+  # Valid chain, digests are part of the hashed header
+  join(ANCESTRY_DIGEST_BLOCKS, ";")
+    => "head: 7ee647da09b4b3fb466e6a981bbe3f078411dfb4cd664d2818faf9cb02cc5189\nancestry: ok\n",
+  # Linked chain without any digests is not authored
+  join(ANCESTRY_BLOCKS, ";")
+    => "ancestry: missing pre-runtime digest at block 1\n",
+  # Missing block in between
+  join(ANCESTRY_BLOCKS[[1, 3]], ";")
+    => "ancestry: invalid parent hash at block 2\n",
+  # Correct parent hash, but block number not incremented by one
+  join([ANCESTRY_BLOCKS[1], join([split(ANCESTRY_BLOCKS[2], ",")[1], "2", HEADER_STATE, HEADER_EXTRINSICS], ",")], ";")
+    => "ancestry: invalid number at block 2\n",
+  # Seal stripped from block 1
+  join([ANCESTRY_DIGEST_BLOCKS[1], replace(ANCESTRY_DIGEST_BLOCKS[2], "," * ANCESTRY_SEAL => ""), ANCESTRY_DIGEST_BLOCKS[3]], ";")
+    => "ancestry: missing seal at block 1\n",
+  # Pre-runtime digest data which is no BABE pre-digest
+  join([ANCESTRY_DIGEST_BLOCKS[1], replace(ANCESTRY_DIGEST_BLOCKS[2], ANCESTRY_PRE_DIGESTS[1] => "06424142450c010203")], ";")
+    => "ancestry: invalid pre-runtime digest at block 1\n",
+  # Block 2 authored in the slot of its parent
+  join([ANCESTRY_DIGEST_BLOCKS[1:2]..., replace(ANCESTRY_DIGEST_BLOCKS[3], ANCESTRY_PRE_DIGESTS[2] => ANCESTRY_PRE_DIGESTS[1])], ";")
+    => "ancestry: slot not increasing at block 2\n",
+]
+
+# Justifications as comma separated engine id and justification pairs
+const JUSTIFICATIONS = [
+  # GRANDPA only
+  "46524e4b:0102030405",
+  # GRANDPA and BEEFY, in both insertion orders
+  "46524e4b:0102030405,42454546:aabbccdd",
+  "42454546:aabbccdd,46524e4b:0102030405",
+  # Empty justification data and an unknown engine id
+  "46524e4b:,ffffffff:00",
+  # Second entry for an engine already present is rejected
+  "46524e4b:01,42454546:02,46524e4b:03",
+]
+
+# Block bodies as semicolon separated encoded extrinsics (including their length
+# prefix), with the extrinsics root defined by the spec: the ordered trie root
+# of the encodings, keyed by the compact encoded index.
+const EXTRINSICS_ROOTS = [
+  # Empty body
+  ""
+    => "extrinsics: 0\nroot: 03170a2e7597b7b7e3d84c05391d139a62b157e78786d8c082f29dcf4c111314\n",
+  # Single unsigned extrinsic (timestamp inherent)
+  "280403000b00806e877401"
+    => "extrinsics: 1\nroot: be9328673aa9ab5b58e97c77f135d10e010a87ce92897c3c6122c63177781ff9\n",
+  # Extrinsic exceeding 32 bytes, i.e. referenced by hash in the trie
+  join(["280403000b00806e877401", "1004040000", "250384" * bytes2hex(UInt8.(0:199))], ";")
+    => "extrinsics: 3\nroot: ed1969e3c4e1003b577f0cfaccf12ac831fcd7d7be0e17bf98aaf43635f55fe0\n",
+  # Indices up to 63 are encoded in a single byte, 64 takes two bytes
+  join(["0c0400" * string(i, base=16, pad=2) for i in 0:63], ";")
+    => "extrinsics: 64\nroot: bd6c02c2a4a06fdac2e8e27505e5ae10c56337abfa2e6b2443cb89401d0517cf\n",
+  join(["0c0400" * string(i, base=16, pad=2) for i in 0:64], ";")
+    => "extrinsics: 65\nroot: e367e15fa2cc05aba974241ecb61e44640d30c53db3ea28d3fe8d2e847927ed0\n",
+]
+
+"Compact integer encoding mode boundaries, including all big integer mode lengths"
+const COMPACT_BOUNDARIES = [
+  UInt128(1) << 6,
+  UInt128(1) << 14,
+  UInt128(1) << 30,
+  (UInt128(1) << (8 * n) for n in 4:15)...
+]
+
+# Values right below, at and above each boundary, as well as the extremes
+const COMPACT_VALUES = unique(vcat(
+  ["0", string(typemax(UInt128))],
+  [string(v) for b in COMPACT_BOUNDARIES for v in (b - one(b), b, b + one(b))],
+))
+
+# Hex encoded compact integers with their expected decoding. Non-canonical
+# (i.e. not minimal) encodings have to be rejected.
+const COMPACT_ENCODINGS = [
+  "00"             => "decoded: 0\n",
+  "fc"             => "decoded: 63\n",
+  "0101"           => "decoded: 64\n",
+  "0100"           => "error\n",
+  "fd00"           => "error\n",
+  "02000100"       => "decoded: 16384\n",
+  "feff0000"       => "error\n",
+  "feffffff"       => "decoded: 1073741823\n",
+  "0300000040"     => "decoded: 1073741824\n",
+  "03ffffff3f"     => "error\n",
+  "03ffffffff"     => "decoded: 4294967295\n",
+  "070000000001"   => "decoded: 4294967296\n",
+  "07ffffffff00"   => "error\n",
+  "33" * "ff"^16   => "decoded: 340282366920938463463374607431768211455\n",
+  "37" * "ff"^17   => "error\n",
+  "03ffff"         => "error\n",
+  "0000"           => "error\n",
+]
+
+# Hex encoded Option<bool> values with their expected decoding
+const OPTION_BOOL_ENCODINGS = [
+  "00"   => "decoded: none\n",
+  "01"   => "decoded: true\n",
+  "02"   => "decoded: false\n",
+  "03"   => "error\n",
+  "ff"   => "error\n",
+  "0101" => "error\n",
+]
+
+# Hex encoded enum values with their expected decoding. The enum has the
+# variants First (index 0), Second(u8) (index 5) and Third(u32) (index 254).
+const SPARSE_ENUM_ENCODINGS = [
+  "00"           => "decoded: First\n",
+  "052a"         => "decoded: Second(42)\n",
+  "feffffffff"   => "decoded: Third(4294967295)\n",
+  "01"           => "error\n",
+  "04"           => "error\n",
+  "06"           => "error\n",
+  "fd"           => "error\n",
+  "ff"           => "error\n",
+  "05"           => "error\n",
+  "fe01000000ff" => "error\n",
+]
+
+# Per-things as comma separated parts and value to multiply with (out of
+# range parts are clamped)
+const PERBILL_VALUES = [
+  "0,1000",
+  "1,1000000000",
+  "333333333,3",
+  "500000000,3",
+  "999999999,1000000007",
+  "1000000000,18446744073709551615",
+  "1000000001,100",
+  "4294967295,100",
+]
+
+const PERCENT_VALUES = [
+  "0,100",
+  "1,100",
+  "50,3",
+  "99,101",
+  "100,255",
+  "101,7",
+  "255,7",
+]
+
+# Fixed point numbers (with 10^18 as one) as comma separated inner value and
+# integer to multiply with
+const FIXED_U128_VALUES = [
+  "0,5",
+  "1,1000000000000000000",
+  "500000000000000000,1",
+  "1000000000000000000,42",
+  "1500000000000000000,3",
+  "340282366920938463463374607431768211455,2",
+]
+
+# Mortal eras as comma separated period and current block (periods get
+# rounded to a power of two between 4 and 65536)
+const MORTAL_ERAS = [
+  "$period,$current" for period in [0, 1, 4, 5, 64, 100, 1024, 65536, 70000]
+                     for current in [0, 1, 42, 1000000, 4294967295]
+]
+
+# Eras as comma separated hex encoding and current block
+const ENCODED_ERAS = [
+  "00,0",
+  "00,1000000",
+  "0100,0",
+  "0100,1000000",
+  "1100,42",
+  "0e00,1000000",
+  "0f00,1000000",
+  "1f00,100",
+  "4100,42",
+  "0000,0",
+  "01,0",
+]
+
+# Hex encoded multi addresses of all variants
+const MULTI_ADDRESSES = [
+  # Id
+  "00d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+  # Index (compact encoded)
+  "0104",
+  "01feffffff",
+  "0103ffffffff",
+  # Raw
+  "0200",
+  "020c010203",
+  # Address32 and Address20
+  "03" * "0123456789abcdef"^4,
+  "04" * "0123456789abcdef0123456789abcdef01234567",
+  # Invalid variant index, truncated payload and non-canonical index
+  "05",
+  "04" * "0123456789abcdef0123456789abcdef012345",
+  "01fd00",
+]
+
+# Hex encoded multi signers of all variants
+const MULTI_SIGNERS = [
+  "0088dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee",
+  "01d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+  "02020a1091341fe5664bfa1782d5e04779689068c916b04cb365ec3153755684d9a1",
+  # Invalid variant index and truncated payload
+  "03d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d",
+  "01d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da2",
+]
+
+# Multi signatures as comma separated scheme, secret uri and message
+const MULTI_SIGNATURES = [
+  "$scheme,$suri,$message" for scheme in ["ed25519", "sr25519", "ecdsa"]
+                           for suri in ["//Alice", "//Bob"]
+                           for message in ["", "hello"]
+]
+
+# Signing payloads as comma separated hex encoded call and signed extensions
+const PAYLOAD_CALL = "00010c010203"
+const PAYLOAD_LARGE_CALL = "0001b104" * "ab"^300
+const PAYLOAD_GENESIS = "CheckGenesis=" * "0123456789abcdef"^4
+const PAYLOAD_MORTAL = "CheckMortality=1100:" * "fedcba9876543210"^4
+const PAYLOAD_IMMORTAL = "CheckMortality=00:" * "0123456789abcdef"^4
+
+const SIGNED_PAYLOADS = [
+  # Single extensions
+  join([PAYLOAD_CALL, "CheckSpecVersion=9050"], ","),
+  join([PAYLOAD_CALL, "CheckNonce=64"], ","),
+  join([PAYLOAD_CALL, "ChargeTransactionPayment=0"], ","),
+  join([PAYLOAD_CALL, "CheckMetadataHash="], ","),
+  join([PAYLOAD_CALL, "CheckMetadataHash=" * "00112233445566778899aabbccddeeff"^2], ","),
+  # Typical sets, extra and additional signed data in extension order
+  join([PAYLOAD_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS,
+        PAYLOAD_IMMORTAL, "CheckNonce=0", "CheckWeight", "ChargeTransactionPayment=0"], ","),
+  join([PAYLOAD_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS,
+        PAYLOAD_MORTAL, "CheckNonce=16384", "CheckWeight", "ChargeTransactionPayment=1000000000000"], ","),
+  join([PAYLOAD_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS,
+        PAYLOAD_MORTAL, "CheckNonce=1", "CheckWeight", "ChargeTransactionPayment=10",
+        "CheckMetadataHash=" * "00112233445566778899aabbccddeeff"^2], ","),
+  # Reordered extensions
+  join([PAYLOAD_CALL, "ChargeTransactionPayment=10", "CheckNonce=1", PAYLOAD_MORTAL,
+        PAYLOAD_GENESIS, "CheckTxVersion=7", "CheckSpecVersion=9050"], ","),
+  # Payloads exceeding 256 bytes are hashed
+  join([PAYLOAD_LARGE_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS,
+        PAYLOAD_IMMORTAL, "CheckNonce=0", "CheckWeight", "ChargeTransactionPayment=0"], ","),
+]
+
+# Signing payloads with their expected construction: hex encoded call, extra and
+# additional signed data in order of the extensions (as of polkadot), hashed with
+# blake2-256 if longer than 256 bytes
+payload_output(call, extra, additional, to_sign=call * extra * additional) =
+  "extra: $extra\nadditional: $additional\npayload: $(call * extra * additional)\nto_sign: $to_sign\n"
+
+const PAYLOAD_VERSIONS = "5a230000" * "07000000"
+const PAYLOAD_METADATA_HASH = "00112233445566778899aabbccddeeff"^2
+
+const SIGNED_PAYLOAD_ENCODINGS = [
+  # Immortal, without nonce, tip or metadata hash
+  join([PAYLOAD_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS, PAYLOAD_IMMORTAL,
+        "CheckNonce=0", "CheckWeight", "ChargeTransactionPayment=0", "CheckMetadataHash="], ",")
+    => payload_output("00010c010203", "00" * "00" * "00" * "00",
+                      PAYLOAD_VERSIONS * "0123456789abcdef"^4 * "0123456789abcdef"^4 * "00"),
+  # Mortal, with four byte compact nonce, big integer compact tip and metadata hash
+  join([PAYLOAD_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS, PAYLOAD_MORTAL,
+        "CheckNonce=16384", "CheckWeight", "ChargeTransactionPayment=1000000000000",
+        "CheckMetadataHash=" * PAYLOAD_METADATA_HASH], ",")
+    => payload_output("00010c010203", "1100" * "02000100" * "070010a5d4e8" * "01",
+                      PAYLOAD_VERSIONS * "0123456789abcdef"^4 * "fedcba9876543210"^4 * "01" * PAYLOAD_METADATA_HASH),
+  # Payload exceeding 256 bytes is hashed
+  join([PAYLOAD_LARGE_CALL, "CheckSpecVersion=9050", "CheckTxVersion=7", PAYLOAD_GENESIS, PAYLOAD_IMMORTAL,
+        "CheckNonce=1", "CheckWeight", "ChargeTransactionPayment=10", "CheckMetadataHash=" * PAYLOAD_METADATA_HASH], ",")
+    => payload_output("0001b104" * "ab"^300, "00" * "04" * "28" * "01",
+                      PAYLOAD_VERSIONS * "0123456789abcdef"^4 * "0123456789abcdef"^4 * "01" * PAYLOAD_METADATA_HASH,
+                      "3914775af407bcdffb736f0df897a86b301d79f5124ea9847e3c6575a4e9aeeb"),
+]
 
 tests = AdapterFixture.Builder("Scale Codec", `scale-codec`)
 
+describe!(tests, "SCALE encoding and decoding of primitive and runtime types",
+          "Polkadot Host Specification, Appendix: SCALE Codec")
+
 sub!(tests) do t
   arg!(t, `encode --input`)
   foreach!(t, TEST_DATA)
   commit!(t)
 end
 
+sub!(tests) do t
+  arg!(t, `digest-item --input`)
+  foreach!(t, DIGEST_ITEMS)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `header --input`)
+  foreach!(t, HEADERS)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `encode-compact --input`)
+  foreach!(t, COMPACT_VALUES)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `perbill --input`)
+  foreach!(t, PERBILL_VALUES)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `percent --input`)
+  foreach!(t, PERCENT_VALUES)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `fixed-u128 --input`)
+  foreach!(t, FIXED_U128_VALUES)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `mortal-era --input`)
+  foreach!(t, MORTAL_ERAS)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `decode-era --input`)
+  foreach!(t, ENCODED_ERAS)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `multi-address --input`)
+  foreach!(t, MULTI_ADDRESSES)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `multi-signer --input`)
+  foreach!(t, MULTI_SIGNERS)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `multi-signature --input`)
+  foreach!(t, MULTI_SIGNATURES)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `signed-payload --input`)
+  foreach!(t, SIGNED_PAYLOADS)
+  commit!(t)
+end
+
 prepare!(tests)
 
+# Added after preparing, as expected outputs are defined by the spec
+sub!(tests) do t
+  arg!(t, `decode-compact --input`)
+  foreach!(t, first.(COMPACT_ENCODINGS))
+  commit!(t, last.(COMPACT_ENCODINGS))
+end
+
+sub!(tests) do t
+  arg!(t, `option-bool --input`)
+  foreach!(t, first.(OPTION_BOOL_ENCODINGS))
+  commit!(t, last.(OPTION_BOOL_ENCODINGS))
+end
+
+sub!(tests) do t
+  arg!(t, `sparse-enum --input`)
+  foreach!(t, first.(SPARSE_ENUM_ENCODINGS))
+  commit!(t, last.(SPARSE_ENUM_ENCODINGS))
+end
+
+sub!(tests) do t
+  arg!(t, `extrinsics-root --input`)
+  foreach!(t, first.(EXTRINSICS_ROOTS))
+  commit!(t, last.(EXTRINSICS_ROOTS))
+end
+
+sub!(tests) do t
+  arg!(t, `signed-payload --input`)
+  foreach!(t, first.(SIGNED_PAYLOAD_ENCODINGS))
+  commit!(t, last.(SIGNED_PAYLOAD_ENCODINGS))
+end
+
 AdapterFixture.execute(tests)
+
+# Justifications are reported as canonical JSON, with entries keyed by engine id
+justifications = AdapterFixture.Builder("Scale Codec Justifications", `scale-codec`)
+
+describe!(justifications, "Encoding of justifications container and retrieval of its entries by consensus engine id",
+          "Polkadot Host Specification, Appendix: SCALE Codec")
+
+canonical!(justifications)
+
+sub!(justifications) do t
+  arg!(t, `justifications --input`)
+  foreach!(t, JUSTIFICATIONS)
+  commit!(t)
+end
+
+prepare!(justifications)
+
+AdapterFixture.execute(justifications)
+
+# Reduced verification of header chains for light and embedded implementations
+ancestry = AdapterFixture.Builder("Scale Codec Header Ancestry", `scale-codec`)
+
+describe!(ancestry, "Verification of header ancestry from headers and their digests only (parent hashes, numbers, BABE slots and seal placement), without any state access",
+          "Polkadot Host Specification, Block Format")
+
+require!(ancestry, "header-ancestry")
+
+sub!(ancestry) do t
+  arg!(t, `header-ancestry --input`)
+  foreach!(t, first.(HEADER_CHAINS))
+  commit!(t, last.(HEADER_CHAINS))
+end
+
+AdapterFixture.execute(ancestry)
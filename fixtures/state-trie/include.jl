@@ -20,9 +20,21 @@ const TEST_FILES_HEX = TEST_DIR .* [
   "10000_node.yaml",
 ]
 
+# Values stored in branch nodes: At the root, nested below other branches,
+# large enough to require hashing and behind odd length partial keys.
+const TEST_FILES_BRANCH_VALUE = TEST_DIR .* [
+  "branch_value_root.yaml",
+  "branch_value_nested.yaml",
+  "branch_value_hashed.yaml",
+  "branch_value_odd.yaml",
+]
+
 
 tests = AdapterFixture.Builder("State Trie", `state-trie`)
 
+describe!(tests, "Merkle radix-16 trie root computation, insertion and deletion",
+          "Polkadot Host Specification, State Storage Trie")
+
 sub!(tests) do t
   arg!(t, `trie-root --state-file`)
   foreach!(t, TEST_FILES)
@@ -41,6 +53,78 @@ sub!(tests) do t
   commit!(t)
 end
 
+sub!(tests) do t
+  arg!(t, `trie-root --keys-in-hex --state-file`)
+  foreach!(t, TEST_FILES_BRANCH_VALUE)
+  commit!(t)
+end
+
+sub!(tests) do t
+  arg!(t, `insert-and-delete --keys-in-hex --state-file`)
+  foreach!(t, TEST_FILES_BRANCH_VALUE)
+  commit!(t)
+end
+
 prepare!(tests)
 
+# Expected encodings of all stored nodes, as these are easily hidden by a
+# matching root (i.e. only the root node is compared otherwise).
+sub!(tests) do t
+  arg!(t, `trie-nodes --keys-in-hex --state-file`)
+  foreach!(t, TEST_FILES_BRANCH_VALUE)
+  commit!(t, [
+  """
+  node 5f13d3192fdbe57378e7a38d56e56eecb00432e550cc5867e20f6b525ef1e4a2: c20104002041687065653461652c4103206f686b374f686368
+  state root: 5f13d3192fdbe57378e7a38d56e56eecb00432e550cc5867e20f6b525ef1e4a2
+  """,
+  """
+  node c0100f8e12cad7fc87b2ad1beb36a5c4c6d6a62d0b6bf554e641772ef4726185: c2120800204f6f71756168387564c10420002049656834616879652c41062045657468326f6f74
+  state root: c0100f8e12cad7fc87b2ad1beb36a5c4c6d6a62d0b6bf554e641772ef4726185
+  """,
+  """
+  node 5d9d62ac1acce4411b0fa44bb79dbe74a32173bf59e63ff8802edd90b06e8e0f: 40a054686f6f326565566f6834456963357361695368616963683961686d65697a3941696768366f6f52
+  node 6df5d3abd04d5735aa2f2c03b88def670550a7ff8bd72aef9eb843a37c753045: c2ab0100a061694a3361687468656950686169776f68783148616832656547686f68306e65694e67346965503880dacad0d317e15ec25f2f9f00dc8b6001fa8973afa9c967aaa9b31a4b76066902
+  node 786d2a9f04d6791194a3393c987e87fc90455519280949f731c71b41a7714bf8: 40a045613969656e6f686368324f68666565346168706865654e67653861687468374c6f6f6a33656562
+  node dacad0d317e15ec25f2f9f00dc8b6001fa8973afa9c967aaa9b31a4b76066902: 800600805d9d62ac1acce4411b0fa44bb79dbe74a32173bf59e63ff8802edd90b06e8e0f80786d2a9f04d6791194a3393c987e87fc90455519280949f731c71b41a7714bf8
+  state root: 6df5d3abd04d5735aa2f2c03b88def670550a7ff8bd72aef9eb843a37c753045
+  """,
+  """
+  node 530c2029edbfb38ff56764f8e908842ef040e4327140d6a101320e838e83dc68: c20a020020456979306f75706880c272f638d6415dfda8c2c7724de9ab4013b4b26307db3cea14db6a211b564a3e
+  node c272f638d6415dfda8c2c7724de9ab4013b4b26307db3cea14db6a211b564a3e: c10228002061684b3461654e672c41042062616970683941652c4106206965443165695865
+  state root: 530c2029edbfb38ff56764f8e908842ef040e4327140d6a101320e838e83dc68
+  """,
+  ])
+end
+
 AdapterFixture.execute(tests)
+
+
+# Pathological trie shapes: Keys thousands of nibbles deep and hundreds of
+# keys sharing a long prefix, expected to be processed in reasonable time.
+const TEST_FILES_PATHOLOGICAL = TEST_DIR .* [
+  "deep_key.yaml",
+  "shared_prefix.yaml",
+]
+
+pathological = AdapterFixture.Builder("State Trie Pathological", `state-trie`)
+
+describe!(pathological, "Trie root computation for very deep keys and long shared prefixes",
+          "Polkadot Host Specification, State Storage Trie")
+
+limit!(pathological, 30)
+
+sub!(pathological) do t
+  arg!(t, `trie-root --keys-in-hex --state-file`)
+  foreach!(t, TEST_FILES_PATHOLOGICAL)
+  commit!(t)
+end
+
+sub!(pathological) do t
+  arg!(t, `insert-and-delete --keys-in-hex --state-file`)
+  foreach!(t, TEST_FILES_PATHOLOGICAL)
+  commit!(t)
+end
+
+prepare!(pathological)
+
+AdapterFixture.execute(pathological)
@@ -1,12 +1,24 @@
 module AdapterFixture
 
-export sub!, arg!, foreach!, commit!, reset!, clear!, prepare!
+export sub!, arg!, foreach!, commit!, reset!, clear!, prepare!, describe!, require!, limit!, canonical!, nonreference!, state!, pattern, versioned
 
 
 using Test
 
 using ..StringHelpers
 using ..Config
+import ..SpecificationTestsuite: ALL_CAPABILITIES
+using ..StatusServer
+using ..CanonicalOutput
+using ..OutputPattern
+using ..VersionedOutput
+
+
+"""
+Expected output: exact string, pattern with tolerance expressions, outputs by
+spec version or nothing to disable checks
+"""
+Expected = Union{MaybeString, Pattern, Versioned}
 
 
 "Exit code to state outcome of adapter test, based on errno"
@@ -28,10 +40,28 @@ mutable struct Builder
     inputs::CmdList
 
     "Expected output generated with reference implementations"
-    outputs::MaybeStringList
+    outputs::Vector{Expected}
+
+    "Short description of what is tested"
+    description::String
+
+    "Reference to the relevant section of the specification"
+    reference::String
+
+    "Optional capabilities required by the testsuite"
+    requires::StringList
+
+    "Maximum time in seconds a single test may take to run"
+    time_limit::Union{Float64,Nothing}
+
+    "Outputs are in canonical encoding and compared as such"
+    canonical::Bool
+
+    "Reason why outputs are not those of a reference implementation, if so"
+    nonreference::MaybeString
 
     "Constructor: Only allow to set name on construction"
-    Builder(name, default=``) = new(name, default, [default], [], [])
+    Builder(name, default=``) = new(name, default, [default], [], [], "", "", [], nothing, false, nothing)
 end
 
 
@@ -74,7 +104,7 @@ end
 Commit commands currently being build to inputs list together with list 
 of expected output. Use nothing entries to disable output checks.
 """
-function commit!(self::Builder, outputs::MaybeStringList)
+function commit!(self::Builder, outputs::Vector)
     if length(outputs) != length(self.current)
         error("Different count of Inputs and expected outputs.")
     end
@@ -87,8 +117,58 @@ end
 Commit commands currently being build to inputs list together with a single
 expected output for all of them. Use nothing to disable output checks.
 """
-function commit!(self::Builder, output::MaybeString)
-    commit!(self, MaybeStringList(output, length(self.current)))
+function commit!(self::Builder, output::Expected)
+    commit!(self, fill(output, length(self.current)))
+end
+
+"""
+Declare initial state of commands currently being build inline, as hex encoded
+top storage and child storages (by storage key without prefix).
+"""
+function state!(self::Builder, top::AbstractDict, children::AbstractDict=Dict())
+    args = String[]
+    for (key, value) in sort(collect(top))
+        push!(args, "--storage", "$key:$value")
+    end
+    for (child, storage) in sort(collect(children))
+        for (key, value) in sort(collect(storage))
+            push!(args, "--child-storage", "$child:$key:$value")
+        end
+    end
+    arg!(self, Cmd(args))
+end
+
+"Describe testsuite and reference relevant section of the specification"
+function describe!(self::Builder, description::String, reference::String="")
+    self.description = description
+    self.reference = reference
+end
+
+"Require optional capability, implementations not supporting it are skipped"
+function require!(self::Builder, capability::String)
+    if !(capability in ALL_CAPABILITIES)
+        error("Unknown capability: $capability")
+    end
+    push!(self.requires, capability)
+end
+
+"Limit time a single test may take, to catch excessive slowdowns"
+function limit!(self::Builder, seconds::Real)
+    self.time_limit = seconds
+end
+
+"Expect outputs in canonical encoding, compare them independent of formatting"
+function canonical!(self::Builder)
+    self.canonical = true
+end
+
+"""
+Mark outputs as not those of a reference implementation (e.g. as they were
+produced with a stand-in of the testsuite), so failures are only reported as
+broken and no expected outputs are exported.
+"""
+function nonreference!(self::Builder, reason::String)
+    self.nonreference = reason
 end
 
 "Reset current command being build to default"
@@ -103,43 +183,228 @@ function clear!(self::Builder)
 end
 
 
+"Arguments for internal consistency checks of the substrate adapter, if enabled"
+function check_args(adapter::CmdString, input::Cmd)::Cmd
+    if Config.verify_roots && adapter == "substrate-adapter" && !isempty(input.exec) && first(input.exec) == "host-api"
+        return `--verify-root`
+    end
+    return ``
+end
+
 "Cache expected output by running reference implementation, substrate by default."
 function prepare!(self::Builder, implementation="substrate")
+    # Exporting the corpus or listing tests does not depend on outputs
+    if Config.corpus != nothing || Config.listing
+        return
+    end
+
     self.outputs = []
     for (i, args) in enumerate(self.inputs)
         try
-            cmd = `$(implementation)-adapter $args`
-            push!(self.outputs, read(cmd, String))
+            adapter = "$(implementation)-adapter"
+            cmd = `$adapter $args $(check_args(adapter, args))`
+            push!(self.outputs, with_input_file(c -> read(c, String), cmd))
         catch e
             error("Failed to cache reference output: $e")
         end
     end
 end
 
+"Compare result with pattern of expected output, report first mismatching line."
+function matches(self::Builder, result::String, output::Pattern)::Bool
+    reason = mismatch(output, result)
+    if reason != nothing
+        @warn "Output does not match pattern, $reason"
+    end
+    return reason == nothing
+end
+
+"Compare result with expected output, which adapters have to match exactly in canonical encoding if configured."
+function matches(self::Builder, result::String, output::String)::Bool
+    if !self.canonical
+        return result == output
+    end
+
+    # Differing values are reported by the test, formatting issues separately
+    try
+        if !is_canonical(result)
+            same = canonicalize(result) == canonicalize(output)
+            @warn "Output not in canonical encoding" * (same ? " (values match)" : "") * ":\n$result"
+            return false
+        end
+        return result == canonicalize(output)
+    catch e
+        @error "Failed to canonicalize output: $e"
+        return false
+    end
+end
+
+"Maximum size of host api inputs passed as argument, larger ones are passed in a file"
+const MAX_INPUT_ARGUMENT = 64 * 1024
+
+"Call function with command, with oversized host api input replaced by a temporary input file."
+function with_input_file(f::Function, cmd::Cmd)
+    exec = copy(cmd.exec)
+    i = findfirst(isequal("--input"), exec)
+    if !("host-api" in exec) || i == nothing || i == length(exec) || sizeof(exec[i+1]) <= MAX_INPUT_ARGUMENT
+        return f(cmd)
+    end
+
+    # Input files contain one argument per line, which can not contain any
+    value = exec[i+1]
+    if occursin('\n', value)
+        error("Host api input contains line break")
+    end
+
+    path, io = mktemp()
+    try
+        for arg in split(value, ",")
+            println(io, arg)
+        end
+        close(io)
+        exec[i:i+1] = ["--input-file", path]
+        return f(Cmd(Cmd(exec), ignorestatus=cmd.ignorestatus, env=cmd.env, dir=cmd.dir))
+    finally
+        rm(path, force=true)
+    end
+end
+
+"Execute adapter command and collect process and combined output, kill it after optional timeout."
+function execute_cmd(cmd::Cmd, timeout::Union{Real,Nothing}=nothing)
+    with_input_file(cmd) do cmd
+        stream = Pipe()
+        proc = Base.run(pipeline(ignorestatus(cmd), stdout=stream, stderr=stream), wait=false)
+        close(stream.in)
+
+        timer = timeout == nothing ? nothing : Timer(_ -> process_running(proc) && kill(proc), timeout)
+        result = read(stream, String)
+        wait(proc)
+
+        if timer != nothing
+            close(timer)
+            if proc.termsignal != 0
+                result *= "\n[killed after timeout of $(timeout)s]\n"
+            end
+        end
+        return proc, result
+    end
+end
+
+"Output expected from the healthcheck subcommand of every adapter"
+const HEALTHCHECK_OUTPUT = "ok\n"
+
+"""
+Check that adapter responds to the healthcheck and warm it up by running the
+first test once untimed (e.g. to populate caches of compiled wasm). Returns the
+time taken by the healthcheck as start-up overhead, or nothing if it failed.
+"""
+function warmup(self::Builder, adapter::CmdString, args::CmdString, timeout::Union{Real,Nothing})::Union{Float64,Nothing}
+    local proc, result, startup
+    try
+        startup = @elapsed begin
+            proc, result = execute_cmd(`$adapter healthcheck`, timeout)
+        end
+    catch e
+        @error "Adapter $adapter could not be started: $e"
+        return nothing
+    end
+
+    if !success(proc) || result != HEALTHCHECK_OUTPUT
+        @error "Adapter $adapter failed healthcheck (exit code $(proc.exitcode)), expected \"ok\":\n$result"
+        return nothing
+    end
+
+    inputs = filter(Config.in_profile, self.inputs)
+    if !isempty(inputs)
+        input = first(inputs)
+        execute_cmd(cmdjoin(adapter, cmdjoin(input, cmdjoin(args, check_args(adapter, input)))), timeout)
+    end
+
+    return startup
+end
+
 "Run all commited test for specified adapter."
 function run(self::Builder, adapter::CmdString, args::CmdString=``)
     if length(self.inputs) != length(self.outputs)
         error("Missing or outdated cached reference outputs")
     end
 
+    implementation = replace(string(adapter), "-adapter" => "")
+
+    # Timed tests are measured without start-up of the adapter, broken ones fail early
+    startup = 0.0
+    if self.time_limit != nothing
+        startup = warmup(self, adapter, args, Config.timeout(self.name, implementation))
+        if startup == nothing
+            for input in filter(Config.in_profile, self.inputs)
+                @test startup != nothing
+                record!(cmdjoin(adapter, input), StatusServer.Failed)
+            end
+            return
+        end
+    end
+
     for (input, output) in zip(self.inputs, self.outputs)
 
+        # Tests outside of the selected profile are not part of the target
+        if !Config.in_profile(input)
+            continue
+        end
+
+        # Expected output can depend on the spec version targeted by the implementation
+        if output isa Versioned
+            output = select_output(output, Config.spec_version(implementation))
+        end
+
         # Execute adapter and collect output and exit code
-        cmd = cmdjoin(adapter, cmdjoin(input, args))
+        cmd = cmdjoin(adapter, cmdjoin(input, cmdjoin(args, check_args(adapter, input))))
+
+        # Tests without expected output are considered non-deterministic
+        if output == nothing && Config.deterministic_only
+            @test_skip output != nothing
+            record!(cmd, StatusServer.Skipped)
+            continue
+        end
 
         if Config.verbose
             println("┌ [COMMAND] ", cmd)
         end
 
-        stream = Pipe()
-        proc = Base.run(pipeline(ignorestatus(cmd), stdout=stream, stderr=stream))
-        close(stream.in)
-        result = read(stream, String)
+        # Rerun failing tests as configured, e.g. for flaky network dependent tests
+        timeout = Config.timeout(self.name, implementation)
+        attempts = 1 + Config.retries(self.name, implementation)
+        local proc, result, elapsed
+        for attempt in 1:attempts
+            elapsed = @elapsed begin
+                proc, result = execute_cmd(cmd, timeout)
+            end
+            elapsed = max(0.0, elapsed - startup)
+            if proc.exitcode == Int(NotSupported) || (success(proc) && (output == nothing || matches(self, result, output)))
+                break
+            end
+            if attempt < attempts
+                @warn "Retrying failed test ($attempt/$(attempts - 1)): $cmd"
+            end
+        end
 
         # Check if adapter is missing adaption (special case)
         if proc.exitcode == Int(NotSupported)
             @warn "Missing adaption: $cmd"
             @test_skip AdapterExitCode(proc.exitcode) == NotSupported
+            record!(cmd, StatusServer.Skipped)
+            continue
+        end
+
+        # Failures of quarantined tests and against non-reference outputs are only reported as broken
+        if Config.quarantined(cmd) || self.nonreference != nothing
+            passed = success(proc) && (output == nothing || matches(self, result, output))
+            if passed
+                @test passed
+            else
+                @warn (Config.quarantined(cmd) ? "Quarantined" : "Non-reference") * " test failed: $cmd"
+                @test_broken passed
+            end
+            record!(cmd, passed ? StatusServer.Passed : StatusServer.Skipped)
             continue
         end
 
@@ -147,7 +412,7 @@ function run(self::Builder, adapter::CmdString, args::CmdString=``)
         if success(proc)
             if output != nothing
                 # Default: Compare result against expected result
-                @test result == output
+                @test matches(self, result, output)
             else
                 # Empty outputs are used to disable comparison
                 @test output == nothing
@@ -158,6 +423,42 @@ function run(self::Builder, adapter::CmdString, args::CmdString=``)
             @test success(proc)
         end
 
+        # Rerun in fresh process and check output did not change
+        deterministic = true
+        if Config.determinism && success(proc)
+            _, rerun = execute_cmd(cmd, timeout)
+            deterministic = rerun == result
+
+            # Host api tests are also run twice within one process where
+            # supported, to detect state leaking from one run into the next
+            if !isempty(input.exec) && first(input.exec) == "host-api" && Config.supports(implementation, "repeat")
+                _, repeated = execute_cmd(cmdjoin(cmd, `--repeat 2`), timeout)
+                deterministic &= repeated == result^2
+            end
+
+            if !deterministic
+                @warn "Non-deterministic output: $cmd"
+            end
+            @test deterministic
+        end
+
+        # Check that test did not exceed time limit
+        timely = true
+        if self.time_limit != nothing && success(proc)
+            timely = elapsed <= self.time_limit
+            if !timely
+                @warn "Slow test ($(round(elapsed, digits=2))s): $cmd"
+            end
+            @test elapsed <= self.time_limit
+        end
+
+        passed = success(proc) && (output == nothing || matches(self, result, output)) && deterministic && timely
+        record!(cmd, passed ? StatusServer.Passed : StatusServer.Failed)
+
+        if !passed && Config.failures != nothing
+            export_failure(self, Config.failures, adapter, input, args, output, proc.exitcode, result)
+        end
+
         if Config.verbose
             if success(proc)
                 if output != nothing
@@ -176,19 +477,287 @@ function run(self::Builder, adapter::CmdString, args::CmdString=``)
    end # for inputs
 end
 
+"SCALE encode string as byte vector, i.e. prefixed with its compact length"
+function scale_encode(value::String)::Vector{UInt8}
+    data = Vector{UInt8}(value)
+    len = length(data)
+
+    if len < 1 << 6
+        prefix = [UInt8(len << 2)]
+    elseif len < 1 << 14
+        prefix = reinterpret(UInt8, [htol(UInt16(len << 2 | 0b01))])
+    elseif len < 1 << 30
+        prefix = reinterpret(UInt8, [htol(UInt32(len << 2 | 0b10))])
+    else
+        error("Value too large to be encoded")
+    end
+
+    return vcat(prefix, data)
+end
+
+"Escape byte vector as dictionary entry (as understood by libFuzzer and AFL)"
+function dict_entry(data::Vector{UInt8})::String
+    escape(b) = (0x20 <= b < 0x7f && b != UInt8('"') && b != UInt8('\\')) ? string(Char(b)) : "\\x" * string(b, base=16, pad=2)
+    return "\"" * join(map(escape, data)) * "\""
+end
+
+"Export all commited test inputs as fuzzing corpus and dictionary into directory."
+function export_corpus(self::Builder, dir::String)
+    name = replace(lowercase(self.name), " " => "-")
+
+    # Write one corpus file per input, containing one argument per line
+    corpus = joinpath(dir, name)
+    mkpath(corpus)
+    for (i, input) in enumerate(self.inputs)
+        write(joinpath(corpus, lpad(i, 6, "0")), join(input.exec, "\n"))
+    end
+
+    # Collect all distinct argument values, including comma separated ones
+    tokens = unique(filter(!isempty, collect(Iterators.flatten(
+        (split(arg, ",") for input in self.inputs for arg in input.exec)
+    ))))
+
+    # Write dictionary of raw and SCALE encoded values
+    open(joinpath(dir, "$name.dict"), "w") do io
+        for token in map(String, tokens)
+            println(io, dict_entry(Vector{UInt8}(token)))
+            println(io, dict_entry(scale_encode(token)))
+        end
+    end
+
+    println("Exported $(length(self.inputs)) inputs of $(self.name) to $corpus")
+end
+
+"""
+Export failed test as self-contained bundle into directory: The command to
+reproduce it, copies of all referenced files, expected and actual output.
+"""
+function export_failure(self::Builder, dir::String, adapter::CmdString, input::Cmd, args::Cmd, output::Expected, exitcode, result::String)
+    name = replace(lowercase(self.name), " " => "-")
+
+    # One numbered bundle per failure of the testsuite
+    suitedir = joinpath(dir, name)
+    mkpath(suitedir)
+    bundle = joinpath(suitedir, lpad(length(readdir(suitedir)) + 1, 6, "0"))
+    mkpath(bundle)
+
+    # Copy state files and other referenced files, and refer to the copies
+    exec = map(input.exec) do arg
+        if isfile(arg)
+            cp(arg, joinpath(bundle, basename(arg)), force=true)
+            return basename(arg)
+        end
+        return arg
+    end
+
+    # Shell script to rerun the exact command from within the bundle
+    command = cmdjoin(adapter, cmdjoin(Cmd(exec), args))
+    write(joinpath(bundle, "reproduce.sh"), "#!/bin/sh\ncd \"\$(dirname \"\$0\")\"\n$(Base.shell_escape(command))\n")
+    chmod(joinpath(bundle, "reproduce.sh"), 0o755)
+
+    # Host api tests are run with the runtime embedded in the adapter
+    open(joinpath(bundle, "info.txt"), "w") do io
+        println(io, "testsuite: ", self.name)
+        println(io, "adapter:   ", something(Sys.which(first(command.exec)), first(command.exec)))
+        println(io, "exit code: ", exitcode)
+        if !isempty(input.exec) && first(input.exec) == "host-api"
+            println(io, "runtime:   runtimes/hostapi (embedded in adapter)")
+        end
+        revision = try readchomp(`git -C $(@__DIR__) rev-parse HEAD`) catch; "unknown" end
+        println(io, "revision:  ", revision)
+    end
+
+    if output isa Pattern
+        write(joinpath(bundle, "expected.pattern"), output.expr)
+    elseif output != nothing
+        write(joinpath(bundle, "expected"), output)
+    end
+    write(joinpath(bundle, "actual"), result)
+
+    @info "Exported failing test to $bundle"
+end
+
+"""
+Export all commited test vectors into directory, to debug single vectors
+without the testsuite: The command and expected output, and for host api
+vectors the initial state and every call into the wasm blob as recorded by
+the reference implementation (entry point, hex encoded input and output and
+the blob called, shared by all vectors in `runtimes`).
+The arguments are also written one per line, as read by the vectors crate.
+"""
+function export_vectors(self::Builder, dir::String)
+    name = replace(lowercase(self.name), " " => "-")
+    suitedir = joinpath(dir, name)
+    mkpath(suitedir)
+
+    count = 0
+    for (i, (input, output)) in enumerate(zip(self.inputs, self.outputs))
+        if !Config.in_profile(input)
+            continue
+        end
+
+        vector = joinpath(suitedir, lpad(i, 6, "0"))
+        mkpath(vector)
+        write(joinpath(vector, "command"), Base.shell_escape(input) * "\n")
+        write(joinpath(vector, "args"), join(input.exec, "\n"))
+
+        # Versioned outputs are exported once per spec version, e.g. as expected@0.1.0
+        expected = output isa Versioned ? ["expected@$v" => o for (v, o) in output.outputs] : ["expected" => output]
+        if self.nonreference != nothing
+            write(joinpath(vector, "nonreference"), self.nonreference * "\n")
+            expected = []
+        end
+        for (file, output) in expected
+            if output isa Pattern
+                write(joinpath(vector, "$file.pattern"), output.expr)
+            elseif output != nothing
+                write(joinpath(vector, file), output)
+            end
+        end
+
+        if !isempty(input.exec) && first(input.exec) == "host-api"
+            # Initial state declared inline, one entry per line
+            open(joinpath(vector, "state"), "w") do io
+                for (arg, entry) in zip(input.exec, input.exec[2:end])
+                    if arg == "--storage"
+                        println(io, "top ", replace(entry, ":" => " "))
+                    elseif arg == "--child-storage"
+                        println(io, "child ", replace(entry, ":" => " "))
+                    end
+                end
+            end
+
+            # Calls into the wasm blob, recorded by the reference implementation
+            calls = joinpath(vector, "calls")
+            with_input_file(`substrate-adapter $input --export-calls $calls`) do cmd
+                read(ignorestatus(cmd), String)
+            end
+
+            # Blobs called (by hash) are shared within the testsuite
+            runtimes = joinpath(suitedir, "runtimes")
+            for file in filter(f -> endswith(f, ".wasm"), readdir(vector))
+                mkpath(runtimes)
+                mv(joinpath(vector, file), joinpath(runtimes, file), force=true)
+            end
+
+            # Genesis of the tester runtime replaces the declared state
+            if isfile(joinpath(vector, "genesis"))
+                mv(joinpath(vector, "genesis"), joinpath(vector, "state"), force=true)
+            end
+        end
+        count += 1
+    end
+
+    println("Exported $count vectors of $(self.name) to $suitedir")
+end
+
+"List testsuite with its metadata and all commited test inputs."
+function list(self::Builder)
+    println(self.name)
+    if !isempty(self.description)
+        println("  description: ", self.description)
+    end
+    if !isempty(self.reference)
+        println("  reference:   ", self.reference)
+    end
+    if !isempty(self.requires)
+        println("  requires:    ", join(self.requires, ", "))
+    end
+    if self.time_limit != nothing
+        println("  time limit:  ", self.time_limit, "s")
+    end
+    if self.canonical
+        println("  outputs:     canonical")
+    end
+    if self.nonreference != nothing
+        println("  outputs:     non-reference, ", self.nonreference)
+    end
+    inputs = filter(Config.in_profile, self.inputs)
+    println("  vectors:     ", length(inputs))
+    for input in inputs
+        println("    ", Base.shell_escape(input.exec...))
+    end
+end
+
+"Run host api fixture against each configured variant of the test blob, if any"
+function run_variants(self::Builder, adapter::CmdString, args::CmdString=``)
+    variants = Config.blob_variants()
+    if isempty(variants) || isempty(self.default.exec) || self.default.exec[1] != "host-api"
+        run(self, adapter, args)
+        return
+    end
+
+    # Variants are passed as blob override, which not every adapter supports
+    implementation = replace(string(adapter), "-adapter" => "")
+    if !Config.supports(implementation, "wasm-blob")
+        @info "Skipping blob variants for $implementation, test blob can not be replaced"
+        run(self, adapter, args)
+        return
+    end
+
+    for variant in variants
+        @testset "$(variant.name)" begin
+            withenv("WASM_BLOB_PATH" => variant.path) do
+                run(self, adapter, args)
+            end
+        end
+    end
+end
+
 "Run fixture for each configured implementation"
 function execute(self::Builder)
+    # Only export inputs if requested
+    if Config.corpus != nothing
+        export_corpus(self, Config.corpus)
+        return
+    end
+
+    # Only export vectors if requested
+    if Config.vectors != nothing
+        export_vectors(self, Config.vectors)
+        return
+    end
+
+    # Only list inputs if requested
+    if Config.listing
+        list(self)
+        return
+    end
+
+    set_testsuite!(self.name)
+
     @testset "$(self.name)" begin
         for implementation in Config.implementations
             adapter = "$implementation-adapter"
+            set_implementation!(implementation)
+
+            # Native oracles only run host api tests, which they execute without wasm
+            if Config.is_oracle(implementation) && (isempty(self.default.exec) || self.default.exec[1] != "host-api")
+                @info "Skipping $implementation, native oracles only run host api tests"
+                for input in filter(Config.in_profile, self.inputs)
+                    @test_skip !Config.is_oracle(implementation)
+                    record!(cmdjoin(adapter, input), StatusServer.Skipped)
+                end
+                continue
+            end
 
+            # Skip implementations lacking required capabilities
+            lacking = filter(c -> !Config.supports(implementation, c), self.requires)
+            if !isempty(lacking)
+                @info "Skipping $implementation, missing capabilities: $(join(lacking, ", "))"
+                for input in filter(Config.in_profile, self.inputs)
+                    @test_skip isempty(lacking)
+                    record!(cmdjoin(adapter, input), StatusServer.Skipped)
+                end
+                continue
+            end
 
             if isempty(Config.environments)
-                run(self, adapter)
+                run_variants(self, adapter)
             else
                 for environment in Config.environments
                     @testset "$environment" begin
-                        run(self, adapter, `--environment $environment`)
+                        run_variants(self, adapter, `--environment $environment`)
                     end
                 end # for environments
             end
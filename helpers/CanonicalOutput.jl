@@ -0,0 +1,255 @@
+"""
+Canonical encoding of adapter results, so they can be compared byte by byte
+independent of the formatting habits of each language ecosystem.
+
+The canonical encoding is JSON with the following rules:
+
+- no whitespace outside of strings, followed by a single newline
+- object keys sorted by codepoint, duplicate keys are not allowed
+- integers in decimal without sign (unless negative) or leading zeros,
+  floating point numbers are not allowed
+- byte arrays as strings of lowercase hex with `0x` prefix
+- strings only escape `"`, `\\` and control characters (as `\\u00xx`)
+"""
+module CanonicalOutput
+
+export canonicalize, is_canonical
+
+
+"Position in output being parsed"
+mutable struct Parser
+    chars::Vector{Char}
+    pos::Int
+end
+
+peek(p::Parser) = p.pos <= length(p.chars) ? p.chars[p.pos] : nothing
+
+function next!(p::Parser)::Char
+    c = peek(p)
+    if c == nothing
+        error("Unexpected end of output")
+    end
+    p.pos += 1
+    return c
+end
+
+function skip_whitespace!(p::Parser)
+    while peek(p) in (' ', '\t', '\n', '\r')
+        p.pos += 1
+    end
+end
+
+function expect!(p::Parser, word::String)
+    for c in word
+        if next!(p) != c
+            error("Expected '$word' at position $(p.pos - 1)")
+        end
+    end
+end
+
+"Parse any value, objects as sorted vector of pairs"
+function parse_value!(p::Parser)
+    skip_whitespace!(p)
+    c = peek(p)
+    if c == '{'
+        return parse_object!(p)
+    elseif c == '['
+        return parse_array!(p)
+    elseif c == '"'
+        return parse_string!(p)
+    elseif c == 't'
+        expect!(p, "true")
+        return true
+    elseif c == 'f'
+        expect!(p, "false")
+        return false
+    elseif c == 'n'
+        expect!(p, "null")
+        return nothing
+    elseif c == '-' || (c != nothing && isdigit(c))
+        return parse_integer!(p)
+    else
+        error("Unexpected character '$c' at position $(p.pos)")
+    end
+end
+
+function parse_object!(p::Parser)
+    expect!(p, "{")
+    entries = Vector{Pair{String,Any}}()
+    skip_whitespace!(p)
+    if peek(p) == '}'
+        p.pos += 1
+        return entries
+    end
+    while true
+        skip_whitespace!(p)
+        key = parse_string!(p)
+        skip_whitespace!(p)
+        expect!(p, ":")
+        push!(entries, key => parse_value!(p))
+        skip_whitespace!(p)
+        if next!(p) == '}'
+            break
+        end
+        p.pos -= 1
+        expect!(p, ",")
+    end
+
+    sort!(entries, by=first)
+    for i in 2:length(entries)
+        if first(entries[i]) == first(entries[i-1])
+            error("Duplicate key '$(first(entries[i]))'")
+        end
+    end
+    return entries
+end
+
+function parse_array!(p::Parser)
+    expect!(p, "[")
+    values = Vector{Any}()
+    skip_whitespace!(p)
+    if peek(p) == ']'
+        p.pos += 1
+        return values
+    end
+    while true
+        push!(values, parse_value!(p))
+        skip_whitespace!(p)
+        if next!(p) == ']'
+            break
+        end
+        p.pos -= 1
+        expect!(p, ",")
+    end
+    return values
+end
+
+function parse_hex4!(p::Parser)::UInt32
+    return parse(UInt32, String([next!(p) for _ in 1:4]), base=16)
+end
+
+function parse_string!(p::Parser)::String
+    expect!(p, "\"")
+    io = IOBuffer()
+    while (c = next!(p)) != '"'
+        if c < ' '
+            error("Unescaped control character at position $(p.pos - 1)")
+        elseif c != '\\'
+            print(io, c)
+            continue
+        end
+
+        e = next!(p)
+        if e == 'u'
+            code = parse_hex4!(p)
+            # Combine surrogate pairs
+            if 0xd800 <= code < 0xdc00
+                expect!(p, "\\u")
+                low = parse_hex4!(p)
+                code = 0x10000 + ((code - 0xd800) << 10) + (low - 0xdc00)
+            end
+            print(io, Char(code))
+        else
+            escapes = Dict('"' => '"', '\\' => '\\', '/' => '/', 'b' => '\b',
+                           'f' => '\f', 'n' => '\n', 'r' => '\r', 't' => '\t')
+            if !haskey(escapes, e)
+                error("Invalid escape '\\$e' at position $(p.pos - 1)")
+            end
+            print(io, escapes[e])
+        end
+    end
+    return String(take!(io))
+end
+
+function parse_integer!(p::Parser)::BigInt
+    start = p.pos
+    if peek(p) == '-'
+        p.pos += 1
+    end
+    while peek(p) != nothing && isdigit(peek(p))
+        p.pos += 1
+    end
+    if peek(p) in ('.', 'e', 'E')
+        error("Floating point number at position $start")
+    end
+    return parse(BigInt, String(p.chars[start:p.pos-1]))
+end
+
+
+"Serialize parsed value in canonical encoding"
+function write_value(io::IO, value)
+    if value == nothing
+        print(io, "null")
+    elseif value isa Bool
+        print(io, value ? "true" : "false")
+    elseif value isa Integer
+        print(io, string(value))
+    elseif value isa String
+        write_string(io, value)
+    elseif value isa Vector{Pair{String,Any}}
+        print(io, "{")
+        for (i, (key, entry)) in enumerate(value)
+            i > 1 && print(io, ",")
+            write_string(io, key)
+            print(io, ":")
+            write_value(io, entry)
+        end
+        print(io, "}")
+    else
+        print(io, "[")
+        for (i, entry) in enumerate(value)
+            i > 1 && print(io, ",")
+            write_value(io, entry)
+        end
+        print(io, "]")
+    end
+end
+
+function write_string(io::IO, value::String)
+    # Byte arrays are encoded as lowercase hex
+    if occursin(r"^0[xX][0-9a-fA-F]*$", value)
+        if isodd(length(value))
+            error("Byte array with odd number of hex digits: $value")
+        end
+        value = lowercase(value)
+    end
+
+    print(io, "\"")
+    for c in value
+        if c == '"' || c == '\\'
+            print(io, "\\", c)
+        elseif c < ' '
+            print(io, "\\u", string(UInt32(c), base=16, pad=4))
+        else
+            print(io, c)
+        end
+    end
+    print(io, "\"")
+end
+
+
+"Convert JSON output to canonical encoding, fails if it can not be represented."
+function canonicalize(output::String)::String
+    p = Parser(collect(output), 1)
+    value = parse_value!(p)
+    skip_whitespace!(p)
+    if peek(p) != nothing
+        error("Trailing data at position $(p.pos)")
+    end
+
+    io = IOBuffer()
+    write_value(io, value)
+    println(io)
+    return String(take!(io))
+end
+
+"Check that output already is in canonical encoding."
+function is_canonical(output::String)::Bool
+    try
+        return canonicalize(output) == output
+    catch
+        return false
+    end
+end
+
+end # module
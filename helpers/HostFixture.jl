@@ -1,6 +1,8 @@
 module HostFixture
 
 using ..Config
+using ..StatusServer
+using Sockets
 using Test
 
 "Represensts a fixture based on a tester runtime"
@@ -10,6 +12,12 @@ struct Tester
 
     "Name of the runtime to use"
     runtime::String
+
+    "Genesis to use instead of the runtime's default genesis"
+    genesis::Union{String,Nothing}
+
+    "Constructor: By default the runtime's genesis is used"
+    Tester(name, runtime, genesis=nothing) = new(name, runtime, genesis)
 end
 
 
@@ -39,13 +47,49 @@ function compute_root(self::Tester)
 end
 
 
-"Run host with tester genesis for certain time in seconds"
-function run_tester(self::Tester, host::String, duration::Number)
+"Port of the HTTP JSON-RPC server of each host supporting RPC queries"
+const RPC_PORTS = Dict(
+    "substrate" => 9933,
+    "gossamer"  => 8545,
+)
+
+"""
+Call JSON-RPC method with params (as JSON array) on local port, returns the
+raw response body or the error if the request failed.
+"""
+function rpc_call(port::Int, method::String, params::String="[]")::String
+    body = """{"jsonrpc":"2.0","id":1,"method":"$method","params":$params}"""
+    try
+        conn = connect(port)
+        write(conn, "POST / HTTP/1.1\r\nHost: localhost:$port\r\n" *
+                    "Content-Type: application/json\r\nContent-Length: $(sizeof(body))\r\n" *
+                    "Connection: close\r\n\r\n" * body)
+        response = read(conn, String)
+        close(conn)
+
+        separator = findfirst("\r\n\r\n", response)
+        return separator == nothing ? response : response[last(separator)+1:end]
+    catch e
+        return "error: $e"
+    end
+end
+
+"""
+Run host with tester genesis for certain time in seconds. If the host is
+expected to exit (e.g. to reject the genesis), it is checked to do so.
+RPC queries (method and params) are sent to the host before it is stopped,
+their responses are returned together with the output of the host.
+"""
+function run_tester(self::Tester, host::String, duration::Number, expect_exit::Bool=false, rpc::Vector=[])
     # Locations of needed files and folders
     tempdir = mktempdir() * "/"
 
     # Determine correct genesis of runtime
-    genesis = "$(@__DIR__)/../runtimes/$(self.runtime)/genesis.json"
+    genesis = if self.genesis != nothing
+        self.genesis
+    else
+        "$(@__DIR__)/../runtimes/$(self.runtime)/genesis.json"
+    end
 
     # Make sure genesis is available
     if !isfile(genesis)
@@ -96,6 +140,19 @@ function run_tester(self::Tester, host::String, duration::Number)
         error("Unknown host: ", host)
     end
 
+    # Enable RPC server only if queried (reachable from outside of container)
+    ports = ``
+    if !isempty(rpc)
+        port = RPC_PORTS[host]
+        if host == "substrate"
+            # Authorship of the local keys is only reported by unsafe methods
+            args = `$args --rpc-port $port --rpc-methods Unsafe $(Config.docker ? `--rpc-external` : ``)`
+        elseif host == "gossamer"
+            args = `$args --rpc --rpcport $port $(Config.docker ? `--rpchost 0.0.0.0` : ``)`
+        end
+        ports = `-p $port:$port`
+    end
+
     # Prepare container and overwrite command with docker invocation
     if Config.docker
         image = Config.get_container(host)
@@ -107,7 +164,7 @@ function run_tester(self::Tester, host::String, duration::Number)
         println("Caching/updating docker images of '$host':")
         run(`docker pull $image`)
 
-        exec = `docker run -e RUST_LOG=runtime=debug -v $tempdir:$datadir --rm -i $image`
+        exec = `docker run -e RUST_LOG=runtime=debug -v $tempdir:$datadir $ports --rm -i $image`
     end
 
     cmd = `$exec $args`
@@ -121,9 +178,18 @@ function run_tester(self::Tester, host::String, duration::Number)
     proc = run(pipeline(cmd, stdout=stream, stderr=stream), wait=false)
     sleep(duration)
 
-    # Stop process if necessary
+    # Stop process if necessary, after querying it
     crashed = !process_running(proc)
+    responses = Dict{String,String}()
     if !crashed
+        for (method, params) in rpc
+            responses[method] = rpc_call(RPC_PORTS[host], method, params)
+
+            if Config.verbose
+                println("│ [RPC] ", method, params, " => ", responses[method])
+            end
+        end
+
         kill(proc)
 
         while(process_running(proc))
@@ -135,31 +201,79 @@ function run_tester(self::Tester, host::String, duration::Number)
     close(stream.in)
     result = read(stream, String)
 
-    # Check and warn about unexpected crashes
-    if crashed
-        @warn "Host '$host' aborted unexpectedly:\n$result"
+    # Check and warn about unexpected crashes or unexpected survival
+    if expect_exit
+        if !crashed
+            @warn "Host '$host' did not exit as expected:\n$result"
+        end
+
+        @test crashed
+    else
+        if crashed
+            @warn "Host '$host' aborted unexpectedly:\n$result"
+        end
+
+        @test !crashed
     end
 
-    @test !crashed
+    record!(cmd, crashed == expect_exit ? StatusServer.Passed : StatusServer.Failed)
 
     if Config.verbose
         println("└ [OUTPUTS] ", result)
     end
 
-    return result
+    return result, responses
 end
 
-"Execute host and use supplied function to verify result."
-function execute(verify::Function, self::Tester, duration)
+"""
+Execute host and use supplied function to verify result, i.e. the expected
+storage root, output of the host and responses to the RPC queries (if any).
+Hosts not supporting RPC queries are skipped if queries are given.
+"""
+function execute(verify::Function, self::Tester, duration, expect_exit::Bool=false; rpc::Vector=[])
+    # Host fixtures have no inputs to export
+    if Config.corpus != nothing || Config.vectors != nothing
+        return
+    end
+
+    # Host fixtures have no inputs to list
+    if Config.listing
+        println(self.name)
+        println("  runtime:     ", self.runtime)
+        if self.genesis != nothing
+            println("  genesis:     ", self.genesis)
+        end
+        return
+    end
+
+    set_testsuite!(self.name)
+
     @testset "$(self.name)" begin
         for host in Config.implementations
+            set_implementation!(host)
+
+            # Native oracles have no node to run the tester runtime with
+            if Config.is_oracle(host)
+                @info "Skipping $host, native oracles have no node"
+                @test_skip !Config.is_oracle(host)
+                record!(host, StatusServer.Skipped)
+                continue
+            end
+
+            if !isempty(rpc) && !Config.supports(host, "rpc")
+                @info "Skipping $host, missing capabilities: rpc"
+                @test_skip Config.supports(host, "rpc")
+                record!(host, StatusServer.Skipped)
+                continue
+            end
+
             # Compute expected storage root
             root = compute_root(self)
 
             # Run host long enough to load genesis
-            result = run_tester(self, host, duration)
+            result, responses = run_tester(self, host, duration, expect_exit, rpc)
 
-            verify((root, result))
+            verify((root, result, responses))
         end
     end # testset
 end
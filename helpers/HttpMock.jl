@@ -0,0 +1,106 @@
+"""
+Local HTTP mock server for the offchain http host functions, with fixed
+responses so request and response handling can be checked deterministically:
+
+- `/hello`: `Hello World`
+- `/echo`: the request body, the `X-Test` request header as `X-Mock-Test`
+- `/slow`: answered only after 5 seconds, to check deadlines
+- any other path: `404 Not Found`
+
+Every response sets an `X-Mock` header with its route. The url of the server
+is exposed to adapters in `HTTP_MOCK_URL`.
+"""
+module HttpMock
+
+using Sockets
+
+
+"Parsed request: method, path, headers (lowercase names) and body"
+struct Request
+    method::String
+    path::String
+    headers::Dict{String,String}
+    body::Vector{UInt8}
+end
+
+"Read request from connection, supports fixed length and chunked bodies"
+function read_request(conn::TCPSocket)::Request
+    method, path = split(readline(conn))[1:2]
+
+    headers = Dict{String,String}()
+    while !isempty((line = readline(conn)))
+        name, value = split(line, ":", limit=2)
+        headers[lowercase(strip(name))] = strip(value)
+    end
+
+    body = UInt8[]
+    if get(headers, "transfer-encoding", "") == "chunked"
+        while (len = parse(Int, split(readline(conn), ";")[1], base=16)) > 0
+            append!(body, read(conn, len))
+            readline(conn)
+        end
+        readline(conn)
+    elseif haskey(headers, "content-length")
+        body = read(conn, parse(Int, headers["content-length"]))
+    end
+
+    return Request(method, path, headers, body)
+end
+
+"Fixed response of route: status, additional headers and body"
+function respond(request::Request)
+    if request.path == "/hello"
+        return "200 OK", ["X-Mock" => "hello"], Vector{UInt8}("Hello World")
+    elseif request.path == "/echo"
+        test = get(request.headers, "x-test", "")
+        return "200 OK", ["X-Mock" => "echo", "X-Mock-Test" => test], request.body
+    elseif request.path == "/slow"
+        sleep(5)
+        return "200 OK", ["X-Mock" => "slow"], Vector{UInt8}("Too late")
+    else
+        return "404 Not Found", ["X-Mock" => "missing"], Vector{UInt8}("Not found")
+    end
+end
+
+"Answer a single HTTP request on connection"
+function handle(conn::TCPSocket)
+    try
+        code, headers, body = respond(read_request(conn))
+
+        write(conn, "HTTP/1.1 $code\r\n" *
+                    join(["$name: $value\r\n" for (name, value) in headers]) *
+                    "Content-Type: text/plain\r\n" *
+                    "Content-Length: $(length(body))\r\n" *
+                    "Connection: close\r\n\r\n")
+        write(conn, body)
+    catch e
+        @debug "Failed to answer mock request: $e"
+    finally
+        close(conn)
+    end
+end
+
+"Start mock server on a free local port in background and expose its url"
+function start()
+    port, server = listenany(Sockets.localhost, 8000)
+
+    @async while isopen(server)
+        try
+            conn = accept(server)
+            @async handle(conn)
+        catch e
+            isopen(server) && @debug "Failed to accept mock connection: $e"
+        end
+    end
+
+    ENV["HTTP_MOCK_URL"] = "http://$(Sockets.localhost):$port"
+    return server
+end
+
+"Stop mock server"
+function stop(server)
+    close(server)
+    delete!(ENV, "HTTP_MOCK_URL")
+end
+
+end # module
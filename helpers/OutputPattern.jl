@@ -0,0 +1,87 @@
+"""
+Expected outputs with tolerance expressions, for results that legitimately
+vary between runs or implementations (random keys, signatures, timings).
+
+A pattern is compared line by line with the result. Each line is matched
+literally, unless it ends in one of the following expressions, in which case
+the text before it is matched literally and the rest of the line against it:
+
+- `{len=N}`: exactly N characters, e.g. `Public key: {len=64}`
+- `{regex=RE}`: the regular expression RE, e.g. `{regex=[0-9]+ ms}`
+- `{any-of=A|B|C}`: one of the alternatives, e.g. `is_validator: {any-of=0|1}`
+"""
+module OutputPattern
+
+export Pattern, pattern, mismatch
+
+
+"Match of a single line: literal prefix followed by optional expression"
+struct Line
+    prefix::String
+    rest::Union{Function,Nothing}
+end
+
+"Expected output with tolerance expressions"
+struct Pattern
+    "Source of the pattern, as provided by the fixture"
+    expr::String
+
+    lines::Vector{Line}
+end
+
+Base.show(io::IO, p::Pattern) = print(io, "pattern(", repr(p.expr), ")")
+
+
+"Parse expression at end of line, if any"
+function parse_line(line::AbstractString)::Line
+    m = match(r"^(.*?)\{(len|regex|any-of)=(.*)\}$", line)
+    if m == nothing
+        return Line(line, nothing)
+    end
+
+    prefix, kind, arg = m.captures
+    if kind == "len"
+        n = tryparse(Int, arg)
+        if n == nothing || n < 0
+            error("Invalid length in pattern: $line")
+        end
+        return Line(prefix, s -> length(s) == n)
+    elseif kind == "regex"
+        re = Regex("^(?:$arg)\$")
+        return Line(prefix, s -> occursin(re, s))
+    else
+        alternatives = split(arg, "|")
+        return Line(prefix, s -> s in alternatives)
+    end
+end
+
+"Create pattern from expression, see module documentation for the syntax."
+function pattern(expr::String)::Pattern
+    return Pattern(expr, map(parse_line, split(expr, "\n")))
+end
+
+
+"Check single line of result against line of pattern"
+function matches(line::Line, result::AbstractString)::Bool
+    if line.rest == nothing
+        return result == line.prefix
+    end
+    return startswith(result, line.prefix) && line.rest(chop(result, head=length(line.prefix), tail=0))
+end
+
+"Describe first line of result not matching pattern, or nothing if it matches."
+function mismatch(p::Pattern, result::String)::Union{String,Nothing}
+    lines = split(result, "\n")
+    if length(lines) != length(p.lines)
+        return "expected $(length(p.lines)) lines, got $(length(lines))"
+    end
+
+    for (i, (line, expr)) in enumerate(zip(lines, split(p.expr, "\n")))
+        if !matches(p.lines[i], line)
+            return "line $i \"$line\" does not match \"$expr\""
+        end
+    end
+    return nothing
+end
+
+end # module
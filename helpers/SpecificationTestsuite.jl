@@ -4,7 +4,7 @@ module SpecificationTestsuite
     include("StringHelpers.jl")
 
 
-    export ALL_IMPLEMENTATIONS, ALL_ENVIRONMENTS, ALL_FIXTURES, Config, execute
+    export ALL_IMPLEMENTATIONS, ALL_ORACLES, ALL_ENVIRONMENTS, ALL_CAPABILITIES, ALL_PROFILES, ALL_FIXTURES, SPEC_VERSION, Config, execute
 
     "List of all known implementations"
     const ALL_IMPLEMENTATIONS = [
@@ -13,6 +13,11 @@ module SpecificationTestsuite
       "gossamer"
     ]
 
+    "Native oracles: implementations only running host api tests directly against a host api (without wasm or node), only run if selected"
+    const ALL_ORACLES = [
+      "substrate-native"
+    ]
+
     "List of all known environments"
     const ALL_ENVIRONMENTS = [
         "wasmi"
@@ -21,9 +26,41 @@ module SpecificationTestsuite
         "life"
     ]
 
+    "List of all known optional capabilities, which testsuites can require"
+    const ALL_CAPABILITIES = [
+        "sandbox"
+        "concurrent-keystore"
+        "header-ancestry"
+        "offchain-http"
+        "wasm-blob"
+        "grandpa-voter"
+        "rpc"
+        "repeat"
+    ]
+
+    "Latest version of the host specification covered by the fixtures"
+    const SPEC_VERSION = v"0.1.0"
+
+    "Conformance profiles: Fixtures and host api subsets for each class of implementation"
+    const ALL_PROFILES = Dict(
+        "minimal-host" => (
+            fixtures = ["scale-codec", "state-trie", "host-api", "negative", "genesis"],
+            host_api = ["storage", "child_storage", "crypto", "hashing", "allocator", "trie", "misc", "logging", "wasm"],
+        ),
+        "full-host" => (
+            fixtures = ["scale-codec", "state-trie", "host-api", "negative", "genesis", "genesis-invalid", "chain-properties", "grandpa"],
+            host_api = ["storage", "child_storage", "crypto", "hashing", "allocator", "trie", "misc", "logging", "offchain", "sandbox", "wasm"],
+        ),
+        "light-client" => (
+            fixtures = ["scale-codec", "state-trie", "host-api", "negative"],
+            host_api = ["crypto", "hashing", "allocator", "trie", "misc", "logging", "wasm"],
+        ),
+    )
+
     module Config
-        import ..SpecificationTestsuite: ALL_IMPLEMENTATIONS
+        import ..SpecificationTestsuite: ALL_IMPLEMENTATIONS, ALL_ORACLES, ALL_CAPABILITIES, ALL_PROFILES, SPEC_VERSION
         import ..StringHelpers: StringList
+        import TOML
 
         "By default we log on a warning level."
         verbose = false
@@ -38,12 +75,53 @@ module SpecificationTestsuite
             #"gossamer"  => "docker.pkg.github.com/w3f/polkadot-spec/gossamer:xxxxxxx",
         )
 
+        "Capability manifest: Optional capabilities supported by each implementation"
+        capabilities = Dict{String,StringList}(
+            "substrate" => ["sandbox", "concurrent-keystore", "header-ancestry", "offchain-http", "wasm-blob", "grandpa-voter", "rpc", "repeat"],
+            "kagome"    => [],
+            "gossamer"  => ["wasm-blob", "rpc"],
+            "substrate-native" => ["repeat"],
+        )
+
+        "Spec version targeted by each implementation, all others target the latest one"
+        spec_versions = Dict{String,VersionNumber}()
+
         "By default all implementations are enabled."
         implementations = ALL_IMPLEMENTATIONS
 
         "By default no special environment is selected."
         environments = []
 
+        "By default no fuzzing corpus is exported."
+        corpus = nothing
+
+        "By default failing tests are not exported."
+        failures = nothing
+
+        "By default test vectors are run instead of exported."
+        vectors = nothing
+
+        "By default tests are run instead of listed."
+        listing = false
+
+        "By default no status server is started."
+        serve = nothing
+
+        "By default results are not written to a file."
+        results = nothing
+
+        "By default tests are only run once."
+        determinism = false
+
+        "By default tests without expected output are run as well."
+        deterministic_only = false
+
+        "By default storage roots of the substrate adapter are not cross-checked."
+        verify_roots = false
+
+        "By default host api tests only use the test blob embedded in the adapters."
+        variants = nothing
+
         "Path of folder containing all fixtures."
         function fixdir()::String
             return "$(@__DIR__)/../fixtures"
@@ -57,6 +135,9 @@ module SpecificationTestsuite
         "By default all fixtures are enabled."
         fixtures = fixsubdirs()
 
+        "By default no conformance profile is selected (i.e. everything is tested)."
+        profile = nothing
+
         "Update verbose setting in config"
         function set_verbose(enabled::Bool)
             global verbose = enabled
@@ -82,9 +163,188 @@ module SpecificationTestsuite
             global docker = enabled
         end
 
+        "Update corpus export directory in config"
+        function set_corpus(path::String)
+            global corpus = path
+        end
+
+        "Update failure bundle export directory in config"
+        function set_failures(path::String)
+            global failures = path
+        end
+
+        "Update test vector export directory in config"
+        function set_vectors(path::String)
+            global vectors = path
+        end
+
+        "Update listing setting in config"
+        function set_listing(enabled::Bool)
+            global listing = enabled
+        end
+
+        "Update status server port in config"
+        function set_serve(port::Int)
+            global serve = port
+        end
+
+        "Update results file in config"
+        function set_results(path::String)
+            global results = path
+        end
+
+        "Update selected conformance profile in config"
+        function set_profile(name::String)
+            if !haskey(ALL_PROFILES, name)
+                error("Unknown profile: $name")
+            end
+            global profile = name
+        end
+
+        "Check if adapter command is part of the selected profile"
+        function in_profile(cmd::Cmd)::Bool
+            if profile == nothing
+                return true
+            end
+
+            # Only host api functions are restricted beyond the fixture selection
+            args = cmd.exec
+            i = findfirst(==("--function"), args)
+            if isempty(args) || args[1] != "host-api" || i == nothing || i == length(args)
+                return true
+            end
+
+            # Functions are grouped by api, e.g. ext_default_child_storage_* or test_child_storage_*
+            api = replace(args[i+1], r"^(ext|test)_(default_)?" => "")
+            return any(group -> startswith(api, group * "_"), ALL_PROFILES[profile].host_api)
+        end
+
+        "Update determinism audit setting in config"
+        function set_determinism(enabled::Bool)
+            global determinism = enabled
+        end
+
+        "Update storage root cross-check setting in config"
+        function set_verify_roots(enabled::Bool)
+            global verify_roots = enabled
+        end
+
+        "Update blob variant directory in config"
+        function set_variants(path::String)
+            global variants = path
+        end
+
+        """
+        Variants of the test blob in the configured directory, as installed by
+        `make -C runtimes/hostapi install-variants`: name and path of each blob,
+        with toolchain and opt level from its metadata file (if present).
+        """
+        function blob_variants()
+            if variants == nothing
+                return []
+            end
+
+            blobs = filter(f -> endswith(f, ".wasm"), readdir(variants))
+            return map(blobs) do blob
+                name = replace(blob, r"\.wasm$" => "")
+                metadata = joinpath(variants, "$name.toml")
+                meta = isfile(metadata) ? TOML.parsefile(metadata) : Dict()
+                (
+                    name = name,
+                    path = joinpath(variants, blob),
+                    rustc = get(meta, "rustc", "unknown"),
+                    opt_level = get(meta, "opt_level", "unknown"),
+                )
+            end
+        end
+
+        "Update non-deterministic tests setting in config"
+        function set_deterministic_only(enabled::Bool)
+            global deterministic_only = enabled
+        end
+
+        "By default no runner settings are loaded (no timeouts, retries or quarantine)."
+        settings = Dict{String,Any}()
+
+        """
+        Load runner settings from TOML file: timeouts and retries per testsuite
+        (`[testsuite."<name>"]`) and per implementation (`[implementation.<name>]`,
+        taking precedence), the targeted `spec_version` per implementation and a
+        `quarantine` list of command substrings.
+        """
+        function load_settings(path::String)
+            loaded = TOML.parsefile(path)
+            for (section, entries) in loaded
+                if section in ("testsuite", "implementation")
+                    allowed = section == "implementation" ? ("timeout", "retries", "spec_version") : ("timeout", "retries")
+                    for (name, options) in entries, key in keys(options)
+                        if !(key in allowed)
+                            error("Unknown setting '$key' for $section $name in $path")
+                        end
+                        if key == "spec_version" && tryparse(VersionNumber, string(options[key])) == nothing
+                            error("Invalid spec version '$(options[key])' for $section $name in $path")
+                        end
+                    end
+                elseif section != "quarantine"
+                    error("Unknown section '$section' in $path")
+                end
+            end
+            global settings = loaded
+        end
+
+        "Look up setting of testsuite and implementation, the latter takes precedence"
+        function setting(testsuite::String, implementation::String, key::String)
+            for (section, name) in (("implementation", implementation), ("testsuite", testsuite))
+                entries = get(get(settings, section, Dict()), name, Dict())
+                if haskey(entries, key)
+                    return entries[key]
+                end
+            end
+            return nothing
+        end
+
+        "Timeout in seconds after which adapters are killed, if configured"
+        function timeout(testsuite::String, implementation::String)::Union{Float64,Nothing}
+            value = setting(testsuite, implementation, "timeout")
+            return value == nothing ? nothing : Float64(value)
+        end
+
+        "Number of reruns of failing tests before they are reported, none by default"
+        function retries(testsuite::String, implementation::String)::Int
+            return something(setting(testsuite, implementation, "retries"), 0)
+        end
+
+        "Spec version targeted by implementation, runner settings take precedence over the manifest"
+        function spec_version(implementation::String)::VersionNumber
+            value = get(get(get(settings, "implementation", Dict()), implementation, Dict()), "spec_version", nothing)
+            if value != nothing
+                return VersionNumber(string(value))
+            end
+            return get(spec_versions, implementation, SPEC_VERSION)
+        end
+
+        "Check if command is quarantined, i.e. its failures are reported as broken"
+        function quarantined(cmd::Cmd)::Bool
+            command = join(cmd.exec, " ")
+            return any(entry -> occursin(entry, command), get(settings, "quarantine", []))
+        end
+
+        "Check if implementation supports capability according to manifest"
+        function supports(implementation::String, capability::String)::Bool
+            if !(capability in ALL_CAPABILITIES)
+                @error "Unknown capability '$capability'"
+            end
+            return capability in get(capabilities, implementation, [])
+        end
+
+        "Check if implementation is a native oracle, which only runs host api tests"
+        function is_oracle(implementation::String)::Bool
+            return implementation in ALL_ORACLES
+        end
+
         "Retrieve docker image name to use in docker mode"
         function get_container(implementation::String)::String
-            if !(implementation in ALL_IMPLEMENTATIONS)
+            if !(implementation in ALL_IMPLEMENTATIONS || implementation in ALL_ORACLES)
                 @error "Unknown implementation '$implementation'"
             end
             return get(containers, implementation, "")
@@ -94,7 +354,12 @@ module SpecificationTestsuite
     "List of all available fixtures"
     const ALL_FIXTURES = Config.fixsubdirs()
 
-    # Include fixture helpers
+    # Include status server, output encoding and fixture helpers
+    include("StatusServer.jl")
+    include("HttpMock.jl")
+    include("CanonicalOutput.jl")
+    include("OutputPattern.jl")
+    include("VersionedOutput.jl")
     include("AdapterFixture.jl")
     include("HostFixture.jl")
 
@@ -104,14 +369,33 @@ module SpecificationTestsuite
             error("Unknown fixture: " * fixture)
         end
 
-        @time include(Config.fixdir() * "/$fixture/include.jl")
+        StatusServer.set_fixture!(fixture)
+
+        if Config.listing
+            println("[$fixture]")
+            include(Config.fixdir() * "/$fixture/include.jl")
+            println()
+        else
+            @time include(Config.fixdir() * "/$fixture/include.jl")
+        end
     end
 
     "Run all configured fixtures"
     function execute()
+        if Config.serve != nothing
+            StatusServer.start(Config.serve)
+        end
+
         for fixture in Config.fixtures
             run_fixture(fixture)
         end
+
+        StatusServer.finish!()
+
+        if Config.results != nothing
+            write(Config.results, StatusServer.render_results())
+            println("Results written to $(Config.results)")
+        end
     end
 end
 
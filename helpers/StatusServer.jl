@@ -0,0 +1,216 @@
+module StatusServer
+
+export record!, set_fixture!, set_testsuite!, set_implementation!
+
+using Sockets
+using Dates
+
+import ..SpecificationTestsuite: SPEC_VERSION, Config
+
+
+"Outcome of a single test as seen by the status server"
+@enum Outcome Passed Failed Skipped
+
+
+"Progress and results of the current run"
+mutable struct Status
+    "Time the run was started"
+    started::DateTime
+
+    "Fixture currently being run"
+    fixture::String
+
+    "Testsuite currently being run"
+    testsuite::String
+
+    "Implementation currently being tested"
+    implementation::String
+
+    "Number of tests per outcome"
+    counts::Dict{Outcome,Int}
+
+    "Number of tests per outcome, by implementation and fixture"
+    results::Dict{Tuple{String,String},Dict{Outcome,Int}}
+
+    "Commands of all failed tests"
+    failures::Vector{String}
+
+    "Set once all fixtures have been run"
+    finished::Bool
+
+    Status() = new(now(), "", "", "", Dict(Passed => 0, Failed => 0, Skipped => 0), Dict(), [], false)
+end
+
+const status = Status()
+
+
+"Update fixture currently being run"
+function set_fixture!(fixture::String)
+    status.fixture = fixture
+    status.testsuite = ""
+end
+
+"Update testsuite currently being run"
+function set_testsuite!(testsuite::String)
+    status.testsuite = testsuite
+end
+
+"Update implementation currently being tested"
+function set_implementation!(implementation::String)
+    status.implementation = implementation
+end
+
+"Record outcome of a test"
+function record!(cmd, outcome::Outcome)
+    status.counts[outcome] += 1
+
+    counts = get!(status.results, (status.implementation, status.fixture)) do
+        Dict(Passed => 0, Failed => 0, Skipped => 0)
+    end
+    counts[outcome] += 1
+
+    if outcome == Failed
+        push!(status.failures, string(cmd))
+    end
+end
+
+"Mark run as finished"
+function finish!()
+    status.finished = true
+end
+
+
+"Escape string as JSON string literal"
+function json_string(value::String)::String
+    escape(c) = c == '"'  ? "\\\"" :
+                c == '\\' ? "\\\\" :
+                c < ' '   ? "\\u" * string(UInt16(c), base=16, pad=4) : string(c)
+    return "\"" * join(map(escape, collect(value))) * "\""
+end
+
+"Render current status as JSON"
+function render_json()::String
+    return """{
+      "started": $(json_string(string(status.started))),
+      "fixture": $(json_string(status.fixture)),
+      "testsuite": $(json_string(status.testsuite)),
+      "passed": $(status.counts[Passed]),
+      "failed": $(status.counts[Failed]),
+      "skipped": $(status.counts[Skipped]),
+      "failures": [$(join(map(json_string, status.failures), ", "))],
+      "finished": $(status.finished)
+    }
+    """
+end
+
+"Revision of the test vector catalogue, i.e. of this repository"
+function catalogue_version()::String
+    try
+        return readchomp(`git -C $(@__DIR__) rev-parse HEAD`)
+    catch
+        return "unknown"
+    end
+end
+
+"""
+Render results of the run as JSON: tests per outcome by implementation and
+fixture, in stable order, together with the versions of catalogue and spec.
+"""
+function render_results()::String
+    entries = map(sort(collect(keys(status.results)))) do key
+        implementation, fixture = key
+        counts = status.results[key]
+        """    {"implementation": $(json_string(implementation)), "fixture": $(json_string(fixture)), """ *
+        """"spec_version": $(json_string(string(Config.spec_version(implementation)))), """ *
+        """"passed": $(counts[Passed]), "failed": $(counts[Failed]), "skipped": $(counts[Skipped])}"""
+    end
+
+    return """{
+      "catalogue": $(json_string(catalogue_version())),
+      "spec_version": $(json_string(string(SPEC_VERSION))),
+      "started": $(json_string(string(status.started))),
+      "results": [
+    $(join(entries, ",\n"))
+      ]
+    }
+    """
+end
+
+"Escape string for use in HTML"
+function html_escape(value::String)::String
+    return replace(replace(replace(value, "&" => "&amp;"), "<" => "&lt;"), ">" => "&gt;")
+end
+
+"Render current status as simple, self refreshing HTML page"
+function render_html()::String
+    failures = join(map(f -> "<li><code>$(html_escape(f))</code></li>", status.failures), "\n")
+
+    return """<!DOCTYPE html>
+    <html>
+    <head>
+      <title>Polkadot Host Test Suite</title>
+      $(status.finished ? "" : "<meta http-equiv=\"refresh\" content=\"5\">")
+    </head>
+    <body>
+      <h1>Polkadot Host Test Suite</h1>
+      <p>Started: $(status.started)</p>
+      <p>State: $(status.finished ? "finished" : "running $(html_escape(status.fixture)) $(html_escape(status.testsuite))")</p>
+      <table>
+        <tr><th>Passed</th><th>Failed</th><th>Skipped</th></tr>
+        <tr><td>$(status.counts[Passed])</td><td>$(status.counts[Failed])</td><td>$(status.counts[Skipped])</td></tr>
+      </table>
+      <h2>Failures</h2>
+      <ul>
+    $failures
+      </ul>
+    </body>
+    </html>
+    """
+end
+
+"Answer a single HTTP request on connection"
+function handle(conn::TCPSocket)
+    try
+        request = split(readline(conn))
+
+        # Skip all request headers
+        while !isempty(readline(conn))
+        end
+
+        path = length(request) >= 2 ? request[2] : "/"
+        if path == "/status.json"
+            code, type, body = "200 OK", "application/json", render_json()
+        elseif path == "/results.json"
+            code, type, body = "200 OK", "application/json", render_results()
+        elseif path == "/"
+            code, type, body = "200 OK", "text/html", render_html()
+        else
+            code, type, body = "404 Not Found", "text/plain", "Not found\n"
+        end
+
+        write(conn, "HTTP/1.1 $code\r\n" *
+                    "Content-Type: $type; charset=utf-8\r\n" *
+                    "Content-Length: $(sizeof(body))\r\n" *
+                    "Connection: close\r\n\r\n" * body)
+    catch e
+        @debug "Failed to answer status request: $e"
+    finally
+        close(conn)
+    end
+end
+
+"Serve status of run on local port in background"
+function start(port::Integer)
+    server = listen(Sockets.localhost, port)
+
+    @async while isopen(server)
+        conn = accept(server)
+        @async handle(conn)
+    end
+
+    println("Serving status on http://localhost:$port/ (JSON at /status.json)")
+
+    return server
+end
+
+end # module
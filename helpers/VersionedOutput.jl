@@ -0,0 +1,43 @@
+"""
+Expected outputs keyed by host spec version, for behaviour that changed between
+releases of the specification (e.g. after a clarification). Each output applies
+from its spec version on, until the next one:
+
+    versioned(v"0.1.0" => "old behaviour\\n", v"0.2.0" => pattern("new {any-of=a|b}\\n"))
+
+The output is selected by the spec version an implementation declares in the
+config (see `Config.spec_version`). Implementations targeting a version before
+the first output are not checked.
+"""
+module VersionedOutput
+
+export Versioned, versioned, select_output
+
+using ..OutputPattern
+
+
+"Expected outputs by spec version from which on they apply"
+struct Versioned
+    "Versions and their outputs, in ascending order of version"
+    outputs::Vector{Pair{VersionNumber,Union{String,Pattern,Nothing}}}
+end
+
+Base.show(io::IO, v::Versioned) = print(io, "versioned(", join(map(repr, v.outputs), ", "), ")")
+
+
+"Create versioned expected output, see module documentation for details."
+function versioned(outputs::Pair...)::Versioned
+    versions = map(first, outputs)
+    if !allunique(versions)
+        error("Duplicate spec version in versioned output: $(join(versions, ", "))")
+    end
+    return Versioned(sort(collect(outputs), by=first))
+end
+
+"Select expected output for spec version, nothing if it predates all outputs."
+function select_output(v::Versioned, version::VersionNumber)::Union{String,Pattern,Nothing}
+    i = findlast(o -> first(o) <= version, v.outputs)
+    return i == nothing ? nothing : last(v.outputs[i])
+end
+
+end # module
@@ -0,0 +1,213 @@
+#!/usr/bin/env julia
+
+# Maintenance of the test vector catalogue: Detect semantically duplicate
+# vectors and minimize failing vectors by delta debugging them against the
+# reference implementation.
+
+include("helpers/SpecificationTestsuite.jl")
+
+using .SpecificationTestsuite
+using .SpecificationTestsuite.AdapterFixture: execute_cmd
+
+"Exit code of adapters for unsupported tests (EOPNOTSUPP)"
+const NOT_SUPPORTED = 95
+
+"Arguments declaring initial state, order independent"
+const STATE_ARGS = ["--storage", "--child-storage"]
+
+
+function print_usage()
+    println("usage: $PROGRAM_FILE duplicates [FIXTURE...]")
+    println("       $PROGRAM_FILE minimize <IMPLEMENTATION> <COMMAND...>")
+    println()
+    println("COMMANDS:")
+    println("  duplicates   List vectors with identical function, inputs and initial state.")
+    println("  minimize     Shrink inputs and state of a vector, for which the implementation")
+    println("               disagrees with the reference (substrate), while it keeps failing.")
+    println()
+    println("FIXTURES:")
+    println(join(ALL_FIXTURES, ", "))
+    println()
+    println("IMPLEMENTATIONS:")
+    println(join(ALL_IMPLEMENTATIONS, ", "))
+end
+
+
+"Split vector into arguments, input fields and initial state entries"
+function split_vector(args::Vector{String})
+    rest, fields, state = String[], String[], Pair{String,String}[]
+    i = 1
+    while i <= length(args)
+        if args[i] in STATE_ARGS && i < length(args)
+            push!(state, args[i] => args[i+1])
+            i += 2
+        elseif args[i] == "--input" && i < length(args)
+            append!(fields, split(args[i+1], ","))
+            push!(rest, args[i])
+            i += 2
+        else
+            push!(rest, args[i])
+            i += 1
+        end
+    end
+    return rest, fields, state
+end
+
+"Join split vector back into arguments, fields replace the input value"
+function join_vector(rest::Vector{String}, fields::Vector{String}, state::Vector{Pair{String,String}})
+    args = String[]
+    for arg in rest
+        push!(args, arg)
+        if arg == "--input"
+            push!(args, join(fields, ","))
+        end
+    end
+    for (arg, entry) in state
+        push!(args, arg, entry)
+    end
+    return args
+end
+
+"Normalize vector, so semantically identical vectors compare equal"
+function normalize(args::Vector{String})::String
+    rest, fields, state = split_vector(args)
+    state = sort([arg => lowercase(replace(entry, "0x" => "")) for (arg, entry) in state])
+    return Base.shell_escape(join_vector(rest, fields, state)...)
+end
+
+"Report all groups of duplicate vectors in listing of fixtures"
+function duplicates(fixtures::Vector{String})
+    listing = read(`$(Base.julia_cmd()) $(@__DIR__)/runtests.jl list $fixtures`, String)
+
+    # Collect normalized vectors with their testsuite and position
+    vectors = Dict{String,Vector{String}}()
+    suite, index = "", 0
+    for line in split(listing, "\n")
+        if startswith(line, "    ")
+            index += 1
+            key = normalize(Base.shell_split(strip(line)))
+            push!(get!(vectors, key, String[]), "$suite #$index")
+        elseif !isempty(line) && !startswith(line, " ") && !startswith(line, "[")
+            suite, index = line, 0
+        end
+    end
+
+    groups = filter(v -> length(last(v)) > 1, collect(vectors))
+    for (key, locations) in sort(groups)
+        println(key)
+        for location in locations
+            println("  ", location)
+        end
+    end
+
+    println("Found $(length(groups)) duplicate vectors in $(length(vectors)) distinct vectors")
+    return isempty(groups)
+end
+
+
+"Check that implementation disagrees with the reference, while the latter succeeds"
+function failing(implementation::String, args::Vector{String})::Bool
+    reference, expected = execute_cmd(`substrate-adapter $args`)
+    if reference.exitcode != 0
+        return false
+    end
+
+    proc, result = execute_cmd(`$implementation-adapter $args`)
+    if proc.exitcode == NOT_SUPPORTED
+        return false
+    end
+    return proc.exitcode != 0 || result != expected
+end
+
+"Minimize units with delta debugging (ddmin), as long as the test keeps failing"
+function ddmin(test::Function, units::Vector)
+    n = 2
+    while length(units) >= 2
+        len = cld(length(units), n)
+        chunks = [units[i:min(i + len - 1, end)] for i in 1:len:length(units)]
+
+        reduced = false
+        for (i, chunk) in enumerate(chunks)
+            complement = vcat(chunks[1:i-1]..., chunks[i+1:end]...)
+            if test(chunk)
+                units, n, reduced = chunk, 2, true
+                break
+            elseif length(chunks) > 2 && test(complement)
+                units, n, reduced = complement, max(n - 1, 2), true
+                break
+            end
+        end
+
+        if !reduced
+            if n >= length(units)
+                break
+            end
+            n = min(2n, length(units))
+        end
+    end
+    return units
+end
+
+"Minimize state entries first, then every input field (hex encoded ones bytewise)"
+function minimize(implementation::String, args::Vector{String})
+    if !failing(implementation, args)
+        println("Vector does not fail for $implementation, nothing to minimize")
+        return false
+    end
+
+    rest, fields, state = split_vector(args)
+
+    state = ddmin(s -> failing(implementation, join_vector(rest, fields, s)), state)
+
+    for i in eachindex(fields)
+        hex = occursin(r"^([0-9a-fA-F]{2})+$", fields[i])
+        units = hex ? [fields[i][j:j+1] for j in 1:2:length(fields[i])] : collect(fields[i])
+
+        fields[i] = join(ddmin(units) do u
+            candidate = copy(fields)
+            candidate[i] = join(u)
+            failing(implementation, join_vector(rest, candidate, state))
+        end)
+    end
+
+    minimized = join_vector(rest, fields, state)
+    println("Minimized vector ($(sum(length, args)) -> $(sum(length, minimized)) characters):")
+    println(Base.shell_escape(minimized...))
+    return true
+end
+
+
+# Process all command line arguments
+if isempty(ARGS) || ARGS[1] == "--help"
+    print_usage()
+    exit(isempty(ARGS) ? 1 : 0)
+end
+
+# Add locally build or downloaded adapters to PATH
+ENV["PATH"] *= ":$(@__DIR__)/bin"
+
+command, args = ARGS[1], ARGS[2:end]
+
+if command == "duplicates"
+    unknown = filter(f -> !(f in ALL_FIXTURES), args)
+    if !isempty(unknown)
+        println("Unknown fixtures: ", join(unknown, ", "))
+        exit(1)
+    end
+    exit(duplicates(args) ? 0 : 1)
+end
+
+if command == "minimize"
+    if length(args) < 2 || !(args[1] in ALL_IMPLEMENTATIONS)
+        println("Missing or unknown implementation and command for minimize")
+        println()
+        print_usage()
+        exit(1)
+    end
+    exit(minimize(args[1], args[2:end]) ? 0 : 1)
+end
+
+println("Unknown command: ", command)
+println()
+print_usage()
+exit(1)
@@ -6,12 +6,38 @@ using .SpecificationTestsuite
 
 
 function print_usage()
-    println("usage: $PROGRAM_FILE [OPTIONS] [FILTERS ...]")
+    println("usage: $PROGRAM_FILE [OPTIONS] [list] [FILTERS ...]")
+    println()
+    println("COMMANDS:")
+    println("  list         List all selected fixtures and their test vectors instead of running them.")
     println()
     println("OPTIONS:")
     println("  --help       Display this message")
     println("  --verbose    Print debug information")
     println("  --docker     Use docker containers instead of local builds.")
+    println("  --serve <PORT>")
+    println("               Serve status of the run on a local port (HTML and JSON).")
+    println("  --determinism")
+    println("               Run every test twice and flag tests with differing outputs (host api tests")
+    println("               additionally twice within one process, where the adapter supports it).")
+    println("  --deterministic-only")
+    println("               Skip tests without expected output (i.e. non-deterministic).")
+    println("  --verify-roots")
+    println("               Cross-check storage roots of the substrate adapter against a full recomputation.")
+    println("  --results <FILE>")
+    println("               Write results per implementation and fixture as JSON (see summarize-results.jl).")
+    println("  --export-corpus <DIR>")
+    println("               Export fixture inputs as fuzzing corpus instead of running them.")
+    println("  --export-failures <DIR>")
+    println("               Export every failing test as bundle to reproduce it without the testsuite.")
+    println("  --export-vectors <DIR>")
+    println("               Export every test vector with its wasm calls and expected output instead of running them.")
+    println("  --blob-variants <DIR>")
+    println("               Run host api tests against every variant of the test blob in directory.")
+    println("  --config <FILE>")
+    println("               Load runner settings (timeouts, retries, quarantine) from TOML file.")
+    println("  --profile <PROFILE>")
+    println("               Only run tests of conformance profile (fixtures can still be selected).")
     println()
     println("FILTERS:")
     println("A filter can be used to run specific implementations or fixtures.")
@@ -20,11 +46,17 @@ function print_usage()
     println("IMPLEMENTATIONS:")
     println(join(ALL_IMPLEMENTATIONS, ", "))
     println()
+    println("ORACLES: (only run if selected)")
+    println(join(ALL_ORACLES, ", "))
+    println()
     println("FIXTURES: ")
     println(join(ALL_FIXTURES, ", "))
     println()
     println("ENVIRONMENTS: (host-api only)")
     println(join(ALL_ENVIRONMENTS, ", "))
+    println()
+    println("PROFILES:")
+    println(join(sort(collect(keys(ALL_PROFILES))), ", "))
 end
 
 
@@ -33,8 +65,12 @@ implementations = Vector{String}()
 fixtures = Vector{String}()
 environments = Vector{String}()
 
+# Runner settings file, if any
+settings = nothing
+
 # Process all command line arguments
-for arg in ARGS
+args = Iterators.Stateful(ARGS)
+for arg in args
     if arg == "--help"
         print_usage()
         exit()
@@ -50,7 +86,119 @@ for arg in ARGS
         continue
     end
 
-    if arg in ALL_IMPLEMENTATIONS
+    if arg == "--serve"
+        port = isempty(args) ? nothing : tryparse(Int, popfirst!(args))
+        if port == nothing
+            println("Missing or invalid port for --serve")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_serve(port)
+        continue
+    end
+
+    if arg == "--determinism"
+        Config.set_determinism(true)
+        continue
+    end
+
+    if arg == "--verify-roots"
+        Config.set_verify_roots(true)
+        continue
+    end
+
+    if arg == "--deterministic-only"
+        Config.set_deterministic_only(true)
+        continue
+    end
+
+    if arg == "--export-corpus"
+        if isempty(args)
+            println("Missing directory for --export-corpus")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_corpus(abspath(popfirst!(args)))
+        continue
+    end
+
+    if arg == "--results"
+        if isempty(args)
+            println("Missing file for --results")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_results(abspath(popfirst!(args)))
+        continue
+    end
+
+    if arg == "--export-failures"
+        if isempty(args)
+            println("Missing directory for --export-failures")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_failures(abspath(popfirst!(args)))
+        continue
+    end
+
+    if arg == "--export-vectors"
+        if isempty(args)
+            println("Missing directory for --export-vectors")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_vectors(abspath(popfirst!(args)))
+        continue
+    end
+
+    if arg == "--blob-variants"
+        path = isempty(args) ? nothing : popfirst!(args)
+        if path == nothing || !isdir(path)
+            println("Missing or invalid directory for --blob-variants")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_variants(abspath(path))
+        continue
+    end
+
+    if arg == "--config"
+        if isempty(args)
+            println("Missing file for --config")
+            println()
+            print_usage()
+            exit()
+        end
+        global settings = abspath(popfirst!(args))
+        Config.load_settings(settings)
+        continue
+    end
+
+    if arg == "--profile"
+        profile = isempty(args) ? nothing : popfirst!(args)
+        if !haskey(ALL_PROFILES, profile)
+            println("Missing or unknown profile for --profile")
+            println()
+            print_usage()
+            exit()
+        end
+        Config.set_profile(profile)
+        continue
+    end
+
+    if arg == "list"
+        Config.set_listing(true)
+        continue
+    end
+
+    if arg in ALL_IMPLEMENTATIONS || arg in ALL_ORACLES
         push!(implementations, arg)
         continue
     end
@@ -78,17 +226,62 @@ end
 
 if !isempty(fixtures)
     Config.set_fixtures(fixtures)
+elseif Config.profile != nothing
+    Config.set_fixtures(ALL_PROFILES[Config.profile].fixtures)
 end
 
 if !isempty(environments)
     Config.set_environments(environments)
 end
 
+# Only list fixtures (no need to display config or set up paths)
+if Config.listing
+    execute()
+    exit()
+end
+
 # Display config
 println("CONFIGURATION:")
 println("Loglevel:        " * (Config.verbose ? "verbose"   : "info"))
 println("Binaries:        " * (Config.docker  ? "container" : "local"))
+if Config.corpus != nothing
+    println("Corpus:          " * Config.corpus)
+end
+if Config.failures != nothing
+    println("Failures:        " * Config.failures)
+end
+if Config.vectors != nothing
+    println("Vectors:         " * Config.vectors)
+end
+if Config.serve != nothing
+    println("Status server:   http://localhost:$(Config.serve)/")
+end
+if Config.results != nothing
+    println("Results:         " * Config.results)
+end
+if Config.determinism
+    println("Determinism:     audit" * (Config.deterministic_only ? " (deterministic only)" : ""))
+elseif Config.deterministic_only
+    println("Determinism:     deterministic only")
+end
+if Config.verify_roots
+    println("Roots:           cross-checked (substrate)")
+end
+if Config.variants != nothing
+    println("Blob variants:   " * Config.variants)
+    for variant in Config.blob_variants()
+        println("  $(variant.name): $(variant.rustc), opt-level=$(variant.opt_level)")
+    end
+end
+if settings != nothing
+    println("Settings:        " * settings)
+end
+if Config.profile != nothing
+    println("Profile:         " * Config.profile)
+end
 println("Implementations: " * join(Config.implementations, ", "))
+versions = ["$i: $(Config.spec_version(i))" for i in Config.implementations if Config.spec_version(i) != SPEC_VERSION]
+println("Spec version:    $SPEC_VERSION" * (isempty(versions) ? "" : " (" * join(versions, ", ") * ")"))
 println("Fixtures:        " * join(Config.fixtures, ", "))
 if isempty(Config.environments)
     println("Environments:    (default)")
@@ -1,6 +1,378 @@
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::Path;
+
 use substrate_wasm_builder::WasmBuilder;
 
+/// Declarative list of all imported host functions
+const HOST_FUNCTIONS: &str = "host-functions.txt";
+
+/// Modules of sp-io providing the native equivalents of the host functions
+const SP_IO_MODULES: &[&str] =
+	&["storage", "default_child_storage", "crypto", "hashing", "trie", "misc", "logging", "offchain"];
+
+/// Host function as declared in the list
+struct HostFunction {
+	/// Only declared, test function is written by hand
+	external: bool,
+	/// Has a native equivalent in sp-io, i.e. is not superseded by a later version
+	native: bool,
+	name: String,
+	/// Name, type and (if given) native type of each parameter
+	params: Vec<(String, String, Option<String>)>,
+	/// Call pattern and type of the result, if any
+	result: Option<(String, String)>,
+}
+
+/// Split at top level commas, i.e. not within brackets of types
+fn split_top_level(list: &str) -> Vec<&str> {
+	let mut parts = Vec::new();
+	let (mut depth, mut start) = (0, 0);
+	for (i, c) in list.char_indices() {
+		match c {
+			'(' | '[' | '<' => depth += 1,
+			')' | ']' | '>' => depth -= 1,
+			',' if depth == 0 => {
+				parts.push(list[start..i].trim());
+				start = i + 1;
+			}
+			_ => {}
+		}
+	}
+	parts.push(list[start..].trim());
+	parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+fn parse(line: &str) -> HostFunction {
+	let (external, line) = match line.strip_prefix("extern ") {
+		Some(line) => (true, line.trim()),
+		None => (false, line),
+	};
+	let (native, line) = match line.strip_prefix("wasm ") {
+		Some(line) => (false, line.trim()),
+		None => (!external, line),
+	};
+
+	let open = line.find('(').unwrap_or_else(|| panic!("Host function without parameters: {}", line));
+	let close = line.rfind(')').unwrap_or_else(|| panic!("Unclosed parameters: {}", line));
+	let close = match line.find("->") {
+		Some(arrow) => line[..arrow].rfind(')').unwrap_or(close),
+		None => close,
+	};
+
+	let params = split_top_level(&line[open + 1..close])
+		.into_iter()
+		.map(|param| {
+			let mut parts = param.splitn(2, ':');
+			let name = parts.next().unwrap().trim().to_string();
+			let ty = parts.next().unwrap_or_else(|| panic!("Parameter without type: {}", param));
+			let mut types = ty.splitn(2, " as ");
+			let ty = types.next().unwrap().trim().to_string();
+			(name, ty, types.next().map(|native| native.trim().to_string()))
+		})
+		.collect();
+
+	let result = line[close + 1..].trim();
+	let result = match result.strip_prefix("->") {
+		Some(result) => {
+			let result = result.trim();
+			let (pattern, ty) = result.split_at(result.find(' ').unwrap_or(result.len()));
+			Some((pattern.to_string(), ty.trim().to_string()))
+		}
+		None if result.is_empty() => None,
+		None => panic!("Invalid result of {}: {}", &line[..open], result),
+	};
+
+	HostFunction { external, native, name: line[..open].trim().to_string(), params, result }
+}
+
+/// Type a parameter is passed to the host as
+fn abi_type(ty: &str) -> &'static str {
+	match ty {
+		"u32" => "u32",
+		"u64" => "u64",
+		_ if ty.starts_with('[') => "u32",
+		_ => "u64",
+	}
+}
+
+/// Type the host returns a result of the given call pattern as
+fn abi_result<'a>(function: &HostFunction, pattern: &str, ty: &'a str) -> &'a str {
+	match pattern {
+		"value" => ty,
+		"decode" | "encoded" => "u64",
+		"fixed" | "array" | "pointer" => "u32",
+		_ => panic!("Unknown call pattern of {}: {}", function.name, pattern),
+	}
+}
+
+/// Return type of the test function of a host function
+fn test_result(function: &HostFunction) -> String {
+	match &function.result {
+		None => String::new(),
+		Some((pattern, ty)) => match pattern.as_str() {
+			"value" | "decode" | "array" => format!(" -> {}", ty),
+			"encoded" | "fixed" => String::from(" -> Vec<u8>"),
+			_ => panic!("Call pattern {} of {} needs a hand-written test function", pattern, function.name),
+		},
+	}
+}
+
+/// Parameters of the test function of a host function
+fn test_params(function: &HostFunction) -> String {
+	let params: Vec<String> = function.params.iter().map(|(n, t, _)| format!("{}: {}", n, t)).collect();
+	params.join(", ")
+}
+
+/// Parameter names of the test function of a host function, as passed on
+fn test_args(function: &HostFunction) -> String {
+	let names: Vec<&str> = function.params.iter().map(|(n, _, _)| n.as_str()).collect();
+	names.join(", ")
+}
+
+/// Extern declarations, typed wrappers and test functions of all host functions
+fn generate_runtime(functions: &[(Option<String>, HostFunction)]) -> String {
+	let mut externs = String::from("extern \"C\" {\n");
+	let mut wrappers = String::from("/// Typed wrappers of the host functions\nmod host {\n    use super::*;\n");
+	let mut exports = String::from("sp_core::wasm_export_functions! {\n");
+
+	for (section, function) in functions {
+		if let Some(section) = section {
+			writeln!(externs, "\n    // {}", section).unwrap();
+			writeln!(wrappers, "\n    // {}", section).unwrap();
+			writeln!(exports, "\n    // {}\n", section).unwrap();
+		}
+
+		let params: Vec<String> = function.params.iter().map(|(n, t, _)| format!("{}: {}", n, abi_type(t))).collect();
+		let result = match &function.result {
+			Some((pattern, ty)) => format!(" -> {}", abi_result(function, pattern, ty)),
+			None => String::new(),
+		};
+		writeln!(externs, "    fn {}({}){};", function.name, params.join(", "), result).unwrap();
+
+		if function.external {
+			continue;
+		}
+
+		// Encoded parameters have to outlive the call
+		let mut body = String::new();
+		for (name, ty, _) in &function.params {
+			if abi_type(ty) == "u64" && ty != "u64" && ty != "Vec<u8>" {
+				writeln!(body, "        let {} = {}.encode();", name, name).unwrap();
+			}
+		}
+		let args: Vec<String> = function
+			.params
+			.iter()
+			.map(|(name, ty, _)| match abi_type(ty) {
+				"u64" if ty != "u64" => format!("{}.as_re_ptr()", name),
+				"u32" if ty.starts_with('[') => format!("{}.as_ptr() as u32", name),
+				_ => name.clone(),
+			})
+			.collect();
+		// Wrappers share the names of the externs they call
+		let call = format!("super::{}({})", function.name, args.join(", "));
+
+		let call = match &function.result {
+			None => format!("{};", call),
+			Some((pattern, ty)) => match pattern.as_str() {
+				"value" => call,
+				"decode" => {
+					format!("let result = {};\n            Decode::decode(&mut from_mem(result).as_slice()).unwrap()", call)
+				}
+				"encoded" => format!("from_mem({})", call),
+				"fixed" => format!(
+					"let result = {};\n            std::slice::from_raw_parts(result as *mut u8, std::mem::size_of::<{}>()).to_vec()",
+					call, ty
+				),
+				"array" => format!(
+					"let result = {};\n            std::slice::from_raw_parts(result as *mut u8, std::mem::size_of::<{}>()).try_into().unwrap()",
+					call, ty
+				),
+				_ => panic!("Call pattern {} of {} needs a hand-written test function", pattern, function.name),
+			},
+		};
+
+		let (params, ret) = (test_params(function), test_result(function));
+		writeln!(
+			wrappers,
+			"    pub fn {}({}){} {{\n{}        unsafe {{\n            {}\n        }}\n    }}",
+			function.name, params, ret, body, call
+		)
+		.unwrap();
+		writeln!(
+			exports,
+			"    fn rtm_{}({}){} {{\n        host::{}({})\n    }}",
+			function.name,
+			params,
+			ret,
+			function.name,
+			test_args(function)
+		)
+		.unwrap();
+	}
+
+	externs.push_str("}\n\n");
+	wrappers.push_str("}\n\n");
+	exports.push_str("}\n");
+	externs + &wrappers + &exports
+}
+
+/// Path of the native equivalent of a host function in sp-io, which only
+/// provides the latest version of each function
+fn sp_io_path(function: &HostFunction) -> String {
+	let name = function.name.strip_prefix("ext_").unwrap_or(&function.name);
+	let name = &name[..name.rfind("_version_").unwrap_or_else(|| panic!("Unversioned host function {}", function.name))];
+	let module = SP_IO_MODULES
+		.iter()
+		.find(|module| name.strip_prefix(*module).map_or(false, |rest| rest.starts_with('_')))
+		.unwrap_or_else(|| panic!("Host function {} of no sp-io module", function.name));
+	format!("sp_io::{}::{}", module, &name[module.len() + 1..])
+}
+
+/// Macro expanding to the native equivalents of all host functions provided by
+/// sp-io, i.e. typed wrappers calling sp-io and the dispatch of their test
+/// functions. It is expanded by the native oracle of the adapter, which
+/// provides the conversions between the declared and the sp-io types.
+fn generate_native(functions: &[(Option<String>, HostFunction)]) -> String {
+	let mut wrappers = String::new();
+	let mut arms = String::new();
+
+	// Sections are kept for the first function with a native equivalent
+	let mut pending = None;
+	for (section, function) in functions {
+		pending = section.as_ref().or(pending);
+		if !function.native {
+			continue;
+		}
+		if let Some(section) = pending.take() {
+			writeln!(wrappers, "\n            // {}", section).unwrap();
+			writeln!(arms, "\n                // {}", section).unwrap();
+		}
+
+		// Parameters of the given native type are converted first, if
+		// borrowed they are passed by reference
+		let mut body = String::new();
+		let args: Vec<String> = function
+			.params
+			.iter()
+			.map(|(name, _, native)| match native {
+				Some(native) => {
+					let ty = native.trim_start_matches('&').trim();
+					writeln!(body, "                let {}: {} = Arg::arg(&{});", name, ty, name).unwrap();
+					if native.starts_with('&') { format!("&{}", name) } else { name.clone() }
+				}
+				None => format!("Arg::arg(&{})", name),
+			})
+			.collect();
+		let call = format!("{}({})", sp_io_path(function), args.join(", "));
+
+		// Results are returned like the host passes them to the runtime
+		let call = match function.result.as_ref().map(|(pattern, _)| pattern.as_str()) {
+			None => call,
+			Some("value") => format!("Value::value({})", call),
+			Some("decode") => format!("Decode::decode(&mut Encode::encode(&{}).as_slice()).unwrap()", call),
+			Some("encoded") => format!("Encoded::encoded({})", call),
+			Some("fixed") => format!("AsRef::<[u8]>::as_ref(&{}).to_vec()", call),
+			Some("array") => format!("AsRef::<[u8]>::as_ref(&{}).try_into().unwrap()", call),
+			Some(pattern) => panic!("Call pattern {} of {} has no native equivalent", pattern, function.name),
+		};
+
+		writeln!(
+			wrappers,
+			"            pub fn {}({}){} {{\n{}                {}\n            }}",
+			function.name,
+			test_params(function),
+			test_result(function),
+			body,
+			call
+		)
+		.unwrap();
+		writeln!(
+			arms,
+			"                \"rtm_{}\" => native!(input, |{}| host::{}({})),",
+			function.name,
+			test_params(function),
+			function.name,
+			test_args(function)
+		)
+		.unwrap();
+	}
+
+	let mut out = String::from(
+		"/// Expands to the native equivalents of all host functions declared in\n\
+		 /// host-functions.txt that sp-io provides: typed wrappers `host::<name>`\n\
+		 /// calling sp-io and `dispatch_declared` executing their test functions.\n\
+		 /// Expects the conversion traits `Arg`, `Value` and `Encoded` as well as the\n\
+		 /// `native!` macro in scope.\n\
+		 #[macro_export]\nmacro_rules! native_functions {\n    () => {\n",
+	);
+	out.push_str("        mod host {\n            use super::*;\n");
+	out += &wrappers;
+	out.push_str("        }\n\n        fn dispatch_declared(func: &str, input: &mut &[u8]) -> Option<Vec<u8>> {\n");
+	out.push_str("            Some(match func {");
+	out += &arms;
+	out.push_str("\n                _ => return None,\n            })\n        }\n    };\n}\n");
+	out
+}
+
+/// Signatures of all host functions, as needed for raw calls
+fn generate_signatures(functions: &[(Option<String>, HostFunction)]) -> String {
+	let value_type = |ty: &str| if ty == "u32" { "ValueType::I32" } else { "ValueType::I64" };
+
+	let mut out = String::from("/// Signatures of all imported host functions\npub static HOST_FUNCTIONS: &[HostFunction] = &[\n");
+	for (_, function) in functions {
+		let params: Vec<&str> = function.params.iter().map(|(_, t, _)| value_type(abi_type(t))).collect();
+		let result = match &function.result {
+			None => String::from("Return::Nothing"),
+			Some((pattern, ty)) => match (pattern.as_str(), abi_result(function, pattern, ty)) {
+				("value", ty) => format!("Return::Value({})", value_type(ty)),
+				(_, ty) => format!("Return::Pointer({})", value_type(ty)),
+			},
+		};
+		writeln!(
+			out,
+			"    HostFunction {{ name: {:?}, params: &[{}], result: {} }},",
+			function.name,
+			params.join(", "),
+			result
+		)
+		.unwrap();
+	}
+	out.push_str("];\n");
+	out
+}
+
 fn main() {
+	println!("cargo:rerun-if-changed={}", HOST_FUNCTIONS);
+
+	// Lines of the list, with the preceding section comment (if any) of each function
+	let list = fs::read_to_string(HOST_FUNCTIONS).expect("Failed to read host function list");
+	let mut functions = Vec::new();
+	let mut comment = None;
+	for line in list.lines().map(str::trim) {
+		match line.strip_prefix('#') {
+			Some(text) => comment = comment.or_else(|| Some(text.trim().to_string())),
+			None if line.is_empty() => {}
+			None => {
+				let section = comment.take().filter(|c| c.ends_with(" API"));
+				functions.push((section, parse(line)));
+			}
+		}
+		if line.is_empty() {
+			comment = None;
+		}
+	}
+
+	let out = env::var("OUT_DIR").unwrap();
+	fs::write(Path::new(&out).join("host_functions.rs"), generate_runtime(&functions))
+		.expect("Failed to write host functions");
+	fs::write(Path::new(&out).join("host_signatures.rs"), generate_signatures(&functions))
+		.expect("Failed to write host function signatures");
+	fs::write(Path::new(&out).join("native_functions.rs"), generate_native(&functions))
+		.expect("Failed to write native host functions");
+
 	let mut b = WasmBuilder::new()
 		.with_current_project()
 		.export_heap_base();
@@ -0,0 +1,76 @@
+//! Tests combining several calls of host functions, written against their
+//! typed wrappers `host::<name>`. They are shared with the native oracle of the
+//! adapter, which expands them against the native wrappers calling sp-io.
+
+/// Defines the combined tests within the invoking module, calling the wrappers
+/// of the `host` module in scope there
+#[macro_export]
+macro_rules! combined_tests {
+    () => {
+        /// Set value in innermost of nested transactions, then commit all of
+        /// them (or all but the outermost one, which is rolled back)
+        pub fn nested_transactions(depth: u32, key: Vec<u8>, value: Vec<u8>, commit: bool) {
+            for _ in 0..depth {
+                host::ext_storage_start_transaction_version_1();
+            }
+            host::ext_storage_set_version_1(key, value);
+            for level in (0..depth).rev() {
+                if level == 0 && !commit {
+                    host::ext_storage_rollback_transaction_version_1();
+                } else {
+                    host::ext_storage_commit_transaction_version_1();
+                }
+            }
+        }
+
+        /// Read key before and after setting it to a new value, clearing it and
+        /// setting it again to value, all within the same call
+        pub fn overlay_reads(key: Vec<u8>, value: Vec<u8>, new_value: Vec<u8>) -> Vec<Option<Vec<u8>>> {
+            let mut reads = vec![host::ext_storage_get_version_1(key.clone())];
+            host::ext_storage_set_version_1(key.clone(), new_value);
+            reads.push(host::ext_storage_get_version_1(key.clone()));
+            host::ext_storage_clear_version_1(key.clone());
+            reads.push(host::ext_storage_get_version_1(key.clone()));
+            host::ext_storage_set_version_1(key.clone(), value);
+            reads.push(host::ext_storage_get_version_1(key));
+            reads
+        }
+
+        /// Overwrite (or clear) value in a transaction, which is then committed
+        /// or rolled back, returns the value seen inside of the transaction
+        pub fn transaction_overwrite(key: Vec<u8>, value: Option<Vec<u8>>, commit: bool) -> Option<Vec<u8>> {
+            host::ext_storage_start_transaction_version_1();
+            match value {
+                Some(value) => host::ext_storage_set_version_1(key.clone(), value),
+                None => host::ext_storage_clear_version_1(key.clone()),
+            }
+            let inner = host::ext_storage_get_version_1(key);
+            if commit {
+                host::ext_storage_commit_transaction_version_1();
+            } else {
+                host::ext_storage_rollback_transaction_version_1();
+            }
+            inner
+        }
+
+        /// Start nested transactions, then close one more than were started
+        pub fn unbalanced_transactions(depth: u32, commit: bool) {
+            for _ in 0..depth {
+                host::ext_storage_start_transaction_version_1();
+            }
+            for _ in 0..=depth {
+                if commit {
+                    host::ext_storage_commit_transaction_version_1();
+                } else {
+                    host::ext_storage_rollback_transaction_version_1();
+                }
+            }
+        }
+    };
+}
+
+#[cfg(feature = "runtime-wasm")]
+use crate::host;
+
+#[cfg(feature = "runtime-wasm")]
+combined_tests!();
@@ -14,6 +14,62 @@ use parity_scale_codec::{Decode, Encode};
 #[cfg(not(feature = "runtime-wasm"))]
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
+/// Wasm value types used by host functions
+#[cfg(not(feature = "runtime-wasm"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ValueType {
+    I32,
+    I64,
+}
+
+/// Kind of value returned by a host function
+#[cfg(not(feature = "runtime-wasm"))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Return {
+    /// Nothing is returned
+    Nothing,
+    /// Plain value (e.g. a boolean flag)
+    Value(ValueType),
+    /// Pointer or pointer-size to data, which is allocation specific
+    Pointer(ValueType),
+}
+
+#[cfg(not(feature = "runtime-wasm"))]
+impl Return {
+    pub fn value_type(&self) -> Option<ValueType> {
+        match self {
+            Return::Nothing => None,
+            Return::Value(t) | Return::Pointer(t) => Some(*t),
+        }
+    }
+}
+
+/// Signature of a host function imported by the runtime
+#[cfg(not(feature = "runtime-wasm"))]
+pub struct HostFunction {
+    pub name: &'static str,
+    pub params: &'static [ValueType],
+    pub result: Return,
+}
+
+// Signatures of all host functions, generated from host-functions.txt
+#[cfg(not(feature = "runtime-wasm"))]
+include!(concat!(env!("OUT_DIR"), "/host_signatures.rs"));
+
+/// Signature of the imported host function of the given name, if any
+#[cfg(not(feature = "runtime-wasm"))]
+pub fn host_function(name: &str) -> Option<&'static HostFunction> {
+    HOST_FUNCTIONS.iter().find(|f| f.name == name)
+}
+
+// Macro `native_functions!` expanding to the native equivalents of the host
+// functions, generated from host-functions.txt (see build.rs)
+#[cfg(not(feature = "runtime-wasm"))]
+include!(concat!(env!("OUT_DIR"), "/native_functions.rs"));
+
+// Tests combining several calls, as `combined_tests!` shared with the adapter
+mod combined;
+
 
 #[cfg(feature = "runtime-wasm")]
 struct WasmAllocator;
@@ -34,62 +90,10 @@ unsafe impl GlobalAlloc for WasmAllocator {
 }
 
 
+// Extern declarations of all host functions, their typed wrappers and test
+// functions calling them, generated from host-functions.txt (see build.rs)
 #[cfg(feature = "runtime-wasm")]
-extern "C" {
-    // Storage API
-    fn ext_storage_set_version_1(key: u64, value: u64);
-    fn ext_storage_get_version_1(key: u64) -> u64;
-    fn ext_storage_read_version_1(key: u64, out: u64, offset: u32) -> u64;
-    fn ext_storage_clear_version_1(key: u64);
-    fn ext_storage_exists_version_1(key: u64) -> i32;
-    fn ext_storage_clear_prefix_version_1(key: u64);
-    fn ext_storage_append_version_1(key: u64, value: u64);
-    fn ext_storage_root_version_1() -> u64;
-    fn ext_storage_next_key_version_1(key: u64) -> u64;
-
-    // Default child stoage API
-    fn ext_default_child_storage_set_version_1(child: u64, key: u64, value: u64);
-    fn ext_default_child_storage_get_version_1(child: u64, key: u64) -> u64;
-    fn ext_default_child_storage_read_version_1(child: u64, key: u64, out: u64, offset: u32) -> u64;
-    fn ext_default_child_storage_clear_version_1(child: u64, key: u64);
-    fn ext_default_child_storage_storage_kill_version_1(child: u64);
-    fn ext_default_child_storage_exists_version_1(child: u64, key: u64) -> i32;
-    fn ext_default_child_storage_clear_prefix_version_1(child: u64, key: u64);
-    fn ext_default_child_storage_root_version_1(child: u64) -> u64;
-    fn ext_default_child_storage_next_key_version_1(child: u64, key: u64) -> u64;
-
-    // Crypto API
-    fn ext_crypto_ed25519_public_keys_version_1(id: u32) -> u64;
-    fn ext_crypto_ed25519_generate_version_1(id: u32, seed: u64) -> u32;
-    fn ext_crypto_ed25519_sign_version_1(id: u32, pubkey: u32, msg: u64) -> u64;
-    fn ext_crypto_ed25519_verify_version_1(sig: u32, msg: u64, pubkey: u32) -> i32;
-
-    fn ext_crypto_sr25519_public_keys_version_1(id: u32) -> u64;
-    fn ext_crypto_sr25519_generate_version_1(id: u32, seed: u64) -> u32;
-    fn ext_crypto_sr25519_sign_version_1(id: u32, pubkey: u32, msg: u64) -> u64;
-    fn ext_crypto_sr25519_verify_version_1(sig: u32, msg: u64, pubkey: u32) -> i32;
-
-    fn ext_crypto_secp256k1_ecdsa_recover_version_1(sig: u32, msg: u32) -> u64;
-
-    // Hashing API
-    fn ext_hashing_keccak_256_version_1(data: u64) -> i32;
-    fn ext_hashing_sha2_256_version_1(data: u64) -> i32;
-
-    fn ext_hashing_blake2_128_version_1(data: u64) -> i32;
-    fn ext_hashing_blake2_256_version_1(data: u64) -> i32;
-
-    fn ext_hashing_twox_256_version_1(data: u64) -> i32;
-    fn ext_hashing_twox_128_version_1(data: u64) -> i32;
-    fn ext_hashing_twox_64_version_1(data: u64) -> i32;
-
-    // Allocator API
-    fn ext_allocator_malloc_version_1(size: u32) -> u32;
-    fn ext_allocator_free_version_1(ptr: u32);
-
-    // Trie API
-    fn ext_trie_blake2_256_root_version_1(data: u64) -> u32;
-    fn ext_trie_blake2_256_ordered_root_version_1(data: u64) -> u32;
-}
+include!(concat!(env!("OUT_DIR"), "/host_functions.rs"));
 
 
 #[cfg(feature = "runtime-wasm")]
@@ -112,28 +116,38 @@ impl AsRePtr for Vec<u8> {
 }
 
 
+/// Sandbox result codes, all other results of instantiate are instance indices
 #[cfg(feature = "runtime-wasm")]
-sp_core::wasm_export_functions! {
-
-    // Storage API
+const SANDBOX_ERR_EXECUTION: u32 = -1i32 as u32;
+#[cfg(feature = "runtime-wasm")]
+const SANDBOX_ERR_MODULE: u32 = -3i32 as u32;
 
-    fn rtm_ext_storage_set_version_1(key: Vec<u8>, value: Vec<u8>) {
-        unsafe {
-            let _ = ext_storage_set_version_1(
-                key.as_re_ptr(),
-                value.as_re_ptr()
-            );
-        }
+/// Supervisor function called by sandboxed code, stores the key value pair
+/// passed as state.
+#[cfg(feature = "runtime-wasm")]
+extern "C" fn sandbox_dispatch_thunk(
+    _args: *const u8,
+    _args_len: usize,
+    state: usize,
+    _function: usize,
+) -> u64 {
+    unsafe {
+        let state = &*(state as *const Vec<u8>);
+        let (key, value) = <(Vec<u8>, Vec<u8>)>::decode(&mut state.as_slice()).unwrap();
+        ext_storage_set_version_1(key.as_re_ptr(), value.as_re_ptr());
+
+        // Encoded Ok(ReturnValue::Unit), freed by the host
+        let result = std::mem::ManuallyDrop::new(vec![0u8, 0u8]);
+        result.as_re_ptr()
     }
+}
 
-    fn rtm_ext_storage_get_version_1(key: Vec<u8>) -> Option<Vec<u8>> {
-        unsafe {
-            let value = ext_storage_get_version_1(
-                key.as_re_ptr(),
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
+// Test functions of host functions declared as extern in host-functions.txt
+// and tests combining several calls
+#[cfg(feature = "runtime-wasm")]
+sp_core::wasm_export_functions! {
+
+    // Storage API
 
     fn rtm_ext_storage_read_version_1(
         key: Vec<u8>,
@@ -154,78 +168,32 @@ sp_core::wasm_export_functions! {
         }
     }
 
-    fn rtm_ext_storage_clear_version_1(key: Vec<u8>) {
+    // Read value at offset into a buffer prefilled with 0xff, returns the raw
+    // result together with the whole buffer, including any untouched bytes
+    fn rtm_ext_storage_read_partial_version_1(key: Vec<u8>, offset: u32, buffer_size: u32) -> (Option<u32>, Vec<u8>) {
+        let mut buffer = vec![0xffu8; buffer_size as usize];
         unsafe {
-            let _ = ext_storage_clear_version_1(key.as_re_ptr());
+            let res = ext_storage_read_version_1(key.as_re_ptr(), buffer.as_re_ptr(), offset);
+            (Decode::decode(&mut from_mem(res).as_slice()).unwrap(), buffer)
         }
     }
 
-    fn rtm_ext_storage_exists_version_1(key: Vec<u8>) -> u32 {
-        unsafe {
-            ext_storage_exists_version_1(key.as_re_ptr()) as u32
-        }
+    // Tests combining several calls, shared with the native oracle
+    fn rtm_ext_storage_nested_transactions_version_1(depth: u32, key: Vec<u8>, value: Vec<u8>, commit: bool) {
+        combined::nested_transactions(depth, key, value, commit)
     }
-
-    fn rtm_ext_storage_clear_prefix_version_1(key: Vec<u8>) {
-        unsafe {
-            let _ = ext_storage_clear_prefix_version_1(key.as_re_ptr());
-        }
+    fn rtm_ext_storage_overlay_reads_version_1(key: Vec<u8>, value: Vec<u8>, new_value: Vec<u8>) -> Vec<Option<Vec<u8>>> {
+        combined::overlay_reads(key, value, new_value)
     }
-
-    fn rtm_ext_storage_append_version_1(key: Vec<u8>, value: Vec<u8>) {
-        unsafe {
-            let _ = ext_storage_append_version_1(
-                key.as_re_ptr(),
-                value.as_re_ptr(),
-            );
-        }
+    fn rtm_ext_storage_transaction_overwrite_version_1(key: Vec<u8>, value: Option<Vec<u8>>, commit: bool) -> Option<Vec<u8>> {
+        combined::transaction_overwrite(key, value, commit)
     }
-
-
-    fn rtm_ext_storage_root_version_1() -> Vec<u8> {
-        unsafe {
-            let value = ext_storage_root_version_1();
-            from_mem(value)
-        }
-    }
-
-    fn rtm_ext_storage_next_key_version_1(key: Vec<u8>) -> Option<Vec<u8>> {
-        unsafe {
-            let value = ext_storage_next_key_version_1(key.as_re_ptr());
-
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
+    fn rtm_ext_storage_unbalanced_transactions_version_1(depth: u32, commit: bool) {
+        combined::unbalanced_transactions(depth, commit)
     }
 
     // Default child storage API
 
-    fn rtm_ext_default_child_storage_set_version_1(
-        child: Vec<u8>,
-        key: Vec<u8>,
-        value: Vec<u8>
-    ) {
-        unsafe {
-            let _ = ext_default_child_storage_set_version_1(
-                child.as_re_ptr(),
-                key.as_re_ptr(),
-                value.as_re_ptr()
-            );
-        }
-    }
-
-    fn rtm_ext_default_child_storage_get_version_1(
-        child: Vec<u8>,
-        key: Vec<u8>
-    ) -> Option<Vec<u8>> {
-        unsafe {
-            let value = ext_default_child_storage_get_version_1(
-                child.as_re_ptr(),
-                key.as_re_ptr(),
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
-
     fn rtm_ext_default_child_storage_read_version_1(
         child: Vec<u8>,
         key: Vec<u8>,
@@ -247,150 +215,8 @@ sp_core::wasm_export_functions! {
         }
     }
 
-    fn rtm_ext_default_child_storage_clear_version_1(
-        child: Vec<u8>,
-        key: Vec<u8>
-    ) {
-        unsafe {
-            let _ = ext_default_child_storage_clear_version_1(
-                child.as_re_ptr(),
-                key.as_re_ptr(),
-            );
-        }
-    }
-
-    fn rtm_ext_default_child_storage_storage_kill_version_1(
-        child: Vec<u8>,
-    ) {
-        unsafe {
-            let _ = ext_default_child_storage_storage_kill_version_1(
-                child.as_re_ptr(),
-            );
-        }
-    }
-
-    fn rtm_ext_default_child_storage_exists_version_1(
-        child: Vec<u8>,
-        key: Vec<u8>
-    ) -> u32 {
-        unsafe {
-            ext_default_child_storage_exists_version_1(
-                child.as_re_ptr(),
-                key.as_re_ptr(),
-            ) as u32
-        }
-    }
-
-    fn rtm_ext_default_child_storage_clear_prefix_version_1(
-        child: Vec<u8>,
-        key: Vec<u8>
-    ) {
-        unsafe {
-            let _ = ext_default_child_storage_clear_prefix_version_1(
-                child.as_re_ptr(),
-                key.as_re_ptr(),
-            );
-        }
-    }
-
-    fn rtm_ext_default_child_storage_root_version_1(child: Vec<u8>) -> Vec<u8> {
-        unsafe {
-            let value = ext_default_child_storage_root_version_1(
-                child.as_re_ptr(),
-            );
-            from_mem(value)
-        }
-    }
-
-    fn rtm_ext_default_child_storage_next_key_version_1(
-        child_key: Vec<u8>,
-        key_data: Vec<u8>
-    ) -> Option<Vec<u8>> {
-        unsafe {
-            let value = ext_default_child_storage_next_key_version_1(
-                child_key.as_re_ptr(),
-                key_data.as_re_ptr(),
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
-
     // Crypto API
 
-    fn rtm_ext_crypto_ed25519_public_keys_version_1(id_data: [u8; 4]) -> Vec<[u8; 32]> {
-        unsafe {
-            let value = ext_crypto_ed25519_public_keys_version_1(
-                id_data.as_ptr() as u32,
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
-    fn rtm_ext_crypto_ed25519_generate_version_1(id_data: [u8; 4], seed_data: Option<Vec<u8>>) -> [u8; 32] {
-        let seed_data = seed_data.encode();
-        unsafe {
-            let value = ext_crypto_ed25519_generate_version_1(
-                id_data.as_ptr() as u32,
-                seed_data.as_re_ptr()
-            );
-            std::slice::from_raw_parts(value as *mut u8, 32).try_into().unwrap()
-        }
-    }
-    fn rtm_ext_crypto_ed25519_sign_version_1(id_data: [u8; 4], pubkey_data: [u8; 32], msg_data: Vec<u8>) -> Option<[u8; 64]> {
-        unsafe {
-            let value = ext_crypto_ed25519_sign_version_1(
-                id_data.as_ptr() as u32,
-                pubkey_data.as_ptr() as u32,
-                msg_data.as_re_ptr()
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
-    fn rtm_ext_crypto_ed25519_verify_version_1(sig_data: [u8; 64], msg_data: Vec<u8>, pubkey_data: [u8; 32]) -> u32 {
-        unsafe {
-            ext_crypto_ed25519_verify_version_1(
-                sig_data.as_ptr() as u32,
-                msg_data.as_re_ptr(),
-                pubkey_data.as_ptr() as u32
-            ) as u32
-        }
-    }
-    fn rtm_ext_crypto_sr25519_public_keys_version_1(id_data: [u8; 4]) -> Vec<[u8; 32]> {
-        unsafe {
-            let value = ext_crypto_sr25519_public_keys_version_1(
-                id_data.as_ptr() as u32,
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
-    fn rtm_ext_crypto_sr25519_generate_version_1(id_data: [u8; 4], seed_data: Option<Vec<u8>>) -> [u8; 32] {
-        let seed_data = seed_data.encode();
-        unsafe {
-            let value = ext_crypto_sr25519_generate_version_1(
-                id_data.as_ptr() as u32,
-                seed_data.as_re_ptr()
-            );
-            std::slice::from_raw_parts(value as *mut u8, 32).try_into().unwrap()
-        }
-    }
-    fn rtm_ext_crypto_sr25519_sign_version_1(id_data: [u8; 4], pubkey_data: [u8; 32], msg_data: Vec<u8>) -> Option<[u8; 64]> {
-        unsafe {
-            let value = ext_crypto_sr25519_sign_version_1(
-                id_data.as_ptr() as u32,
-                pubkey_data.as_ptr() as u32,
-                msg_data.as_re_ptr()
-            );
-            Decode::decode(&mut from_mem(value).as_slice()).unwrap()
-        }
-    }
-    fn rtm_ext_crypto_sr25519_verify_version_1(sig_data: [u8; 64], msg_data: Vec<u8>, pubkey_data: [u8; 32]) -> u32 {
-        unsafe {
-            ext_crypto_sr25519_verify_version_1(
-                sig_data.as_ptr() as u32,
-                msg_data.as_re_ptr(),
-                pubkey_data.as_ptr() as u32
-            ) as u32
-        }
-    }
     fn rtm_ext_crypto_secp256k1_ecdsa_recover_version_1(sig_data: Vec<u8>, msg_data: Vec<u8>) -> Vec<u8> {
         unsafe {
             let value = ext_crypto_secp256k1_ecdsa_recover_version_1(
@@ -400,63 +226,45 @@ sp_core::wasm_export_functions! {
             from_mem(value)
         }
     }
-
-    // Hashing API
-
-    fn rtm_ext_hashing_keccak_256_version_1(data: Vec<u8>) -> Vec<u8> {
-        unsafe {
-            let value = ext_hashing_keccak_256_version_1(
-                data.as_re_ptr(),
-            );
-            std::slice::from_raw_parts(value as *mut u8, 32).to_vec()
-        }
-    }
-    fn rtm_ext_hashing_sha2_256_version_1(data: Vec<u8>) -> Vec<u8> {
-        unsafe {
-            let value = ext_hashing_sha2_256_version_1(
-                data.as_re_ptr(),
-            );
-            std::slice::from_raw_parts(value as *mut u8, 32).to_vec()
-        }
-    }
-    fn rtm_ext_hashing_blake2_128_version_1(data: Vec<u8>) -> Vec<u8> {
+    fn rtm_ext_crypto_secp256k1_ecdsa_recover_compressed_version_1(sig_data: Vec<u8>, msg_data: Vec<u8>) -> Vec<u8> {
         unsafe {
-            let value = ext_hashing_blake2_128_version_1(
-                data.as_re_ptr(),
-            );
-            std::slice::from_raw_parts(value as *mut u8, 16).to_vec()
-        }
-    }
-    fn rtm_ext_hashing_blake2_256_version_1(data: Vec<u8>) -> Vec<u8> {
-        unsafe {
-            let value = ext_hashing_blake2_256_version_1(
-                data.as_re_ptr(),
-            );
-            std::slice::from_raw_parts(value as *mut u8, 32).to_vec()
-        }
-    }
-    fn rtm_ext_hashing_twox_256_version_1(data: Vec<u8>) -> Vec<u8> {
-        unsafe {
-            let value = ext_hashing_twox_256_version_1(
-                data.as_re_ptr(),
-            );
-            std::slice::from_raw_parts(value as *mut u8, 32).to_vec()
-        }
-    }
-    fn rtm_ext_hashing_twox_128_version_1(data: Vec<u8>) -> Vec<u8> {
-        unsafe {
-            let value = ext_hashing_twox_128_version_1(
-                data.as_re_ptr(),
+            let value = ext_crypto_secp256k1_ecdsa_recover_compressed_version_1(
+                sig_data.as_ptr() as u32,
+                msg_data.as_ptr() as u32,
             );
-            std::slice::from_raw_parts(value as *mut u8, 16).to_vec()
+            from_mem(value)
         }
     }
-    fn rtm_ext_hashing_twox_64_version_1(data: Vec<u8>) -> Vec<u8> {
+    // Verify all signatures (signature, message, public key) in a single batch
+    fn rtm_ext_crypto_batch_verify_version_1(
+        ed25519_sigs: Vec<([u8; 64], Vec<u8>, [u8; 32])>,
+        sr25519_sigs: Vec<([u8; 64], Vec<u8>, [u8; 32])>,
+        ecdsa_sigs: Vec<([u8; 65], Vec<u8>, [u8; 33])>,
+    ) -> u32 {
         unsafe {
-            let value = ext_hashing_twox_64_version_1(
-                data.as_re_ptr(),
-            );
-            std::slice::from_raw_parts(value as *mut u8, 8).to_vec()
+            ext_crypto_start_batch_verify_version_1();
+            for (sig, msg, pubkey) in &ed25519_sigs {
+                let _ = ext_crypto_ed25519_batch_verify_version_1(
+                    sig.as_ptr() as u32,
+                    msg.as_re_ptr(),
+                    pubkey.as_ptr() as u32
+                );
+            }
+            for (sig, msg, pubkey) in &sr25519_sigs {
+                let _ = ext_crypto_sr25519_batch_verify_version_1(
+                    sig.as_ptr() as u32,
+                    msg.as_re_ptr(),
+                    pubkey.as_ptr() as u32
+                );
+            }
+            for (sig, msg, pubkey) in &ecdsa_sigs {
+                let _ = ext_crypto_ecdsa_batch_verify_version_1(
+                    sig.as_ptr() as u32,
+                    msg.as_re_ptr(),
+                    pubkey.as_ptr() as u32
+                );
+            }
+            ext_crypto_finish_batch_verify_version_1() as u32
         }
     }
 
@@ -485,25 +293,172 @@ sp_core::wasm_export_functions! {
             result
         }
     }
+    fn rtm_ext_allocator_roundtrip_version_1(count: u32, size: u32) -> u32 {
+        unsafe {
+            // Second round reuses the freed buffers
+            for _ in 0..2 {
+                let ptrs: Vec<u32> = (0..count)
+                    .map(|_| ext_allocator_malloc_version_1(size))
+                    .collect();
 
-    // Trie API
+                // Fill every buffer with its own pattern, then check none got overwritten
+                for (i, ptr) in ptrs.iter().enumerate() {
+                    assert!(*ptr != 0);
+                    std::ptr::write_bytes(*ptr as *mut u8, i as u8, size as usize);
+                }
+                for (i, ptr) in ptrs.iter().enumerate() {
+                    let buffer = std::slice::from_raw_parts(*ptr as *const u8, size as usize);
+                    if buffer.iter().any(|b| *b != i as u8) {
+                        return 0;
+                    }
+                }
 
-    fn rtm_ext_trie_blake2_256_root_version_1(data: Vec<(Vec<u8>, Vec<u8>)>) -> Vec<u8> {
-        let data = data.encode();
+                for ptr in ptrs {
+                    ext_allocator_free_version_1(ptr);
+                }
+            }
+        }
+        1
+    }
+    fn rtm_ext_allocator_exhaust_version_1(size: u32) -> u32 {
+        // Allocate until the host fails, returns count if it reports a null pointer
+        let mut count = 0;
+        unsafe {
+            while ext_allocator_malloc_version_1(size) != 0 {
+                count += 1;
+            }
+        }
+        count
+    }
+    fn rtm_ext_allocator_double_free_version_1(size: u32) {
         unsafe {
-            let value = ext_trie_blake2_256_root_version_1(
-                data.as_re_ptr()
+            let ptr = ext_allocator_malloc_version_1(size);
+            ext_allocator_free_version_1(ptr);
+            ext_allocator_free_version_1(ptr);
+        }
+    }
+    fn rtm_ext_allocator_fragment_version_1(sizes: Vec<u32>) -> u32 {
+        // Free every buffer only after the next one got allocated, returns peak extent of all buffers
+        let (mut start, mut end) = (u32::MAX, 0);
+        let mut previous = None;
+        unsafe {
+            for size in sizes {
+                let ptr = ext_allocator_malloc_version_1(size);
+                assert!(ptr != 0);
+                std::ptr::write_bytes(ptr as *mut u8, 0xff, size as usize);
+
+                start = start.min(ptr);
+                end = end.max(ptr + size);
+                if let Some(previous) = previous.replace(ptr) {
+                    ext_allocator_free_version_1(previous);
+                }
+            }
+        }
+        end.saturating_sub(start)
+    }
+
+    // Offchain API
+
+    fn rtm_ext_offchain_http_response_read_body_version_1(
+        request_id: u32,
+        buffer_size: u32, // buffer size to use in test
+        deadline: Option<u64>
+    ) -> (Vec<u8>, Vec<u8>) {
+        let buffer = vec![0u8; buffer_size as usize];
+        let deadline = deadline.encode();
+        unsafe {
+            let res = from_mem(ext_offchain_http_response_read_body_version_1(
+                request_id,
+                buffer.as_re_ptr(),
+                deadline.as_re_ptr()
+            ));
+            (res, buffer)
+        }
+    }
+
+    // Sandbox API
+
+    fn rtm_ext_sandbox_memory_new_version_1(
+        initial: u32,
+        maximum: u32,
+        offset: u32,
+        value: Vec<u8>
+    ) -> (u32, u32, Vec<u8>) {
+        let mut buffer = vec![0u8; value.len()];
+        unsafe {
+            let memory = ext_sandbox_memory_new_version_1(initial, maximum);
+
+            // Write `value` to memory and read it back
+            let set = ext_sandbox_memory_set_version_1(
+                memory,
+                offset,
+                value.as_ptr() as u32,
+                value.len() as u32
             );
-            std::slice::from_raw_parts(value as *mut u8, 32).to_vec()
+            let get = ext_sandbox_memory_get_version_1(
+                memory,
+                offset,
+                buffer.as_mut_ptr() as u32,
+                buffer.len() as u32
+            );
+
+            ext_sandbox_memory_teardown_version_1(memory);
+
+            (set, get, buffer)
         }
     }
-    fn rtm_ext_trie_blake2_256_ordered_root_version_1(data: Vec<Vec<u8>>) -> Vec<u8> {
-        let data = data.encode();
+
+    fn rtm_ext_sandbox_invoke_version_1(
+        code: Vec<u8>,
+        function: Vec<u8>,
+        maximum: u32,
+        state: Vec<u8>
+    ) -> (u32, Vec<u8>) {
+        // Environment provides the supervisor function and a memory
+        let memory = unsafe { ext_sandbox_memory_new_version_1(1, maximum) };
+        let env_def = vec![
+            (b"env".to_vec(), b"supervisor".to_vec(), 1u8, 0u32),
+            (b"env".to_vec(), b"memory".to_vec(), 2u8, memory),
+        ].encode();
+
+        // Empty argument list and buffer for encoded return value
+        let args = Vec::<u8>::new().encode();
+        let mut buffer = vec![0u8; 10];
+
         unsafe {
-            let value = ext_trie_blake2_256_ordered_root_version_1(
-                data.as_re_ptr()
+            let instance = ext_sandbox_instantiate_version_1(
+                sandbox_dispatch_thunk as usize as u32,
+                code.as_re_ptr(),
+                env_def.as_re_ptr(),
+                &state as *const Vec<u8> as u32
+            );
+            if instance == SANDBOX_ERR_MODULE || instance == SANDBOX_ERR_EXECUTION {
+                ext_sandbox_memory_teardown_version_1(memory);
+                return (instance, Vec::new());
+            }
+
+            let result = ext_sandbox_invoke_version_1(
+                instance,
+                function.as_re_ptr(),
+                args.as_re_ptr(),
+                buffer.as_mut_ptr() as u32,
+                buffer.len() as u32,
+                &state as *const Vec<u8> as u32
             );
-            std::slice::from_raw_parts(value as *mut u8, 32).to_vec()
+
+            ext_sandbox_instance_teardown_version_1(instance);
+            ext_sandbox_memory_teardown_version_1(memory);
+
+            // Trim buffer to encoded return value (unit, i32/f32 or i64/f64)
+            let len = match (result, buffer[0], buffer[1]) {
+                (0, 0, _) => 1,
+                (0, _, 0) | (0, _, 2) => 6,
+                (0, _, _) => 10,
+                _ => 0,
+            };
+            buffer.truncate(len);
+
+            (result, buffer)
         }
     }
 }
@@ -8,7 +8,7 @@
 include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
 
 use sp_std::prelude::*;
-use sp_core::{Decode, OpaqueMetadata};
+use sp_core::{Decode, OpaqueMetadata, hexdisplay::HexDisplay};
 use sp_runtime::{
 	generic, create_runtime_str, print, impl_opaque_keys,
 	ApplyExtrinsicResult, MultiSignature, KeyTypeId,
@@ -27,6 +27,8 @@ use sp_version::RuntimeVersion;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
 pub use balances::Call as BalancesCall;
+pub use sudo::Call as SudoCall;
+pub use system::Call as SystemCall;
 pub use sp_runtime::{Permill, Perbill};
 pub use frame_support::{
 	construct_runtime, parameter_types, storage_root, runtime_print,
@@ -88,6 +90,9 @@ pub mod opaque {
 }
 
 /// This runtime version.
+///
+/// Intentionally not embedded as `runtime_version` custom section, so hosts
+/// have to fall back to calling `Core_version` to discover it.
 pub const VERSION: RuntimeVersion = RuntimeVersion {
 	spec_name: create_runtime_str!("polkadot"),
 	impl_name: create_runtime_str!("host-tester"),
@@ -324,12 +329,50 @@ fn print_storage_root() {
 	runtime_print!("##{:x}##", storage_root);
 }
 
+/// Print all declared runtime apis and their versions
+fn print_runtime_apis() {
+	for (id, version) in RUNTIME_API_VERSIONS.iter() {
+		runtime_print!("%%{}:{}%%", HexDisplay::from(&id[..]), version);
+	}
+}
+
+
+/// Storage key written in the innermost of nested transactions, by `TesterApi`
+pub const TRANSACTIONAL_KEY: &[u8] = b":tester:transactional";
+
+sp_api::decl_runtime_apis! {
+	/// Entry points to test the host with FRAME logic, not called by nodes
+	#[api_version(2)]
+	pub trait TesterApi {
+		/// Nest storage transactions of FRAME to depth, writing the depth in the
+		/// innermost one
+		#[changed_in(2)]
+		fn nested_transactions(depth: u32);
+		/// Nest storage transactions of FRAME to depth, writing the depth in the
+		/// innermost one, returns the number of transactions entered
+		fn nested_transactions(depth: u32) -> u32;
+	}
+}
+
+/// Enter transaction and nest the remaining ones in it, all of them committed
+fn nest_transactions(depth: u32, level: u32) -> u32 {
+	if level == depth {
+		frame_support::storage::unhashed::put(TRANSACTIONAL_KEY, &depth);
+		return level;
+	}
+
+	frame_support::storage::with_transaction(|| {
+		frame_support::storage::TransactionOutcome::Commit(nest_transactions(depth, level + 1))
+	})
+}
+
 
 impl_runtime_apis! {
 	impl sp_api::Core<Block> for Runtime {
 		fn version() -> RuntimeVersion {
 			print("@@version()@@");
 			print_storage_root();
+			print_runtime_apis();
 			VERSION
 		}
 
@@ -472,4 +515,11 @@ impl_runtime_apis! {
 			None
 		}
 	} // grandpa
+
+	impl self::TesterApi<Block> for Runtime {
+		fn nested_transactions(depth: u32) -> u32 {
+			print("@@nested_transactions()@@");
+			nest_transactions(depth, 0)
+		}
+	} // tester
 }
@@ -4,7 +4,7 @@ use tester_runtime::{
 	GrandpaConfig, SudoConfig, SystemConfig, Signature,
 	BABE_GENESIS_EPOCH_CONFIG, WASM_BINARY,
 };
-use sc_service::{GenericChainSpec, ChainType};
+use sc_service::{GenericChainSpec, ChainType, Properties};
 use sp_runtime::traits::{Verify, IdentifyAccount};
 
 use sp_babe::AuthorityId as BabeId;
@@ -64,6 +64,16 @@ fn default_genesis_config() -> GenesisConfig {
 }
 
 
+/// Chain properties, checked to be reported unchanged by the hosts (see
+/// fixtures/chain-properties). Uses a non-default address format on purpose.
+fn default_properties() -> Properties {
+	let mut properties = Properties::new();
+	properties.insert("ss58Format".into(), 5.into());
+	properties.insert("tokenDecimals".into(), 9.into());
+	properties.insert("tokenSymbol".into(), "SPEC".into());
+	properties
+}
+
 /// Create default chain specification
 fn default_chain_spec() -> ChainSpec {
 	ChainSpec::from_genesis(
@@ -74,7 +84,7 @@ fn default_chain_spec() -> ChainSpec {
 		vec![], // Bootnodes
 		None,   // Telemetry
 		None,   // Protocol Id
-		None,   // Properties
+		Some(default_properties()),
 		None,   // Extensions
 	)
 }
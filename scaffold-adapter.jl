@@ -0,0 +1,264 @@
+#!/usr/bin/env julia
+
+# Generate stub adapter for a new implementation: Every test reports to be
+# not supported, which allows to run the whole testsuite against it right away.
+
+include("helpers/SpecificationTestsuite.jl")
+
+using .SpecificationTestsuite
+
+"Exit code of adapters for unsupported tests (EOPNOTSUPP)"
+const NOT_SUPPORTED = 95
+
+"Languages with stub templates: files relative to adapter folder and their content"
+const TEMPLATES = Dict(
+    "rust" => name -> [
+        "Cargo.toml" => """
+            [package]
+            name = "$name-adapter"
+            version = "0.1.0"
+            edition = "2018"
+
+            [dependencies]
+            """,
+        "src/main.rs" => """
+            use std::process::exit;
+
+            fn usage(program: &str) -> ! {
+                println!("usage: {} <scale-codec|state-trie|host-api> <subcommand-args>", program);
+                exit(1)
+            }
+
+            fn main() {
+                let args: Vec<String> = std::env::args().collect();
+
+                // Verify that a subcommand has been provided
+                if args.len() < 2 {
+                    usage(&args[0]);
+                }
+
+                // TODO: Implement subcommands, report missing ones as not supported
+                match args[1].as_str() {
+                    "healthcheck" => println!("ok"),
+                    "scale-codec" | "state-trie" | "host-api" => {
+                        println!("Not implemented: {}", args[1..].join(" "));
+                        exit($NOT_SUPPORTED)
+                    }
+                    _ => usage(&args[0]),
+                }
+            }
+            """,
+        "Makefile" => """
+            .PHONY: all build install clean
+
+            all: install
+
+            build:
+            \tcargo build --release
+
+            install: build
+            \tcp target/release/$name-adapter ../../bin/
+
+            clean:
+            \trm -rf target
+            """,
+    ],
+    "go" => name -> [
+        "go.mod" => """
+            module w3f/$name-adapter
+
+            go 1.15
+            """,
+        "main.go" => """
+            package main
+
+            import (
+            \t"fmt"
+            \t"os"
+            \t"strings"
+            )
+
+            func usage() {
+            \tfmt.Println("usage: ", os.Args[0], " <scale-codec|state-trie|host-api> <subcommand-args>")
+            }
+
+            func main() {
+            \t// Verify that a subcommand has been provided
+            \tif len(os.Args) < 2 {
+            \t\tusage()
+            \t\tos.Exit(1)
+            \t}
+
+            \t// TODO: Implement subcommands, report missing ones as not supported
+            \tswitch os.Args[1] {
+            \tcase "healthcheck":
+            \t\tfmt.Println("ok")
+            \tcase "scale-codec", "state-trie", "host-api":
+            \t\tfmt.Println("Not implemented: ", strings.Join(os.Args[1:], " "))
+            \t\tos.Exit($NOT_SUPPORTED)
+            \tdefault:
+            \t\tusage()
+            \t\tos.Exit(1)
+            \t}
+            }
+            """,
+        "Makefile" => """
+            .PHONY: all build install clean
+
+            all: install
+
+            build:
+            \tgo build -v
+
+            install: build
+            \tcp $name-adapter ../../bin/
+
+            clean:
+            \trm -f $name-adapter
+            """,
+    ],
+    "c++" => name -> [
+        "CMakeLists.txt" => """
+            cmake_minimum_required(VERSION 3.12)
+
+            project($name-adapter LANGUAGES CXX)
+
+            set(CMAKE_CXX_STANDARD 17)
+
+            add_executable($name-adapter src/main.cpp)
+            """,
+        "src/main.cpp" => """
+            #include <iostream>
+            #include <set>
+            #include <string>
+
+            void usage(const char *program) {
+              std::cout << "usage: " << program
+                        << " <scale-codec|state-trie|host-api> <subcommand-args>\\n";
+            }
+
+            int main(int argc, char **argv) {
+              // Verify that a subcommand has been provided
+              if (argc < 2) {
+                usage(argv[0]);
+                return 1;
+              }
+
+              if (argv[1] == std::string("healthcheck")) {
+                std::cout << "ok\\n";
+                return 0;
+              }
+
+              // TODO: Implement subcommands, report missing ones as not supported
+              const std::set<std::string> subcommands = {
+                  "scale-codec", "state-trie", "host-api"};
+              if (subcommands.count(argv[1]) == 0) {
+                usage(argv[0]);
+                return 1;
+              }
+
+              std::cout << "Not implemented:";
+              for (int i = 1; i < argc; i++) {
+                std::cout << " " << argv[i];
+              }
+              std::cout << "\\n";
+              return $NOT_SUPPORTED;
+            }
+            """,
+        "Makefile" => """
+            .PHONY: all build install clean
+
+            all: install
+
+            build:
+            \tcmake -S . -B build
+            \tcmake --build build
+
+            install: build
+            \tcp build/$name-adapter ../../bin/
+
+            clean:
+            \trm -rf build
+            """,
+    ],
+)
+
+
+function print_usage()
+    println("usage: $PROGRAM_FILE --lang <LANGUAGE> <IMPLEMENTATION>")
+    println()
+    println("Generates a stub adapter in adapters/<IMPLEMENTATION>, which reports")
+    println("all tests as not supported.")
+    println()
+    println("LANGUAGES:")
+    println(join(sort(collect(keys(TEMPLATES))), ", "))
+end
+
+
+# Process all command line arguments
+lang = nothing
+name = nothing
+
+args = Iterators.Stateful(ARGS)
+for arg in args
+    if arg == "--help"
+        print_usage()
+        exit()
+    end
+
+    if arg == "--lang"
+        global lang = isempty(args) ? nothing : popfirst!(args)
+        if !haskey(TEMPLATES, lang)
+            println("Missing or unknown language for --lang")
+            println()
+            print_usage()
+            exit(1)
+        end
+        continue
+    end
+
+    if name == nothing && occursin(r"^[a-z][a-z0-9_-]*$", arg)
+        global name = arg
+        continue
+    end
+
+    println("Unknown argument: ", arg)
+    println()
+    print_usage()
+    exit(1)
+end
+
+if lang == nothing || name == nothing
+    print_usage()
+    exit(1)
+end
+
+if name in ALL_IMPLEMENTATIONS
+    println("Implementation already known: $name")
+    exit(1)
+end
+
+# Generate adapter from template
+dir = "$(@__DIR__)/adapters/$name"
+if ispath(dir)
+    println("Adapter already exists: adapters/$name")
+    exit(1)
+end
+
+for (file, content) in TEMPLATES[lang](name)
+    path = joinpath(dir, file)
+    mkpath(dirname(path))
+    write(path, content)
+    println("Created adapters/$name/$file")
+end
+
+# Capability manifest and implementation list are part of the testsuite config
+println()
+println("To add '$name' to the testsuite, extend helpers/SpecificationTestsuite.jl:")
+println()
+println("  ALL_IMPLEMENTATIONS:  \"$name\"")
+println("  Config.capabilities:  \"$name\" => [],")
+println()
+println("and build it with `make -C adapters/$name`, which installs it into bin.")
+println("Optional capabilities ($(join(ALL_CAPABILITIES, ", "))) should only be added to")
+println("the manifest once the adapter supports them.")
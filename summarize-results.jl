@@ -0,0 +1,155 @@
+#!/usr/bin/env julia
+
+# Conformance summary per implementation: Condense the results written by
+# `./runtests.jl --results <FILE>` into one summary per implementation (pass
+# counts per category, spec coverage and catalogue version), together with a
+# badge description to be published by implementation teams.
+
+include("helpers/SpecificationTestsuite.jl")
+
+using .SpecificationTestsuite.StatusServer: json_string
+
+
+function print_usage()
+    println("usage: $PROGRAM_FILE <RESULTS> <DIR>")
+    println()
+    println("Writes for every implementation in the results file:")
+    println("  <DIR>/<IMPLEMENTATION>.json        Summary with pass counts per category (fixture),")
+    println("                                     coverage of the catalogue and its version.")
+    println("  <DIR>/<IMPLEMENTATION>.badge.json  Badge in the shields.io endpoint format.")
+end
+
+
+"Parse JSON value at position i of text, returns value and next position"
+function parse_json(text::String, i::Int=1)
+    while isspace(text[i])
+        i = nextind(text, i)
+    end
+
+    c = text[i]
+    if c == '{'
+        value, i = Dict{String,Any}(), i + 1
+        while true
+            i = findnext(!isspace, text, i)
+            if text[i] == '}'
+                return value, i + 1
+            end
+            key, i = parse_json(text, i)
+            i = findnext(==(':'), text, i) + 1
+            value[key], i = parse_json(text, i)
+            i = findnext(!isspace, text, i)
+            i = text[i] == ',' ? i + 1 : i
+        end
+    elseif c == '['
+        value, i = Any[], i + 1
+        while true
+            i = findnext(!isspace, text, i)
+            if text[i] == ']'
+                return value, i + 1
+            end
+            element, i = parse_json(text, i)
+            push!(value, element)
+            i = findnext(!isspace, text, i)
+            i = text[i] == ',' ? i + 1 : i
+        end
+    elseif c == '"'
+        value, i = IOBuffer(), i + 1
+        while text[i] != '"'
+            if text[i] == '\\'
+                escaped = text[i+1]
+                if escaped == 'u'
+                    write(value, Char(parse(UInt16, text[i+2:i+5], base=16)))
+                    i += 6
+                else
+                    write(value, get(Dict('n' => '\n', 't' => '\t', 'r' => '\r'), escaped, escaped))
+                    i += 2
+                end
+            else
+                write(value, text[i])
+                i = nextind(text, i)
+            end
+        end
+        return String(take!(value)), i + 1
+    else
+        m = match(r"^(true|false|null|-?[0-9.eE+-]+)", SubString(text, i))
+        m == nothing && error("Invalid JSON at position $i")
+        token = m.captures[1]
+        value = token == "true" ? true : token == "false" ? false : token == "null" ? nothing : parse(Float64, token)
+        return value, i + length(token)
+    end
+end
+
+"Percentage of part in total, rounded to one decimal"
+percentage(part, total) = total == 0 ? 0.0 : round(100 * part / total, digits=1)
+
+"Color of badge by pass rate and failures"
+function badge_color(failed::Int, rate::Float64)::String
+    return failed == 0 ? "brightgreen" : rate >= 90 ? "yellow" : rate >= 50 ? "orange" : "red"
+end
+
+"Write summary and badge of every implementation in the results to directory"
+function summarize(results::Dict, dir::String)
+    mkpath(dir)
+
+    implementations = unique(map(r -> r["implementation"], results["results"]))
+    for implementation in sort(implementations)
+        entries = filter(r -> r["implementation"] == implementation, results["results"])
+        sort!(entries, by=r -> r["fixture"])
+
+        sum_of(key) = Int(sum(r -> r[key], entries))
+        passed, failed, skipped = sum_of("passed"), sum_of("failed"), sum_of("skipped")
+        total = passed + failed + skipped
+
+        # Coverage of the catalogue are all tests which are not skipped (i.e.
+        # are supported), the pass rate is relative to these
+        coverage = percentage(passed + failed, total)
+        rate = percentage(passed, passed + failed)
+
+        categories = map(entries) do r
+            """    {"category": $(json_string(r["fixture"])), "passed": $(Int(r["passed"])), """ *
+            """"failed": $(Int(r["failed"])), "skipped": $(Int(r["skipped"]))}"""
+        end
+
+        spec_version = isempty(entries) ? results["spec_version"] : first(entries)["spec_version"]
+
+        write(joinpath(dir, "$implementation.json"), """{
+          "implementation": $(json_string(implementation)),
+          "catalogue": $(json_string(results["catalogue"])),
+          "spec_version": $(json_string(spec_version)),
+          "passed": $passed,
+          "failed": $failed,
+          "skipped": $skipped,
+          "coverage": $coverage,
+          "pass_rate": $rate,
+          "categories": [
+        $(join(categories, ",\n"))
+          ]
+        }
+        """)
+
+        write(joinpath(dir, "$implementation.badge.json"), """{
+          "schemaVersion": 1,
+          "label": $(json_string("polkadot conformance")),
+          "message": $(json_string("$passed/$(passed + failed) passed, $coverage% coverage")),
+          "color": $(json_string(badge_color(failed, rate)))
+        }
+        """)
+
+        println("$implementation: $passed passed, $failed failed, $skipped skipped ($coverage% coverage)")
+    end
+end
+
+
+# Process all command line arguments
+if length(ARGS) == 1 && ARGS[1] == "--help"
+    print_usage()
+    exit(0)
+end
+
+if length(ARGS) != 2
+    print_usage()
+    exit(1)
+end
+
+results, _ = parse_json(read(ARGS[1], String))
+summarize(results, ARGS[2])
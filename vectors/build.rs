@@ -0,0 +1,170 @@
+// Copyright (c) 2019 Web3 Technologies Foundation
+
+// This file is part of Polkadot Host Test Suite
+
+// Polkadot Host Test Suite is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot Host Tests is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot Host Test Suite.  If not, see <https://www.gnu.org/licenses/>.
+
+///Generates the static vector data from the export of the testsuite
+/// (`./runtests.jl --export-vectors <DIR>`), read from the directory in
+/// POLKADOT_HOST_TEST_VECTORS or `data` next to this file. Without an export
+/// no testsuites are embedded.
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Sorted subdirectories of directory
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)
+        .expect("Failed to read vector directory")
+        .map(|e| e.expect("Failed to read vector directory").path())
+        .filter(|p| p.is_dir())
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn read(path: &Path) -> Option<String> {
+    fs::read(path).ok().map(|data| String::from_utf8_lossy(&data).into_owned())
+}
+
+/// Hex encoded data as byte slice literal
+fn bytes(hex: &str) -> String {
+    assert!(hex.len() % 2 == 0, "Invalid hex encoding: {}", hex);
+    let bytes: Vec<String> = (0..hex.len())
+        .step_by(2)
+        .map(|i| format!("{:#04x}", u8::from_str_radix(&hex[i..i + 2], 16).expect("Invalid hex encoding")))
+        .collect();
+    format!("&[{}]", bytes.join(","))
+}
+
+/// Blobs called by vectors, as static name by path of the exported blob
+type Runtimes = BTreeMap<PathBuf, String>;
+
+fn vector(dir: &Path, runtimes: &mut Runtimes) -> String {
+    let args = read(&dir.join("args")).expect("Vector without args, exported by an older testsuite?");
+    let args: Vec<String> = args.split('\n').map(|a| format!("{:?}", a)).collect();
+
+    // Expected outputs are either exact or patterns, optionally per spec version
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.file_name().unwrap().to_string_lossy().starts_with("expected"))
+        .collect();
+    files.sort();
+    let mut expected = String::new();
+    for file in files {
+        let name = file.file_name().unwrap().to_string_lossy().into_owned();
+        let (name, pattern) = match name.strip_suffix(".pattern") {
+            Some(name) => (name.to_string(), true),
+            None => (name, false),
+        };
+        let version = match name.strip_prefix("expected@") {
+            Some(version) => format!("Some({:?})", version),
+            None => String::from("None"),
+        };
+        let output = format!("{}({:?})", if pattern { "Pattern" } else { "Exact" }, read(&file).unwrap());
+        write!(expected, "Expected{{version:{},output:Output::{}}},", version, output).unwrap();
+    }
+
+    let mut state = String::new();
+    for line in read(&dir.join("state")).unwrap_or_default().lines() {
+        let parts: Vec<&str> = line.split(' ').collect();
+        match parts.as_slice() {
+            ["top", key, value] => write!(state, "Entry{{child:None,key:{},value:{}}},", bytes(key), bytes(value)),
+            ["child", child, key, value] => write!(
+                state,
+                "Entry{{child:Some({}),key:{},value:{}}},",
+                bytes(child),
+                bytes(key),
+                bytes(value)
+            ),
+            _ => panic!("Invalid state entry: {}", line),
+        }
+        .unwrap();
+    }
+
+    // Blobs called are shared by all vectors of the testsuite
+    let mut calls = String::new();
+    for line in read(&dir.join("calls")).unwrap_or_default().lines() {
+        let parts: Vec<&str> = line.split(' ').collect();
+        let (entry, input, output, blob) = match parts.as_slice() {
+            [entry, input, output, blob] => (entry, bytes(input), output, blob),
+            [_, _, _] => panic!("Call without blob, exported by an older testsuite?"),
+            _ => panic!("Invalid call: {}", line),
+        };
+        let output = match *output {
+            "trap" => String::from("None"),
+            output => format!("Some({})", bytes(output)),
+        };
+        let path = dir.parent().unwrap().join("runtimes").join(blob);
+        let count = runtimes.len();
+        let runtime = runtimes.entry(path).or_insert_with(|| format!("RUNTIME_{}", count));
+        write!(calls, "Call{{entry:{:?},input:{},output:{},runtime:{}}},", entry, input, output, runtime).unwrap();
+    }
+
+    format!(
+        "Vector{{args:&[{}],expected:&[{}],state:&[{}],calls:&[{}]}}",
+        args.join(","),
+        expected,
+        state,
+        calls
+    )
+}
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=POLKADOT_HOST_TEST_VECTORS");
+
+    let dir = match env::var("POLKADOT_HOST_TEST_VECTORS") {
+        Ok(dir) => PathBuf::from(dir),
+        Err(_) => Path::new(&env::var("CARGO_MANIFEST_DIR").unwrap()).join("data"),
+    };
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    // Without an export the crate still builds, e.g. as dependency of a workspace
+    let suites = if dir.is_dir() {
+        subdirs(&dir)
+    } else {
+        println!(
+            "cargo:warning=No exported vectors in {}, run `make data` or set POLKADOT_HOST_TEST_VECTORS",
+            dir.display()
+        );
+        Vec::new()
+    };
+
+    let mut runtimes = Runtimes::new();
+    let mut out = String::from("/// All exported testsuites\npub static TESTSUITES: &[Testsuite] = &[\n");
+    for suite in suites {
+        let name = suite.file_name().unwrap().to_string_lossy().into_owned();
+        let vectors: Vec<String> = subdirs(&suite)
+            .iter()
+            .filter(|v| v.file_name().unwrap() != "runtimes")
+            .map(|v| vector(v, &mut runtimes))
+            .collect();
+        writeln!(out, "Testsuite{{name:{:?},vectors:&[\n{}\n]}},", name, vectors.join(",\n")).unwrap();
+    }
+    out.push_str("];\n");
+
+    // Every blob is embedded once, even if called by several testsuites
+    for (path, runtime) in &runtimes {
+        let path = path
+            .canonicalize()
+            .unwrap_or_else(|_| panic!("Blob {} not exported", path.display()));
+        writeln!(out, "static {}: &[u8] = include_bytes!({:?});", runtime, path).unwrap();
+    }
+
+    let dest = Path::new(&env::var("OUT_DIR").unwrap()).join("vectors.rs");
+    fs::write(dest, out).expect("Failed to write vectors");
+}
@@ -0,0 +1,23 @@
+//! Fuzz an adapter binary with the arguments of each input, starting from the
+//! corpus exported by `./runtests.jl --export-corpus corpus/adapter`. The
+//! adapter is taken from `POLKADOT_HOST_ADAPTER` (`substrate-adapter` by
+//! default), only it being killed by a signal is reported as crash, as
+//! adapters exit with an error on invalid arguments.
+#![no_main]
+
+use std::process::Command;
+
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    polkadot_host_test_vectors::fuzz(data, |args| {
+        let adapter = std::env::var("POLKADOT_HOST_ADAPTER").unwrap_or_else(|_| "substrate-adapter".into());
+        let output = Command::new(adapter)
+            .args(args)
+            .output()
+            .expect("Failed to run adapter");
+
+        assert!(output.status.code().is_some(), "Adapter was killed: {}", output.status);
+        String::from_utf8_lossy(&output.stdout).into_owned()
+    });
+});
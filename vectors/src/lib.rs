@@ -0,0 +1,159 @@
+// Copyright (c) 2019 Web3 Technologies Foundation
+
+// This file is part of Polkadot Host Test Suite
+
+// Polkadot Host Test Suite is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// Polkadot Host Tests is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with Polkadot Host Test Suite.  If not, see <https://www.gnu.org/licenses/>.
+
+//! Test vectors of the Polkadot Host Test Suite, to be used directly in the
+//! unit tests of implementations. The vectors are embedded at build time from
+//! the export of the testsuite (see `build.rs`), which stays the only place
+//! where they are defined. Built without an export, no testsuites are embedded
+//! (which the build script warns about).
+//!
+//! Host API vectors can be checked without an adapter, by running the
+//! recorded calls against the blob they were made into:
+//!
+//! ```ignore
+//! let suite = polkadot_host_test_vectors::testsuite("host-api").unwrap();
+//! for vector in suite.vectors {
+//!     let mut host = MyHost::with_state(vector.state);
+//!     for call in vector.calls {
+//!         assert_eq!(host.call(call.runtime, call.entry, call.input).ok(), call.output);
+//!     }
+//! }
+//! ```
+#![no_std]
+
+/// Testsuite with all of its exported vectors
+pub struct Testsuite {
+    /// Name of the testsuite, as used for its export directory (e.g. `host-api`)
+    pub name: &'static str,
+    /// All vectors of the testsuite, in order of definition
+    pub vectors: &'static [Vector],
+}
+
+/// Single test vector, i.e. one invocation of an adapter
+pub struct Vector {
+    /// Arguments of the adapter (e.g. `host-api`, `--function`, ...)
+    pub args: &'static [&'static str],
+    /// Expected outputs of the adapter, more than one if versioned
+    pub expected: &'static [Expected],
+    /// Initial state of host api vectors, the genesis for tests of the tester runtime
+    pub state: &'static [Entry],
+    /// Calls made into the test runtime by host api vectors, as recorded by
+    /// the reference implementation
+    pub calls: &'static [Call],
+}
+
+/// Expected output of an adapter
+pub struct Expected {
+    /// Spec version from which on the output applies, if versioned
+    pub version: Option<&'static str>,
+    /// Exact output or pattern with tolerance expressions
+    pub output: Output,
+}
+
+/// Output as compared by the testsuite
+pub enum Output {
+    /// Output has to match exactly
+    Exact(&'static str),
+    /// Output has to match pattern (see `helpers/OutputPattern.jl`)
+    Pattern(&'static str),
+}
+
+/// Storage entry of the initial state
+pub struct Entry {
+    /// Storage key of child storage (without prefix), none for top storage
+    pub child: Option<&'static [u8]>,
+    pub key: &'static [u8],
+    pub value: &'static [u8],
+}
+
+/// Call into the test runtime
+pub struct Call {
+    /// Exported function of the runtime (e.g. `rtm_ext_storage_set_version_1`)
+    pub entry: &'static str,
+    /// SCALE encoded arguments
+    pub input: &'static [u8],
+    /// SCALE encoded result, none if the call trapped
+    pub output: Option<&'static [u8]>,
+    /// Wasm blob called, e.g. the test runtime or the shim of a raw call
+    pub runtime: &'static [u8],
+}
+
+impl Testsuite {
+    /// Vectors calling a host api function (or test) of the given name
+    pub fn function<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'static Vector> + 'a {
+        self.vectors.iter().filter(move |v| v.arg("--function") == Some(name))
+    }
+}
+
+impl Vector {
+    /// Value of a named argument (e.g. `--function`), if given
+    pub fn arg(&self, name: &str) -> Option<&'static str> {
+        let i = self.args.iter().position(|a| *a == name)?;
+        self.args.get(i + 1).copied()
+    }
+}
+
+/// Arguments of a fuzzing input, one per line as in the corpus exported with
+/// `./runtests.jl --export-corpus <DIR>`
+pub type Args<'a> = core::str::Split<'a, char>;
+
+/// Harness wrapping the vectors for fuzzers, compatible with `fuzz_target!`
+/// of libFuzzer (and AFL), e.g.:
+///
+/// ```ignore
+/// fuzz_target!(|data: &[u8]| {
+///     polkadot_host_test_vectors::fuzz(data, |args| my_adapter(args));
+/// });
+/// ```
+///
+/// The input is split into adapter arguments, which are passed to `adapter`
+/// (inputs which are not valid utf8 are ignored). Panics of the adapter are
+/// reported as crashes by the fuzzer. If the arguments are those of an
+/// exported vector, its output additionally has to match exactly (outputs
+/// checked by pattern or per spec version are not compared).
+pub fn fuzz<'a, F, O>(data: &'a [u8], adapter: F)
+where
+    F: FnOnce(Args<'a>) -> O,
+    O: AsRef<str>,
+{
+    let input = match core::str::from_utf8(data) {
+        Ok(input) => input,
+        Err(_) => return,
+    };
+    let args = input.split('\n');
+
+    let expected = TESTSUITES
+        .iter()
+        .flat_map(|t| t.vectors.iter())
+        .find(|v| args.clone().eq(v.args.iter().copied()))
+        .and_then(|v| match v.expected {
+            [Expected { version: None, output: Output::Exact(output) }] => Some(*output),
+            _ => None,
+        });
+
+    let output = adapter(args);
+    if let Some(expected) = expected {
+        assert_eq!(output.as_ref(), expected, "Output differs from vector");
+    }
+}
+
+include!(concat!(env!("OUT_DIR"), "/vectors.rs"));
+
+/// Testsuite of the given name, if it was exported
+pub fn testsuite(name: &str) -> Option<&'static Testsuite> {
+    TESTSUITES.iter().find(|t| t.name == name)
+}